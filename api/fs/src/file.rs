@@ -44,6 +44,9 @@ impl File<api::Default> {
 
 
 impl<Api: api::Api> File<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+
 	/// Attempts to open a file in read-only mode.
 	///
 	/// See the [`OpenOptions::open`] method and [official docs][docs] for more details.
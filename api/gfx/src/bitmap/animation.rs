@@ -0,0 +1,353 @@
+//! Frame animation over the [`BitmapTable`].
+//!
+//! ```ignore
+//! let table = BitmapTable::load("images/walk")?;
+//! let mut walk = Animation::new(table, Duration::from_millis(100)).with_mode(LoopMode::PingPong);
+//! walk.on_event(|event| println!("{event:?}"));
+//!
+//! // in the update callback:
+//! walk.update(System::Default().elapsed_time());
+//! walk.draw(x, y, BitmapFlip::kBitmapUnflipped);
+//! ```
+
+use core::ffi::c_int;
+use core::time::Duration;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::BitmapFlip;
+use super::BitmapRef;
+use super::table::BitmapTable;
+use super::table::api as table_api;
+
+
+/// Table of the animation frames.
+///
+/// Implemented for owned and borrowed [`BitmapTable`].
+pub trait FrameTable {
+	/// Returns the number of frames.
+	fn count(&self) -> c_int;
+
+	/// Returns the frame at `index`, or `None` if it is out of bounds.
+	fn frame(&self, index: c_int) -> Option<BitmapRef<'_>>;
+}
+
+impl<Api: table_api::Api, const FOD: bool> FrameTable for BitmapTable<Api, FOD> {
+	fn count(&self) -> c_int { BitmapTable::count(self) }
+	fn frame(&self, index: c_int) -> Option<BitmapRef<'_>> { self.bitmap(index) }
+}
+
+impl<T: FrameTable> FrameTable for &'_ T {
+	fn count(&self) -> c_int { (*self).count() }
+	fn frame(&self, index: c_int) -> Option<BitmapRef<'_>> { (*self).frame(index) }
+}
+
+
+/// What happens when the animation reaches its last frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LoopMode {
+	/// Starts over from the first frame.
+	#[default]
+	Loop,
+	/// Plays backward to the first frame, then forward again.
+	PingPong,
+	/// Stops at the last frame.
+	Once,
+}
+
+
+/// Event of the [`Animation`], see [`Animation::on_event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Event {
+	/// Current frame changed to the frame with the index.
+	Frame(usize),
+	/// Animation returned to the first frame and starts next cycle.
+	Looped,
+	/// Animation in [`LoopMode::Once`] finished at its last frame.
+	Finished,
+}
+
+
+/// Animation player over the table of frames, owned or borrowed.
+///
+/// Animation is driven by [`advance`](Animation::advance) with time passed since the last call,
+/// or by [`update`](Animation::update) with the current time,
+/// e.g. [`System::elapsed_time`](https://docs.rs/playdate-system).
+pub struct Animation<T: FrameTable = BitmapTable> {
+	table: T,
+	durations: Vec<Duration>,
+	mode: LoopMode,
+	frame: usize,
+	backward: bool,
+	/// Time spent in the current frame.
+	elapsed: Duration,
+	paused: bool,
+	finished: bool,
+	/// Time of the last [`update`](Animation::update).
+	last: Option<Duration>,
+	on_event: Option<Box<dyn FnMut(Event)>>,
+}
+
+
+// ctor //
+
+impl<T: FrameTable> Animation<T> {
+	/// Creates animation of all frames in the `table`, each shown for `duration`.
+	pub fn new(table: T, duration: Duration) -> Self {
+		let count = table.count().max(0) as usize;
+		Self::with_durations_unchecked(table, vec![duration; count])
+	}
+
+	/// Creates animation of frames in the `table` with `durations` of each frame.
+	///
+	/// Returns `None` if number of durations is not the number of frames.
+	pub fn with_durations(table: T, durations: Vec<Duration>) -> Option<Self> {
+		if durations.len() != table.count().max(0) as usize {
+			return None;
+		}
+		Some(Self::with_durations_unchecked(table, durations))
+	}
+
+	fn with_durations_unchecked(table: T, durations: Vec<Duration>) -> Self {
+		Self { table,
+		       durations,
+		       mode: LoopMode::default(),
+		       frame: 0,
+		       backward: false,
+		       elapsed: Duration::ZERO,
+		       paused: false,
+		       finished: false,
+		       last: None,
+		       on_event: None }
+	}
+
+	/// Sets the loop mode.
+	pub fn with_mode(mut self, mode: LoopMode) -> Self {
+		self.mode = mode;
+		self
+	}
+
+	/// Returns the table of frames.
+	pub fn into_table(self) -> T { self.table }
+}
+
+
+// utils //
+
+impl<T: FrameTable> Animation<T> {
+	pub fn table(&self) -> &T { &self.table }
+
+	pub const fn mode(&self) -> LoopMode { self.mode }
+	pub fn set_mode(&mut self, mode: LoopMode) { self.mode = mode }
+
+	/// Returns the number of frames.
+	pub fn len(&self) -> usize { self.durations.len() }
+	pub fn is_empty(&self) -> bool { self.durations.is_empty() }
+
+	/// Returns durations of frames.
+	pub fn durations(&self) -> &[Duration] { &self.durations }
+
+	/// Sets duration of the frame at `index`, frames out of bounds are ignored.
+	pub fn set_duration(&mut self, index: usize, duration: Duration) {
+		if let Some(d) = self.durations.get_mut(index) {
+			*d = duration
+		}
+	}
+
+	/// Returns total duration of frames.
+	pub fn total_duration(&self) -> Duration { self.durations.iter().sum() }
+
+	/// Returns index of the current frame.
+	pub const fn frame_index(&self) -> usize { self.frame }
+
+	/// Returns the current frame.
+	pub fn frame(&self) -> Option<BitmapRef<'_>> { self.table.frame(self.frame as _) }
+
+	pub const fn is_paused(&self) -> bool { self.paused }
+
+	/// Returns `true` if the animation in [`LoopMode::Once`] reached the end of its last frame.
+	pub const fn is_finished(&self) -> bool { self.finished }
+
+	/// Sets the callback called on every [`Event`] of the animation.
+	pub fn on_event<F: FnMut(Event) + 'static>(&mut self, f: F) { self.on_event = Some(Box::new(f)) }
+
+	/// Removes the callback set with [`on_event`](Animation::on_event).
+	pub fn clear_on_event(&mut self) { self.on_event = None }
+}
+
+
+// impl //
+
+impl<T: FrameTable> Animation<T> {
+	/// Pauses the animation, time passed to [`advance`](Animation::advance) is ignored.
+	pub fn pause(&mut self) { self.paused = true }
+
+	/// Resumes the paused animation.
+	///
+	/// Time between the pause and the next [`update`](Animation::update) is skipped.
+	pub fn resume(&mut self) {
+		self.paused = false;
+		self.last = None;
+	}
+
+	/// Starts the animation over from the first frame.
+	pub fn reset(&mut self) { self.seek(0) }
+
+	/// Jumps to the start of the frame at `index`, clamped to the last frame.
+	pub fn seek(&mut self, index: usize) {
+		self.frame = index.min(self.durations.len().saturating_sub(1));
+		self.elapsed = Duration::ZERO;
+		self.backward = false;
+		self.finished = false;
+	}
+
+	/// Advances the animation to the current time `now`,
+	/// by time passed since the previous call.
+	///
+	/// The first call only remembers the time.
+	///
+	/// Returns `true` if the current frame changed.
+	pub fn update(&mut self, now: Duration) -> bool {
+		let dt = self.last.map_or(Duration::ZERO, |last| now.saturating_sub(last));
+		self.last = Some(now);
+		self.advance(dt)
+	}
+
+	/// Advances the animation by `dt`, firing events for every passed frame.
+	///
+	/// Returns `true` if the current frame changed.
+	pub fn advance(&mut self, dt: Duration) -> bool {
+		if self.paused || self.finished || self.total_duration().is_zero() {
+			return false;
+		}
+
+		let start = self.frame;
+		self.elapsed += dt;
+		while self.elapsed >= self.durations[self.frame] {
+			self.elapsed -= self.durations[self.frame];
+			if !self.step() {
+				self.elapsed = Duration::ZERO;
+				break;
+			}
+		}
+		self.frame != start
+	}
+
+	/// Switches to the next frame by the mode.
+	///
+	/// Returns `false` if the animation finished.
+	fn step(&mut self) -> bool {
+		let last = self.durations.len() - 1;
+		match self.mode {
+			LoopMode::Loop if self.frame == last => {
+				self.frame = 0;
+				self.emit(Event::Looped);
+			},
+			LoopMode::Once if self.frame == last => {
+				self.finished = true;
+				self.emit(Event::Finished);
+				return false;
+			},
+			LoopMode::PingPong if self.backward || self.frame == last => {
+				self.backward = self.frame != 0 && last != 0;
+				if self.backward {
+					self.frame -= 1;
+				} else {
+					self.frame = (last > 0) as usize;
+					self.emit(Event::Looped);
+				}
+			},
+			_ => self.frame += 1,
+		}
+		self.emit(Event::Frame(self.frame));
+		true
+	}
+
+	fn emit(&mut self, event: Event) {
+		if let Some(f) = self.on_event.as_mut() {
+			f(event)
+		}
+	}
+
+	/// Draws the current frame with its upper-left corner at `x`, `y`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_graphics::drawBitmap`].
+	#[doc(alias = "sys::ffi::playdate_graphics::drawBitmap")]
+	pub fn draw(&self, x: c_int, y: c_int, flip: BitmapFlip) {
+		if let Some(frame) = self.frame() {
+			crate::Graphics::Default().draw(&frame, x, y, flip)
+		}
+	}
+}
+
+impl<T: FrameTable> core::fmt::Debug for Animation<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Animation")
+		 .field("len", &self.len())
+		 .field("mode", &self.mode)
+		 .field("frame", &self.frame)
+		 .field("paused", &self.paused)
+		 .field("finished", &self.finished)
+		 .finish()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::rc::Rc;
+	use core::cell::RefCell;
+
+
+	struct Frames(c_int);
+
+	impl FrameTable for Frames {
+		fn count(&self) -> c_int { self.0 }
+		fn frame(&self, _: c_int) -> Option<BitmapRef<'_>> { None }
+	}
+
+	const MS: Duration = Duration::from_millis(1);
+
+	fn play(mode: LoopMode, steps: usize) -> Vec<usize> {
+		let mut anim = Animation::new(Frames(3), MS * 10).with_mode(mode);
+		(0..steps).map(|_| {
+			          anim.advance(MS * 10);
+			          anim.frame_index()
+		          })
+		          .collect()
+	}
+
+
+	#[test]
+	fn loop_modes() {
+		assert_eq!(play(LoopMode::Loop, 5), [1, 2, 0, 1, 2]);
+		assert_eq!(play(LoopMode::PingPong, 6), [1, 2, 1, 0, 1, 2]);
+		assert_eq!(play(LoopMode::Once, 4), [1, 2, 2, 2]);
+	}
+
+	#[test]
+	fn timing_and_events() {
+		let events = Rc::new(RefCell::new(Vec::new()));
+		let mut anim = Animation::with_durations(Frames(2), vec![MS * 10, MS * 30]).unwrap()
+		                                                                           .with_mode(LoopMode::Once);
+		let sink = events.clone();
+		anim.on_event(move |event| sink.borrow_mut().push(event));
+
+		assert!(!anim.update(MS * 100));
+		assert!(!anim.update(MS * 105));
+		assert!(anim.update(MS * 112));
+		anim.pause();
+		assert!(!anim.advance(MS * 100));
+		anim.resume();
+		assert!(!anim.update(MS * 500));
+		assert!(!anim.update(MS * 540));
+		assert!(anim.is_finished());
+		assert_eq!(*events.borrow(), [Event::Frame(1), Event::Finished]);
+
+		anim.reset();
+		assert_eq!((anim.frame_index(), anim.is_finished()), (0, false));
+		assert!(Animation::with_durations(Frames(2), vec![MS]).is_none());
+	}
+}
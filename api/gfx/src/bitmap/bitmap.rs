@@ -20,6 +20,10 @@ use sys::ffi::LCDBitmap;
 use fs::Path;
 
 use crate::Graphics;
+#[cfg(feature = "transform")]
+use crate::geometry::Affine2D;
+#[cfg(feature = "transform")]
+use crate::geometry::Point;
 use crate::error::ApiError;
 use crate::error::Error;
 use super::api;
@@ -464,6 +468,41 @@ impl<Api: api::Api, const FOD: bool> Bitmap<Api, FOD> {
 		unsafe { f(self.0, x, y, x_scale, y_scale) }
 	}
 
+	/// Draws this bitmap mapped by the `transform` from its own coordinates to the screen.
+	///
+	/// If the transform is scale and rotation only, the bitmap is drawn by the system with
+	/// [`draw`](Bitmap::draw), [`draw_scaled`](Bitmap::draw_scaled) or [`draw_rotated`](Bitmap::draw_rotated),
+	/// translation is rounded to whole pixels.
+	///
+	/// Other transforms, such as skew or mirror, are drawn with [`FrameBuffer::draw_transformed`]
+	/// directly into the [frame buffer](crate::frame_buffer),
+	/// so pushed context, draw mode, drawing offset and clip rect are not applied.
+	///
+	/// [`FrameBuffer::draw_transformed`]: crate::frame::FrameBuffer::draw_transformed
+	#[cfg(feature = "transform")]
+	pub fn draw_transformed(&mut self, transform: &Affine2D) -> Result<(), ApiError> {
+		let Some((degrees, x_scale, y_scale)) = transform.to_rotate_scale() else {
+			let mut frame = crate::frame_buffer()?;
+			frame.draw_transformed(&self.bitmap_data(), transform);
+			return Ok(());
+		};
+
+		if degrees == 0.0 {
+			let at = transform.apply_point(Point::ZERO);
+			if x_scale == 1.0 && y_scale == 1.0 {
+				self.draw(at.x, at.y, BitmapFlip::kBitmapUnflipped)
+			} else {
+				self.draw_scaled(at.x, at.y, x_scale, y_scale)
+			}
+		} else {
+			let (width, height) = self.size();
+			let center = transform.apply(width as c_float / 2.0, height as c_float / 2.0);
+			let (x, y) = (libm::roundf(center.0) as _, libm::roundf(center.1) as _);
+			self.draw_rotated(x, y, degrees, 0.5, 0.5, x_scale, y_scale)
+		}
+		Ok(())
+	}
+
 
 	/// Returns `true` if any of the opaque pixels in this bitmap when positioned at `x, y` with `flip`
 	/// overlap any of the opaque pixels in `other` bitmap at `x_other`, `y_other` with `flip_other`
@@ -544,8 +583,8 @@ pub struct BitmapData<'bitmap> {
 	pub width: c_int,
 	pub height: c_int,
 	pub row_bytes: c_int,
-	mask: Option<&'bitmap mut [u8]>,
-	data: &'bitmap mut [u8],
+	pub(super) mask: Option<&'bitmap mut [u8]>,
+	pub(super) data: &'bitmap mut [u8],
 }
 
 impl<'bitmap> BitmapData<'bitmap> {
@@ -820,6 +859,8 @@ impl<Api: crate::api::Api> Graphics<Api> {
 	///
 	/// To push framebuffer to context use [`Graphics::push_framebuffer_to_context`].
 	///
+	/// See also [`Graphics::context_guard`] and [`Graphics::with_context`] which pop the context automatically.
+	///
 	/// Equivalent to [`sys::ffi::playdate_graphics::pushContext`].
 	#[doc(alias = "sys::ffi::playdate_graphics::pushContext")]
 	pub fn push_context(&self, target: &impl AnyBitmap) {
@@ -0,0 +1,341 @@
+//! Pixel access for the [`BitmapData`].
+
+use core::ffi::c_int;
+use alloc::vec::Vec;
+
+use sys::ffi::LCDSolidColor;
+
+use crate::error::Error;
+use crate::geometry::Rect;
+use super::BitmapData;
+
+
+// ctor //
+
+impl<'bitmap> BitmapData<'bitmap> {
+	/// Creates bitmap data over the given `data` and optional `mask` buffers,
+	/// `width` by `height` pixels with `row_bytes` row stride.
+	///
+	/// Useful to work with pixels without the device, e.g. in tests.
+	///
+	/// Returns [`Error::InvalidData`] if the `data` is too short for given size,
+	/// or [`Error::InvalidMask`] if the `mask` is shorter than the `data`.
+	pub fn new(width: c_int,
+	           height: c_int,
+	           row_bytes: c_int,
+	           data: &'bitmap mut [u8],
+	           mask: Option<&'bitmap mut [u8]>)
+	           -> Result<Self, Error> {
+		if width < 0 || height < 0 || (row_bytes as i64) * 8 < width as i64 {
+			return Err(Error::InvalidData);
+		}
+		let len = row_bytes as usize * height as usize;
+		if data.len() < len {
+			return Err(Error::InvalidData);
+		}
+		if mask.as_ref().is_some_and(|mask| mask.len() < len) {
+			return Err(Error::InvalidMask);
+		}
+
+		Ok(Self { width,
+		          height,
+		          row_bytes,
+		          mask,
+		          data })
+	}
+}
+
+
+// utils //
+
+impl BitmapData<'_> {
+	/// Returns `true` if `x, y` is inside of the bitmap.
+	pub const fn contains(&self, x: c_int, y: c_int) -> bool {
+		x >= 0 && y >= 0 && x < self.width && y < self.height
+	}
+
+	pub const fn bounds(&self) -> Rect { Rect::new(0, 0, self.width, self.height) }
+
+	/// Returns byte index and bit of the pixel at `x, y`, which must be inside of the bitmap.
+	#[inline(always)]
+	const fn locate(&self, x: c_int, y: c_int) -> (usize, u8) {
+		(y as usize * self.row_bytes as usize + x as usize / 8, 0x80 >> (x % 8))
+	}
+
+	/// Returns row `y` of the data, including padding bytes.
+	pub fn row(&self, y: c_int) -> Option<&[u8]> {
+		(y >= 0 && y < self.height).then(|| {
+			                           let start = y as usize * self.row_bytes as usize;
+			                           &self.data[start..start + self.row_bytes as usize]
+		                           })
+	}
+
+	/// Returns iterator over rows of the data, including padding bytes.
+	pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+		self.data
+		    .chunks(self.row_bytes.max(1) as usize)
+		    .take(self.height as usize)
+	}
+
+	/// Returns iterator over mutable rows of the data, including padding bytes.
+	pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [u8]> {
+		self.data
+		    .chunks_mut(self.row_bytes.max(1) as usize)
+		    .take(self.height as usize)
+	}
+
+	/// Returns iterator over colors of pixels in row `y`,
+	/// see [`pixel`](BitmapData::pixel).
+	///
+	/// Empty if the row is out of bounds.
+	pub fn row_pixels(&self, y: c_int) -> impl Iterator<Item = LCDSolidColor> + '_ {
+		let width = if y >= 0 && y < self.height { self.width } else { 0 };
+		(0..width).map(move |x| self.pixel(x, y))
+	}
+}
+
+
+// impl //
+
+impl BitmapData<'_> {
+	/// Returns color of the pixel at `x, y`.
+	///
+	/// Returns [`Clear`][LCDSolidColor::kColorClear] if the pixel is out of bounds or transparent,
+	/// otherwise [`White`][LCDSolidColor::kColorWhite] or [`Black`][LCDSolidColor::kColorBlack].
+	pub fn pixel(&self, x: c_int, y: c_int) -> LCDSolidColor {
+		if self.is_transparent(x, y) {
+			return LCDSolidColor::kColorClear;
+		}
+		let (i, bit) = self.locate(x, y);
+		if self.data[i] & bit != 0 {
+			LCDSolidColor::kColorWhite
+		} else {
+			LCDSolidColor::kColorBlack
+		}
+	}
+
+	/// Returns `true` if the pixel at `x, y` is out of bounds
+	/// or transparent by the mask.
+	pub fn is_transparent(&self, x: c_int, y: c_int) -> bool {
+		if !self.contains(x, y) {
+			return true;
+		}
+		let (i, bit) = self.locate(x, y);
+		self.mask.as_ref().is_some_and(|mask| mask[i] & bit == 0)
+	}
+
+	/// Sets the pixel at `x, y` to the `color`.
+	///
+	/// [`White`][LCDSolidColor::kColorWhite] and [`Black`][LCDSolidColor::kColorBlack] make the pixel opaque,
+	/// [`Clear`][LCDSolidColor::kColorClear] makes it transparent if the bitmap has a mask,
+	/// [`XOR`][LCDSolidColor::kColorXOR] inverts it.
+	///
+	/// Pixels out of bounds are ignored.
+	pub fn set_pixel(&mut self, x: c_int, y: c_int, color: LCDSolidColor) {
+		if !self.contains(x, y) {
+			return;
+		}
+		let (i, bit) = self.locate(x, y);
+		let opaque = match color {
+			LCDSolidColor::kColorBlack => {
+				self.data[i] &= !bit;
+				true
+			},
+			LCDSolidColor::kColorWhite => {
+				self.data[i] |= bit;
+				true
+			},
+			LCDSolidColor::kColorXOR => {
+				self.data[i] ^= bit;
+				return;
+			},
+			LCDSolidColor::kColorClear => false,
+		};
+
+		if let Some(mask) = self.mask.as_mut() {
+			if opaque {
+				mask[i] |= bit;
+			} else {
+				mask[i] &= !bit;
+			}
+		}
+	}
+
+	/// Fills the `rect` with the `color`, see [`set_pixel`](BitmapData::set_pixel).
+	pub fn fill_rect(&mut self, rect: Rect, color: LCDSolidColor) {
+		if let Some(rect) = rect.intersection(&self.bounds()) {
+			for y in rect.top()..rect.bottom() {
+				for x in rect.left()..rect.right() {
+					self.set_pixel(x, y, color);
+				}
+			}
+		}
+	}
+
+
+	/// Returns pixels of the data row by row, `true` is white.
+	///
+	/// The mask is ignored.
+	pub fn to_bools(&self) -> Vec<bool> { Self::bits_to_bools(self.data, self.width, self.height, self.row_bytes) }
+
+	/// Returns pixels of the mask row by row, `true` is opaque.
+	pub fn mask_to_bools(&self) -> Option<Vec<bool>> {
+		let mask = self.mask.as_deref()?;
+		Some(Self::bits_to_bools(mask, self.width, self.height, self.row_bytes))
+	}
+
+	/// Sets pixels of the data from `pixels` row by row, `true` is white.
+	///
+	/// Returns [`Error::InvalidData`] if the length of `pixels` is not `width * height`.
+	pub fn copy_from_bools(&mut self, pixels: &[bool]) -> Result<(), Error> {
+		if pixels.len() != self.width as usize * self.height as usize {
+			return Err(Error::InvalidData);
+		}
+		let width = self.width.max(1) as usize;
+		for (i, white) in pixels.iter().enumerate() {
+			let (x, y) = ((i % width) as c_int, (i / width) as c_int);
+			let (i, bit) = self.locate(x, y);
+			if *white {
+				self.data[i] |= bit;
+			} else {
+				self.data[i] &= !bit;
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the data packed 1 bit per pixel without row padding,
+	/// so each row is `(width + 7) / 8` bytes.
+	pub fn to_packed(&self) -> Vec<u8> { self.pack(self.data) }
+
+	/// Returns the mask packed 1 bit per pixel without row padding,
+	/// see [`to_packed`](BitmapData::to_packed).
+	pub fn mask_to_packed(&self) -> Option<Vec<u8>> { self.mask.as_deref().map(|mask| self.pack(mask)) }
+
+	/// Sets the data from `packed` 1 bit per pixel data without row padding,
+	/// as returned by [`to_packed`](BitmapData::to_packed).
+	///
+	/// Returns [`Error::InvalidData`] if the `packed` is too short.
+	pub fn copy_from_packed(&mut self, packed: &[u8]) -> Result<(), Error> {
+		let (width, height, row_bytes) = (self.width, self.height, self.row_bytes);
+		Self::unpack(self.data, width, height, row_bytes, packed)
+	}
+
+	/// Sets the mask from `packed` 1 bit per pixel data without row padding,
+	/// as returned by [`mask_to_packed`](BitmapData::mask_to_packed).
+	///
+	/// Returns [`Error::InvalidMask`] if the bitmap has no mask,
+	/// or [`Error::InvalidData`] if the `packed` is too short.
+	pub fn copy_mask_from_packed(&mut self, packed: &[u8]) -> Result<(), Error> {
+		let (width, height, row_bytes) = (self.width, self.height, self.row_bytes);
+		let mask = self.mask.as_deref_mut().ok_or(Error::InvalidMask)?;
+		Self::unpack(mask, width, height, row_bytes, packed)
+	}
+
+
+	fn pack(&self, bits: &[u8]) -> Vec<u8> {
+		let packed_row = Self::packed_row_bytes(self.width);
+		let mut packed = Vec::with_capacity(packed_row * self.height as usize);
+		for row in bits.chunks(self.row_bytes.max(1) as usize)
+		               .take(self.height as usize)
+		{
+			packed.extend_from_slice(&row[..packed_row]);
+		}
+		if let Some(last) = Self::last_byte_mask(self.width) {
+			packed.chunks_mut(packed_row)
+			      .for_each(|row| row[packed_row - 1] &= last);
+		}
+		packed
+	}
+
+	fn unpack(bits: &mut [u8], width: c_int, height: c_int, row_bytes: c_int, packed: &[u8]) -> Result<(), Error> {
+		let packed_row = Self::packed_row_bytes(width);
+		if packed.len() < packed_row * height as usize {
+			return Err(Error::InvalidData);
+		}
+		if packed_row == 0 {
+			return Ok(());
+		}
+		let last = Self::last_byte_mask(width).unwrap_or(0xFF);
+		let rows = bits.chunks_mut(row_bytes.max(1) as usize).take(height as usize);
+		for (row, src) in rows.zip(packed.chunks(packed_row)) {
+			let i = packed_row - 1;
+			row[..i].copy_from_slice(&src[..i]);
+			// keep bits beyond the width untouched:
+			row[i] = (row[i] & !last) | (src[i] & last);
+		}
+		Ok(())
+	}
+
+	fn packed_row_bytes(width: c_int) -> usize { (width.max(0) as usize).div_ceil(8) }
+
+	/// Mask of meaningful bits of the last byte in a row, if it is partial.
+	const fn last_byte_mask(width: c_int) -> Option<u8> {
+		match width % 8 {
+			0 => None,
+			rem => Some(0xFF << (8 - rem)),
+		}
+	}
+
+	fn bits_to_bools(bits: &[u8], width: c_int, height: c_int, row_bytes: c_int) -> Vec<bool> {
+		let mut result = Vec::with_capacity(width as usize * height as usize);
+		for y in 0..height as usize {
+			let row = &bits[y * row_bytes as usize..];
+			result.extend((0..width as usize).map(|x| row[x / 8] & (0x80 >> (x % 8)) != 0));
+		}
+		result
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+
+	#[test]
+	fn pixels_and_mask() {
+		let mut data = vec![0; 2 * 3];
+		let mut mask = vec![0xFF; 2 * 3];
+		let mut bd = BitmapData::new(10, 3, 2, &mut data, Some(&mut mask)).unwrap();
+
+		bd.set_pixel(9, 1, LCDSolidColor::kColorWhite);
+		bd.set_pixel(0, 2, LCDSolidColor::kColorClear);
+		bd.set_pixel(10, 0, LCDSolidColor::kColorWhite);
+		assert_eq!(bd.pixel(9, 1), LCDSolidColor::kColorWhite);
+		assert_eq!(bd.pixel(8, 1), LCDSolidColor::kColorBlack);
+		assert_eq!(bd.pixel(0, 2), LCDSolidColor::kColorClear);
+		assert!(bd.is_transparent(0, 2));
+		assert!(bd.is_transparent(10, 0));
+		assert_eq!(bd.row(1), Some(&[0, 0b0100_0000][..]));
+
+		bd.fill_rect(Rect::new(6, 0, 10, 1), LCDSolidColor::kColorWhite);
+		assert_eq!(bd.rows().next(), Some(&[0b0000_0011, 0b1100_0000][..]));
+		assert_eq!(
+		           bd.row_pixels(0)
+		             .filter(|c| *c == LCDSolidColor::kColorWhite)
+		             .count(),
+		           4
+		);
+
+		assert!(BitmapData::new(10, 3, 1, &mut [0; 3], None).is_err());
+	}
+
+	#[test]
+	fn bools_and_packed() {
+		let mut data = vec![0xFF; 4 * 2];
+		let mut bd = BitmapData::new(3, 2, 4, &mut data, None).unwrap();
+
+		let pixels = [true, false, true, false, true, false];
+		bd.copy_from_bools(&pixels).unwrap();
+		assert_eq!(bd.to_bools(), pixels);
+		assert_eq!(bd.to_packed(), vec![0b1010_0000, 0b0100_0000]);
+		// padding is untouched:
+		assert_eq!(bd.data()[..2], [0b1011_1111, 0xFF]);
+
+		bd.copy_from_packed(&[0b0110_0000, 0b1000_0000]).unwrap();
+		assert_eq!(bd.to_bools(), [false, true, true, true, false, false]);
+		assert!(bd.copy_from_bools(&pixels[1..]).is_err());
+	}
+}
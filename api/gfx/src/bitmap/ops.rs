@@ -0,0 +1,386 @@
+//! Image processing operations.
+//!
+//! Operations read the source [`BitmapData`] and write the result into the destination one,
+//! so they work with pixels of any origin, e.g. `Vec`s in tests.
+//! [`Bitmap`] methods such as [`Bitmap::inverted`] create new bitmaps with the result.
+
+use core::ffi::c_int;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use sys::ffi::LCDSolidColor;
+
+use crate::error::Error;
+use crate::geometry::Rect;
+use super::api;
+use super::Bitmap;
+use super::BitmapApi;
+use super::BitmapData;
+use super::BitmapFlip;
+use super::Color;
+
+
+/// Method of converting greyscale to 1-bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dither {
+	/// Pixels with value greater or equal to the level are white.
+	Threshold(u8),
+	/// Ordered dithering with 8x8 Bayer matrix.
+	Bayer,
+	/// Error diffusion, spreading 3/4 of the error to the closest neighbours.
+	Atkinson,
+	/// Error diffusion, spreading entire error to the closest neighbours.
+	FloydSteinberg,
+}
+
+impl Default for Dither {
+	fn default() -> Self { Self::FloydSteinberg }
+}
+
+
+/// 8x8 Bayer matrix, values `0..64`.
+const BAYER: [[u8; 8]; 8] = [[0, 32, 8, 40, 2, 34, 10, 42],
+                             [48, 16, 56, 24, 50, 18, 58, 26],
+                             [12, 44, 4, 36, 14, 46, 6, 38],
+                             [60, 28, 52, 20, 62, 30, 54, 22],
+                             [3, 35, 11, 43, 1, 33, 9, 41],
+                             [51, 19, 59, 27, 49, 17, 57, 25],
+                             [15, 47, 7, 39, 13, 45, 5, 37],
+                             [63, 31, 55, 23, 61, 29, 53, 21]];
+
+/// Returns threshold of the Bayer matrix at `x, y`, in `0..=255`.
+const fn bayer(x: c_int, y: c_int) -> u8 { BAYER[(y & 7) as usize][(x & 7) as usize] * 4 + 2 }
+
+
+/// Converts `luma` values, row by row, to 1-bit pixels of the `dst` with given `method`.
+///
+/// The `dst` mask is untouched.
+///
+/// Returns [`Error::InvalidData`] if the length of `luma` is not `width * height` of the `dst`.
+pub fn dither(luma: &[u8], method: Dither, dst: &mut BitmapData) -> Result<(), Error> {
+	let (width, height) = (dst.width().max(0) as usize, dst.height().max(0) as usize);
+	if luma.len() != width * height {
+		return Err(Error::InvalidData);
+	}
+
+	// (dx, dy, weight) of error diffusion kernels:
+	const FLOYD_STEINBERG: &[(isize, usize, i32)] = &[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)];
+	const ATKINSON: &[(isize, usize, i32)] = &[(1, 0, 1), (2, 0, 1), (-1, 1, 1), (0, 1, 1), (1, 1, 1), (0, 2, 1)];
+
+	let (kernel, divisor) = match method {
+		Dither::FloydSteinberg => (FLOYD_STEINBERG, 16),
+		Dither::Atkinson => (ATKINSON, 8),
+		Dither::Threshold(level) => {
+			ordered(luma, width, dst, |_, _| level);
+			return Ok(());
+		},
+		Dither::Bayer => {
+			ordered(luma, width, dst, bayer);
+			return Ok(());
+		},
+	};
+
+	// Errors for the current and two next rows, with two extra cells on both sides:
+	let mut rows = [
+	                vec![0_i32; width + 4],
+	                vec![0_i32; width + 4],
+	                vec![0_i32; width + 4],
+	];
+	for y in 0..height {
+		for x in 0..width {
+			let value = luma[y * width + x] as i32 + rows[0][x + 2] / divisor;
+			let white = value >= 128;
+			dst.set_pixel(x as _, y as _, solid(white));
+
+			let err = value - if white { 255 } else { 0 };
+			for (dx, dy, weight) in kernel {
+				rows[*dy][(x as isize + 2 + dx) as usize] += err * weight;
+			}
+		}
+		rows.rotate_left(1);
+		rows[2].fill(0);
+	}
+	Ok(())
+}
+
+fn ordered(luma: &[u8], width: usize, dst: &mut BitmapData, level: impl Fn(c_int, c_int) -> u8) {
+	for (i, value) in luma.iter().enumerate() {
+		let (x, y) = ((i % width) as c_int, (i / width) as c_int);
+		dst.set_pixel(x, y, solid(*value >= level(x, y)));
+	}
+}
+
+const fn solid(white: bool) -> LCDSolidColor {
+	if white {
+		LCDSolidColor::kColorWhite
+	} else {
+		LCDSolidColor::kColorBlack
+	}
+}
+
+
+/// Returns luma values of the `src` pixels row by row, white is `255`.
+///
+/// The mask is ignored.
+pub fn to_luma(src: &BitmapData) -> Vec<u8> {
+	src.to_bools()
+	   .into_iter()
+	   .map(|white| if white { 0xFF } else { 0 })
+	   .collect()
+}
+
+
+/// Blurs the `src` with box filter of given `radius`,
+/// dithering the result into the `dst` with given `method`.
+///
+/// The mask is ignored and the `dst` mask is untouched.
+///
+/// Returns [`Error::InvalidData`] if sizes of the `src` and `dst` are different.
+pub fn blur(src: &BitmapData, radius: c_int, method: Dither, dst: &mut BitmapData) -> Result<(), Error> {
+	same_size(src, dst)?;
+	let (width, height) = (src.width().max(0) as usize, src.height().max(0) as usize);
+	let radius = radius.max(0) as usize;
+	let luma = to_luma(src);
+
+	// Separable box filter, horizontal then vertical pass:
+	let mut horizontal = vec![0_u8; luma.len()];
+	for y in 0..height {
+		let row = &luma[y * width..(y + 1) * width];
+		for x in 0..width {
+			let window = &row[x.saturating_sub(radius)..(x + radius + 1).min(width)];
+			horizontal[y * width + x] = average(window.iter().copied());
+		}
+	}
+	let mut blurred = vec![0_u8; luma.len()];
+	for y in 0..height {
+		let rows = y.saturating_sub(radius)..(y + radius + 1).min(height);
+		for x in 0..width {
+			blurred[y * width + x] = average(rows.clone().map(|y| horizontal[y * width + x]));
+		}
+	}
+
+	dither(&blurred, method, dst)
+}
+
+fn average(values: impl Iterator<Item = u8>) -> u8 {
+	let (sum, count) = values.fold((0_u32, 0_u32), |(sum, count), v| (sum + v as u32, count + 1));
+	sum.checked_div(count).unwrap_or(0) as u8
+}
+
+
+/// Copies the `src` into the `dst`, replacing part of pixels with the `color`
+/// in ordered dissolve pattern.
+///
+/// The `level` is the fade amount, `0` keeps all pixels and `255` replaces all of them.
+/// [`Clear`][LCDSolidColor::kColorClear] makes pixels transparent if the `dst` has a mask.
+///
+/// Returns [`Error::InvalidData`] if sizes of the `src` and `dst` are different.
+pub fn fade(src: &BitmapData, level: u8, color: LCDSolidColor, dst: &mut BitmapData) -> Result<(), Error> {
+	same_size(src, dst)?;
+	map(dst, |x, y| {
+		if level > 0 && bayer(x, y) <= level {
+			color
+		} else {
+			src.pixel(x, y)
+		}
+	});
+	Ok(())
+}
+
+/// Copies the `src` into the `dst` with inverted colors, the mask is kept.
+///
+/// Returns [`Error::InvalidData`] if sizes of the `src` and `dst` are different.
+pub fn invert(src: &BitmapData, dst: &mut BitmapData) -> Result<(), Error> {
+	same_size(src, dst)?;
+	map(dst, |x, y| {
+		match src.pixel(x, y) {
+			LCDSolidColor::kColorBlack => LCDSolidColor::kColorWhite,
+			LCDSolidColor::kColorWhite => LCDSolidColor::kColorBlack,
+			color => color,
+		}
+	});
+	Ok(())
+}
+
+/// Copies the `src` into the `dst` flipped with given `flip`.
+///
+/// Returns [`Error::InvalidData`] if sizes of the `src` and `dst` are different.
+pub fn flip(src: &BitmapData, flip: BitmapFlip, dst: &mut BitmapData) -> Result<(), Error> {
+	same_size(src, dst)?;
+	let (right, bottom) = (src.width() - 1, src.height() - 1);
+	let (x_flip, y_flip) = match flip {
+		BitmapFlip::kBitmapUnflipped => (false, false),
+		BitmapFlip::kBitmapFlippedX => (true, false),
+		BitmapFlip::kBitmapFlippedY => (false, true),
+		BitmapFlip::kBitmapFlippedXY => (true, true),
+	};
+	map(dst, |x, y| {
+		let x = if x_flip { right - x } else { x };
+		let y = if y_flip { bottom - y } else { y };
+		src.pixel(x, y)
+	});
+	Ok(())
+}
+
+/// Copies the `rect` area of the `src` into the `dst`.
+///
+/// Pixels of the area out of the `src` bounds are transparent.
+///
+/// Returns [`Error::InvalidData`] if size of the `dst` is not size of the `rect`.
+pub fn crop(src: &BitmapData, rect: Rect, dst: &mut BitmapData) -> Result<(), Error> {
+	if dst.bounds().size() != rect.size() {
+		return Err(Error::InvalidData);
+	}
+	map(dst, |x, y| src.pixel(x + rect.x, y + rect.y));
+	Ok(())
+}
+
+
+fn same_size(src: &BitmapData, dst: &BitmapData) -> Result<(), Error> {
+	if src.bounds() == dst.bounds() {
+		Ok(())
+	} else {
+		Err(Error::InvalidData)
+	}
+}
+
+/// Sets each pixel of the `dst` to the color returned by `f`.
+fn map(dst: &mut BitmapData, f: impl Fn(c_int, c_int) -> LCDSolidColor) {
+	for y in 0..dst.height() {
+		for x in 0..dst.width() {
+			dst.set_pixel(x, y, f(x, y));
+		}
+	}
+}
+
+
+impl<Api: api::Api + Clone, const FOD: bool> Bitmap<Api, FOD> {
+	/// Returns a new bitmap with inverted colors, see [`invert`].
+	pub fn inverted(&mut self) -> Result<Bitmap<Api, true>, Error> { self.apply(None, invert) }
+
+	/// Returns a new flipped bitmap, see [`flip`].
+	pub fn flipped(&mut self, flip: BitmapFlip) -> Result<Bitmap<Api, true>, Error> {
+		self.apply(None, |src, dst| self::flip(src, flip, dst))
+	}
+
+	/// Returns a new bitmap with the `rect` area of this one, see [`crop`].
+	pub fn cropped(&mut self, rect: Rect) -> Result<Bitmap<Api, true>, Error> {
+		self.apply(Some(rect), |src, dst| crop(src, rect, dst))
+	}
+
+	/// Returns a new blurred bitmap, see [`blur`].
+	pub fn blurred(&mut self, radius: c_int, method: Dither) -> Result<Bitmap<Api, true>, Error> {
+		self.apply(None, |src, dst| blur(src, radius, method, dst))
+	}
+
+	/// Returns a new bitmap with pixels dissolved to the `color`, see [`fade`].
+	pub fn faded(&mut self, level: u8, color: LCDSolidColor) -> Result<Bitmap<Api, true>, Error> {
+		self.apply(None, |src, dst| fade(src, level, color, dst))
+	}
+
+	/// Creates a new bitmap with the same api and mask presence as this one,
+	/// `rect`-sized if given, and fills it with `op`.
+	fn apply<F>(&mut self, rect: Option<Rect>, op: F) -> Result<Bitmap<Api, true>, Error>
+		where F: FnOnce(&BitmapData, &mut BitmapData) -> Result<(), Error> {
+		let api = self.api_ref().clone();
+		let src = self.bitmap_data();
+		let size = rect.map_or(src.bounds().size(), |rect| rect.size());
+		let bg = if src.mask().is_some() || rect.is_some() {
+			Color::CLEAR
+		} else {
+			Color::WHITE
+		};
+
+		let mut bitmap = Bitmap::new_with(api, size.width, size.height, bg)?;
+		op(&src, &mut bitmap.bitmap_data())?;
+		Ok(bitmap)
+	}
+}
+
+impl Bitmap<api::Default, true> {
+	/// Creates a new bitmap from `luma` values, row by row, see [`dither`].
+	pub fn from_luma(width: c_int, height: c_int, luma: &[u8], method: Dither) -> Result<Self, Error> {
+		let mut bitmap = Bitmap::new(width, height, Color::WHITE)?;
+		dither(luma, method, &mut bitmap.bitmap_data())?;
+		Ok(bitmap)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	fn white_count(data: &BitmapData) -> usize { data.to_bools().into_iter().filter(|w| *w).count() }
+
+	#[test]
+	fn dither_methods() {
+		let mut buf = vec![0; 8];
+		let mut dst = BitmapData::new(8, 8, 1, &mut buf, None).unwrap();
+		for method in [
+		               Dither::Threshold(128),
+		               Dither::Bayer,
+		               Dither::Atkinson,
+		               Dither::FloydSteinberg,
+		] {
+			dither(&[0, 255].repeat(32), method, &mut dst).unwrap();
+			assert_eq!(dst.data(), [0b0101_0101; 8], "{method:?}");
+		}
+
+		// 50% grey gives half of pixels white:
+		for method in [Dither::Bayer, Dither::FloydSteinberg] {
+			dither(&[128; 64], method, &mut dst).unwrap();
+			assert_eq!(white_count(&dst), 32, "{method:?}");
+		}
+		dither(&[64; 64], Dither::Bayer, &mut dst).unwrap();
+		assert_eq!(white_count(&dst), 16);
+		assert!(dither(&[0; 63], Dither::Bayer, &mut dst).is_err());
+	}
+
+	#[test]
+	fn copies() {
+		let mut src = vec![0b1100_0000, 0b1000_0000];
+		let mut mask = vec![0xFF, 0b0111_1111];
+		let src = BitmapData::new(3, 2, 1, &mut src, Some(&mut mask)).unwrap();
+
+		let mut buf = vec![0; 2];
+		let mut buf_mask = vec![0; 2];
+		let mut dst = BitmapData::new(3, 2, 1, &mut buf, Some(&mut buf_mask)).unwrap();
+
+		invert(&src, &mut dst).unwrap();
+		assert_eq!(dst.to_packed(), [0b0010_0000, 0b0110_0000]);
+		assert_eq!(dst.mask_to_packed(), Some(vec![0b1110_0000, 0b0110_0000]));
+
+		flip(&src, BitmapFlip::kBitmapFlippedXY, &mut dst).unwrap();
+		assert_eq!(dst.pixel(2, 1), LCDSolidColor::kColorWhite);
+		assert_eq!(dst.pixel(0, 1), LCDSolidColor::kColorBlack);
+		assert_eq!(dst.pixel(2, 0), LCDSolidColor::kColorClear);
+
+		let mut buf = vec![0; 2];
+		let mut buf_mask = vec![0; 2];
+		let mut cropped = BitmapData::new(2, 2, 1, &mut buf, Some(&mut buf_mask)).unwrap();
+		crop(&src, Rect::new(1, 1, 2, 2), &mut cropped).unwrap();
+		assert_eq!(cropped.pixel(0, 0), LCDSolidColor::kColorBlack);
+		assert_eq!(cropped.pixel(0, 1), LCDSolidColor::kColorClear);
+		assert!(crop(&src, Rect::new(0, 0, 3, 3), &mut cropped).is_err());
+	}
+
+	#[test]
+	fn fade_and_blur() {
+		let mut src = vec![0xFF; 8];
+		let src = BitmapData::new(8, 8, 1, &mut src, None).unwrap();
+		let mut buf = vec![0; 8];
+		let mut dst = BitmapData::new(8, 8, 1, &mut buf, None).unwrap();
+
+		fade(&src, 0, LCDSolidColor::kColorBlack, &mut dst).unwrap();
+		assert_eq!(white_count(&dst), 64);
+		fade(&src, 128, LCDSolidColor::kColorBlack, &mut dst).unwrap();
+		assert_eq!(white_count(&dst), 32);
+		fade(&src, 255, LCDSolidColor::kColorBlack, &mut dst).unwrap();
+		assert_eq!(white_count(&dst), 0);
+
+		blur(&src, 2, Dither::Bayer, &mut dst).unwrap();
+		assert_eq!(white_count(&dst), 64);
+	}
+}
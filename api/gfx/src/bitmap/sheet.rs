@@ -0,0 +1,452 @@
+//! Sprite-sheets, descriptors of frames packed into a single atlas bitmap.
+//!
+//! [`Sheet`] is built by a regular [`Grid`] or parsed from the JSON exported by
+//! Aseprite or TexturePacker, and then the atlas is sliced into a [`BitmapTable`](super::table::BitmapTable)
+//! with [`BitmapTable::from_sheet`](super::table::BitmapTable::from_sheet).
+//!
+//! ```ignore
+//! let atlas = Bitmap::load("images/hero")?;
+//! let sheet = Sheet::parse_json(&json)?;
+//! let table = BitmapTable::from_sheet(&atlas, &sheet)?;
+//! let walk = sheet.animation(&table, "walk").unwrap();
+//! ```
+
+use core::ffi::c_int;
+use core::fmt;
+use core::time::Duration;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::geometry::Point;
+use crate::geometry::Rect;
+use crate::geometry::Size;
+use super::animation::Animation;
+use super::animation::FrameTable;
+use super::animation::LoopMode;
+use super::BitmapRef;
+
+mod json;
+use json::Value;
+
+
+/// Error of the sprite-sheet descriptor parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+	/// Invalid JSON at the byte offset.
+	Syntax { offset: usize },
+	/// Valid JSON, but not a sprite-sheet, or a required field is missing.
+	Format,
+	/// Rotated frames are not supported.
+	Rotated,
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ParseError::Syntax { offset } => write!(f, "Invalid JSON at offset {offset}"),
+			ParseError::Format => write!(f, "Invalid sprite-sheet"),
+			ParseError::Rotated => write!(f, "Rotated frames are not supported"),
+		}
+	}
+}
+
+impl core::error::Error for ParseError {}
+
+
+/// Regular grid of same-sized cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Grid {
+	/// Size of a cell.
+	pub cell: Size,
+	/// Space between cells.
+	pub spacing: c_int,
+	/// Space around the cells along the edges of the atlas.
+	pub margin: c_int,
+}
+
+impl Grid {
+	pub const fn new(width: c_int, height: c_int) -> Self {
+		Self { cell: Size::new(width, height),
+		       spacing: 0,
+		       margin: 0 }
+	}
+
+	pub const fn with_spacing(mut self, spacing: c_int) -> Self {
+		self.spacing = spacing;
+		self
+	}
+
+	pub const fn with_margin(mut self, margin: c_int) -> Self {
+		self.margin = margin;
+		self
+	}
+
+	/// Returns number of columns and rows of whole cells fitting into the `atlas`.
+	pub fn dimensions(&self, atlas: Size) -> (c_int, c_int) {
+		let fit = |len: c_int, cell: c_int| -> Option<c_int> {
+			let step = cell.checked_add(self.spacing)
+			               .filter(|step| cell > 0 && *step > 0)?;
+			let span = self.margin
+			               .checked_mul(2)
+			               .and_then(|margins| len.checked_sub(margins))
+			               .and_then(|len| len.checked_add(self.spacing))?;
+			Some((span / step).max(0))
+		};
+		(fit(atlas.width, self.cell.width).unwrap_or(0), fit(atlas.height, self.cell.height).unwrap_or(0))
+	}
+
+	/// Returns rects of the cells in the `atlas`, row by row.
+	pub fn cells(&self, atlas: Size) -> impl Iterator<Item = Rect> + '_ {
+		let (columns, rows) = self.dimensions(atlas);
+		(0..rows).flat_map(move |row| {
+			         (0..columns).map(move |column| {
+				                     Rect::new(
+				                               self.margin + column * (self.cell.width + self.spacing),
+				                               self.margin + row * (self.cell.height + self.spacing),
+				                               self.cell.width,
+				                               self.cell.height,
+				)
+			                     })
+		         })
+	}
+}
+
+
+/// Frame of the sprite-sheet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Frame {
+	/// Name of the frame, the index for grid frames.
+	pub name: String,
+	/// Area of the frame in the atlas.
+	pub rect: Rect,
+	/// Position of the area in the cell, non-zero for trimmed frames.
+	pub offset: Point,
+	/// Duration of the frame, if specified.
+	pub duration: Option<Duration>,
+}
+
+
+/// Direction of the tagged animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Direction {
+	#[default]
+	Forward,
+	Reverse,
+	PingPong,
+	/// Ping-pong starting backward from the last frame.
+	PingPongReverse,
+}
+
+
+/// Named range of frames.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tag {
+	pub name: String,
+	/// Index of the first frame.
+	pub from: usize,
+	/// Index of the last frame, inclusive.
+	pub to: usize,
+	pub direction: Direction,
+}
+
+impl Tag {
+	/// Returns indices of frames in order of the direction.
+	pub fn frames(&self) -> Vec<usize> {
+		let mut frames: Vec<_> = (self.from..=self.to).collect();
+		if matches!(self.direction, Direction::Reverse | Direction::PingPongReverse) {
+			frames.reverse();
+		}
+		frames
+	}
+}
+
+
+/// Descriptor of the sprite-sheet.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Sheet {
+	cell: Size,
+	frames: Vec<Frame>,
+	tags: Vec<Tag>,
+}
+
+
+// ctor //
+
+impl Sheet {
+	/// Duration of the frame without specified one, same as the default in Aseprite.
+	pub const DEFAULT_DURATION: Duration = Duration::from_millis(100);
+
+	/// Creates sheet of whole cells of the `grid` fitting into the `atlas` of the size, row by row.
+	pub fn from_grid(atlas: Size, grid: &Grid) -> Self {
+		let frames = grid.cells(atlas)
+		                 .enumerate()
+		                 .map(|(i, rect)| {
+			                 Frame { name: i.to_string(),
+			                         rect,
+			                         offset: Point::ZERO,
+			                         duration: None }
+		                 })
+		                 .collect();
+		Self { cell: grid.cell,
+		       frames,
+		       tags: Vec::new() }
+	}
+
+	/// Creates sheet with the `frames` in cells of the `cell` size.
+	pub fn new(cell: Size, frames: Vec<Frame>, tags: Vec<Tag>) -> Self { Self { cell, frames, tags } }
+
+	/// Parses the JSON sheet exported by Aseprite or TexturePacker, in "hash" or "array" layout.
+	///
+	/// Trimmed frames are placed back at their offsets, cell is as large as the largest source size.
+	/// Frame tags of Aseprite, `meta.frameTags`, are available by [`tag`](Sheet::tag).
+	pub fn parse_json(source: &str) -> Result<Self, ParseError> {
+		let root = json::parse(source).map_err(|offset| ParseError::Syntax { offset })?;
+
+		let frames: Vec<(&str, &Value)> = match root.get("frames").ok_or(ParseError::Format)? {
+			Value::Object(members) => members.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+			Value::Array(items) => {
+				items.iter()
+				     .map(|v| (v.get("filename").and_then(Value::as_str).unwrap_or_default(), v))
+				     .collect()
+			},
+			_ => return Err(ParseError::Format),
+		};
+
+		let mut sheet = Self::default();
+		for (name, value) in frames {
+			if value.get("rotated").and_then(Value::as_bool) == Some(true) {
+				return Err(ParseError::Rotated);
+			}
+			let rect = parse_rect(value.get("frame").ok_or(ParseError::Format)?)?;
+			let offset = match value.get("spriteSourceSize") {
+				Some(v) => parse_rect(v)?.origin(),
+				None => Point::ZERO,
+			};
+			let source = match value.get("sourceSize") {
+				Some(v) => parse_size(v)?,
+				None => rect.size(),
+			};
+			let duration = match value.get("duration") {
+				Some(v) => Some(Duration::from_millis(v.as_int().ok_or(ParseError::Format)?.max(0) as u64)),
+				None => None,
+			};
+
+			sheet.cell.width = sheet.cell.width.max(source.width);
+			sheet.cell.height = sheet.cell.height.max(source.height);
+			sheet.frames.push(Frame { name: name.into(),
+			                          rect,
+			                          offset,
+			                          duration });
+		}
+
+		let tags = root.get("meta").and_then(|meta| meta.get("frameTags"));
+		for tag in tags.and_then(Value::as_array).unwrap_or_default() {
+			let index = |key| {
+				tag.get(key)
+				   .and_then(Value::as_int)
+				   .and_then(|i| usize::try_from(i).ok())
+				   .filter(|i| *i < sheet.frames.len())
+				   .ok_or(ParseError::Format)
+			};
+			let direction = match tag.get("direction").and_then(Value::as_str) {
+				Some("reverse") => Direction::Reverse,
+				Some("pingpong") => Direction::PingPong,
+				Some("pingpong_reverse") => Direction::PingPongReverse,
+				_ => Direction::Forward,
+			};
+			let name = tag.get("name")
+			              .and_then(Value::as_str)
+			              .ok_or(ParseError::Format)?;
+			let (from, to) = (index("from")?, index("to")?);
+			if from > to {
+				return Err(ParseError::Format);
+			}
+			sheet.tags.push(Tag { name: name.into(),
+			                      from,
+			                      to,
+			                      direction });
+		}
+
+		Ok(sheet)
+	}
+}
+
+fn parse_rect(value: &Value) -> Result<Rect, ParseError> {
+	let int = |key| value.get(key).and_then(Value::as_int).ok_or(ParseError::Format);
+	Ok(Rect::new(int("x")?, int("y")?, int("w")?, int("h")?))
+}
+
+fn parse_size(value: &Value) -> Result<Size, ParseError> {
+	let int = |key| value.get(key).and_then(Value::as_int).ok_or(ParseError::Format);
+	Ok(Size::new(int("w")?, int("h")?))
+}
+
+
+// utils //
+
+impl Sheet {
+	/// Returns size of the cells of the table sliced from the sheet.
+	pub const fn cell(&self) -> Size { self.cell }
+
+	pub fn frames(&self) -> &[Frame] { &self.frames }
+	pub fn tags(&self) -> &[Tag] { &self.tags }
+
+	/// Returns index of the frame with the `name`.
+	pub fn frame_index(&self, name: &str) -> Option<usize> { self.frames.iter().position(|f| f.name == name) }
+
+	/// Returns the tag with the `name`.
+	pub fn tag(&self, name: &str) -> Option<&Tag> { self.tags.iter().find(|t| t.name == name) }
+
+	/// Returns durations of all frames,
+	/// [`DEFAULT_DURATION`](Sheet::DEFAULT_DURATION) for frames without one.
+	pub fn durations(&self) -> Vec<Duration> {
+		self.frames
+		    .iter()
+		    .map(|f| f.duration.unwrap_or(Self::DEFAULT_DURATION))
+		    .collect()
+	}
+
+	/// Returns animation of all frames in the `table` sliced from the sheet.
+	///
+	/// Returns `None` if the `table` has other number of frames.
+	pub fn frames_animation<T: FrameTable>(&self, table: T) -> Option<Animation<T>> {
+		Animation::with_durations(table, self.durations())
+	}
+
+	/// Returns animation of frames of the tag with the `name` in the `table` sliced from the sheet.
+	///
+	/// Frames are ordered by the direction of the tag, ping-pong tags play in [`LoopMode::PingPong`].
+	///
+	/// Returns `None` if there is no such tag.
+	pub fn animation<T: FrameTable>(&self, table: T, name: &str) -> Option<Animation<TagFrames<T>>> {
+		let tag = self.tag(name)?;
+		let frames = tag.frames();
+		let durations = frames.iter()
+		                      .map(|i| self.frames[*i].duration.unwrap_or(Self::DEFAULT_DURATION))
+		                      .collect();
+		let table = TagFrames { table,
+		                        frames: frames.into_iter().map(|i| i as c_int).collect() };
+		let mode = match tag.direction {
+			Direction::PingPong | Direction::PingPongReverse => LoopMode::PingPong,
+			_ => LoopMode::Loop,
+		};
+		Animation::with_durations(table, durations).map(|a| a.with_mode(mode))
+	}
+}
+
+
+/// Frames of the tag in the table, see [`Sheet::animation`].
+#[derive(Debug, Clone)]
+pub struct TagFrames<T> {
+	table: T,
+	frames: Vec<c_int>,
+}
+
+impl<T> TagFrames<T> {
+	pub fn table(&self) -> &T { &self.table }
+	/// Indices of the frames in the table.
+	pub fn frames(&self) -> &[c_int] { &self.frames }
+	pub fn into_table(self) -> T { self.table }
+}
+
+impl<T: FrameTable> FrameTable for TagFrames<T> {
+	fn count(&self) -> c_int { self.frames.len() as _ }
+
+	fn frame(&self, index: c_int) -> Option<BitmapRef<'_>> {
+		let index = usize::try_from(index).ok()?;
+		self.table.frame(*self.frames.get(index)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	const ASEPRITE: &str = r#"{ "frames": {
+		"hero 0.aseprite": { "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "rotated": false, "trimmed": false,
+		                     "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 }, "sourceSize": { "w": 16, "h": 16 },
+		                     "duration": 100 },
+		"hero 1.aseprite": { "frame": { "x": 16, "y": 0, "w": 10, "h": 12 }, "rotated": false, "trimmed": true,
+		                     "spriteSourceSize": { "x": 3, "y": 4, "w": 10, "h": 12 }, "sourceSize": { "w": 16, "h": 16 },
+		                     "duration": 250 },
+		"hero 2.aseprite": { "frame": { "x": 26, "y": 0, "w": 16, "h": 16 }, "rotated": false, "trimmed": false,
+		                     "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 }, "sourceSize": { "w": 16, "h": 16 },
+		                     "duration": 100 }
+		},
+		"meta": { "app": "https://www.aseprite.org/", "size": { "w": 42, "h": 16 },
+		          "frameTags": [ { "name": "idle", "from": 0, "to": 0, "direction": "forward" },
+		                         { "name": "walk", "from": 1, "to": 2, "direction": "reverse" },
+		                         { "name": "bounce", "from": 0, "to": 2, "direction": "pingpong" } ] }
+	}"#;
+
+
+	#[test]
+	fn grid() {
+		let grid = Grid::new(16, 8).with_spacing(2).with_margin(1);
+		let sheet = Sheet::from_grid(Size::new(1 + 16 + 2 + 16 + 1, 1 + 8 + 2 + 8 + 2 + 7), &grid);
+		assert_eq!(sheet.frames().len(), 4);
+		assert_eq!(sheet.frames()[3].rect, Rect::new(19, 11, 16, 8));
+		assert_eq!(sheet.frame_index("2"), Some(2));
+		assert_eq!(grid.dimensions(Size::new(10, 10)), (0, 1));
+	}
+
+	#[test]
+	fn grid_overflow() {
+		let atlas = Size::new(c_int::MAX, c_int::MIN);
+		assert_eq!(Grid::new(16, 8).with_margin(c_int::MAX).dimensions(atlas), (0, 0));
+		assert_eq!(Grid::new(c_int::MAX, 8).with_spacing(1).dimensions(atlas), (0, 0));
+		assert_eq!(Grid::new(16, 8).with_spacing(-16).dimensions(atlas), (0, 0));
+	}
+
+	#[test]
+	fn aseprite() {
+		let sheet = Sheet::parse_json(ASEPRITE).unwrap();
+		assert_eq!(sheet.cell(), Size::new(16, 16));
+		assert_eq!(sheet.frames().len(), 3);
+		let trimmed = &sheet.frames()[1];
+		assert_eq!(
+		           (trimmed.rect, trimmed.offset),
+		           (Rect::new(16, 0, 10, 12), Point::new(3, 4))
+		);
+		assert_eq!(sheet.frame_index("hero 2.aseprite"), Some(2));
+		assert_eq!(sheet.tag("walk").unwrap().frames(), [2, 1]);
+
+		struct Frames;
+		impl FrameTable for Frames {
+			fn count(&self) -> c_int { 3 }
+			fn frame(&self, _: c_int) -> Option<BitmapRef<'_>> { None }
+		}
+
+		let walk = sheet.animation(Frames, "walk").unwrap();
+		assert_eq!(walk.table().frames(), [2, 1]);
+		assert_eq!(
+		           walk.durations(),
+		           [Duration::from_millis(100), Duration::from_millis(250)]
+		);
+		assert_eq!(
+		           sheet.animation(Frames, "bounce").unwrap().mode(),
+		           LoopMode::PingPong
+		);
+		assert!(sheet.animation(Frames, "run").is_none());
+		assert_eq!(sheet.frames_animation(Frames).unwrap().len(), 3);
+	}
+
+	#[test]
+	fn texture_packer_array() {
+		let source = r#"{"frames": [
+			{"filename": "a", "frame": {"x": 0, "y": 0, "w": 8, "h": 8}, "rotated": false},
+			{"filename": "b", "frame": {"x": 8, "y": 0, "w": 8, "h": 4}, "rotated": false,
+			 "spriteSourceSize": {"x": 0, "y": 2, "w": 8, "h": 4}, "sourceSize": {"w": 8, "h": 8}}
+		]}"#;
+		let sheet = Sheet::parse_json(source).unwrap();
+		assert_eq!(sheet.frame_index("b"), Some(1));
+		assert_eq!(sheet.durations()[1], Sheet::DEFAULT_DURATION);
+
+		let rotated = source.replace("\"rotated\": false}", "\"rotated\": true}");
+		assert_eq!(Sheet::parse_json(&rotated), Err(ParseError::Rotated));
+		assert_eq!(Sheet::parse_json("{}"), Err(ParseError::Format));
+		assert_eq!(Sheet::parse_json("{"), Err(ParseError::Syntax { offset: 1 }));
+	}
+}
@@ -0,0 +1,253 @@
+//! Minimal JSON reader, just enough for the sprite-sheet descriptors.
+
+use core::ffi::c_int;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(String),
+	Array(Vec<Value>),
+	/// Members in order of the source.
+	Object(Vec<(String, Value)>),
+}
+
+impl Value {
+	pub fn get(&self, key: &str) -> Option<&Value> {
+		self.as_object()?.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+	}
+
+	pub fn as_object(&self) -> Option<&[(String, Value)]> {
+		match self {
+			Value::Object(members) => Some(members),
+			_ => None,
+		}
+	}
+
+	pub fn as_array(&self) -> Option<&[Value]> {
+		match self {
+			Value::Array(items) => Some(items),
+			_ => None,
+		}
+	}
+
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			Value::String(s) => Some(s),
+			_ => None,
+		}
+	}
+
+	pub fn as_bool(&self) -> Option<bool> {
+		match self {
+			Value::Bool(b) => Some(*b),
+			_ => None,
+		}
+	}
+
+	pub fn as_int(&self) -> Option<c_int> {
+		match self {
+			Value::Number(n) if *n >= c_int::MIN as f64 && *n <= c_int::MAX as f64 && *n == (*n as c_int) as f64 => {
+				Some(*n as c_int)
+			},
+			_ => None,
+		}
+	}
+}
+
+
+/// Parses the JSON document, returns byte offset of the error.
+pub fn parse(source: &str) -> Result<Value, usize> {
+	let mut parser = Parser { src: source.as_bytes(),
+	                          pos: 0 };
+	let value = parser.value()?;
+	parser.skip_ws();
+	if parser.pos == parser.src.len() {
+		Ok(value)
+	} else {
+		Err(parser.pos)
+	}
+}
+
+
+struct Parser<'s> {
+	src: &'s [u8],
+	pos: usize,
+}
+
+impl Parser<'_> {
+	fn peek(&self) -> Option<u8> { self.src.get(self.pos).copied() }
+
+	fn next(&mut self) -> Result<u8, usize> {
+		let c = self.peek().ok_or(self.pos)?;
+		self.pos += 1;
+		Ok(c)
+	}
+
+	fn skip_ws(&mut self) {
+		while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+			self.pos += 1;
+		}
+	}
+
+	fn expect(&mut self, lit: &[u8]) -> Result<(), usize> {
+		if self.src[self.pos..].starts_with(lit) {
+			self.pos += lit.len();
+			Ok(())
+		} else {
+			Err(self.pos)
+		}
+	}
+
+	fn value(&mut self) -> Result<Value, usize> {
+		self.skip_ws();
+		match self.peek().ok_or(self.pos)? {
+			b'{' => self.object(),
+			b'[' => self.array(),
+			b'"' => self.string().map(Value::String),
+			b't' => self.expect(b"true").map(|_| Value::Bool(true)),
+			b'f' => self.expect(b"false").map(|_| Value::Bool(false)),
+			b'n' => self.expect(b"null").map(|_| Value::Null),
+			b'-' | b'0'..=b'9' => self.number(),
+			_ => Err(self.pos),
+		}
+	}
+
+	fn object(&mut self) -> Result<Value, usize> {
+		self.pos += 1;
+		let mut members = Vec::new();
+		self.skip_ws();
+		if self.peek() == Some(b'}') {
+			self.pos += 1;
+			return Ok(Value::Object(members));
+		}
+		loop {
+			self.skip_ws();
+			if self.peek() != Some(b'"') {
+				return Err(self.pos);
+			}
+			let key = self.string()?;
+			self.skip_ws();
+			self.expect(b":")?;
+			members.push((key, self.value()?));
+			self.skip_ws();
+			match self.next()? {
+				b',' => continue,
+				b'}' => return Ok(Value::Object(members)),
+				_ => return Err(self.pos - 1),
+			}
+		}
+	}
+
+	fn array(&mut self) -> Result<Value, usize> {
+		self.pos += 1;
+		let mut items = Vec::new();
+		self.skip_ws();
+		if self.peek() == Some(b']') {
+			self.pos += 1;
+			return Ok(Value::Array(items));
+		}
+		loop {
+			items.push(self.value()?);
+			self.skip_ws();
+			match self.next()? {
+				b',' => continue,
+				b']' => return Ok(Value::Array(items)),
+				_ => return Err(self.pos - 1),
+			}
+		}
+	}
+
+	fn number(&mut self) -> Result<Value, usize> {
+		let start = self.pos;
+		while matches!(self.peek(), Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')) {
+			self.pos += 1;
+		}
+		// Source is `str` and the number is ASCII:
+		let s = core::str::from_utf8(&self.src[start..self.pos]).map_err(|_| start)?;
+		s.parse().map(Value::Number).map_err(|_| start)
+	}
+
+	fn string(&mut self) -> Result<String, usize> {
+		self.pos += 1;
+		let mut s = String::new();
+		loop {
+			let start = self.pos;
+			while !matches!(self.peek(), Some(b'"' | b'\\') | None) {
+				self.pos += 1;
+			}
+			// Split at ASCII, so the chunk is valid UTF-8:
+			s.push_str(core::str::from_utf8(&self.src[start..self.pos]).map_err(|_| start)?);
+			match self.next()? {
+				b'"' => return Ok(s),
+				_ => {
+					let c = match self.next()? {
+						b'"' => '"',
+						b'\\' => '\\',
+						b'/' => '/',
+						b'b' => '\u{8}',
+						b'f' => '\u{c}',
+						b'n' => '\n',
+						b'r' => '\r',
+						b't' => '\t',
+						b'u' => self.escaped_char()?,
+						_ => return Err(self.pos - 1),
+					};
+					s.push(c);
+				},
+			}
+		}
+	}
+
+	/// Reads `XXXX` of the `\uXXXX` escape, with the low surrogate if needed.
+	fn escaped_char(&mut self) -> Result<char, usize> {
+		let start = self.pos;
+		let high = self.hex4()?;
+		let code = if (0xD800..0xDC00).contains(&high) {
+			self.expect(b"\\u")?;
+			let low = self.hex4()?;
+			if !(0xDC00..0xE000).contains(&low) {
+				return Err(start);
+			}
+			0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+		} else {
+			high
+		};
+		char::from_u32(code).ok_or(start)
+	}
+
+	fn hex4(&mut self) -> Result<u32, usize> {
+		let hex = self.src.get(self.pos..self.pos + 4).ok_or(self.pos)?;
+		let hex = core::str::from_utf8(hex).map_err(|_| self.pos)?;
+		let code = u32::from_str_radix(hex, 16).map_err(|_| self.pos)?;
+		self.pos += 4;
+		Ok(code)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	fn values() {
+		let value = parse(r#" {"a": [1, -2.5e1, true, null], "bé😀": "x\"y", "c": {}} "#).unwrap();
+		let a = value.get("a").unwrap().as_array().unwrap();
+		assert_eq!(a[0].as_int(), Some(1));
+		assert_eq!(a[1], Value::Number(-25.0));
+		assert_eq!(a[1].as_int(), Some(-25));
+		assert_eq!(a[2].as_bool(), Some(true));
+		assert_eq!(value.get("bé😀").and_then(Value::as_str), Some("x\"y"));
+		assert_eq!(value.get("c").and_then(Value::as_object), Some(&[][..]));
+
+		assert_eq!(parse("[1, 2"), Err(5));
+		assert_eq!(parse("{\"a\" 1}"), Err(5));
+		assert_eq!(parse("1 2"), Err(2));
+	}
+}
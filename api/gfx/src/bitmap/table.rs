@@ -10,7 +10,14 @@ use fs::Path;
 
 use crate::error::ApiError;
 use crate::error::Error;
+use crate::geometry::Rect;
+use super::AnyBitmap;
 use super::Bitmap;
+use super::BitmapFlip;
+use super::BitmapRef;
+use super::Color;
+use super::sheet::Grid;
+use super::sheet::Sheet;
 use super::api::Api as BitmapApi;
 
 
@@ -92,6 +99,68 @@ impl<Api: api::Api> BitmapTable<Api, true> {
 			Ok(Self(ptr, api))
 		}
 	}
+
+
+	/// Allocates and returns a new [`BitmapTable`] with cells of the `grid` sliced from the `atlas`.
+	///
+	/// Only whole cells are sliced, row by row, see [`Sheet::from_grid`].
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getBitmapData`] and [`sys::ffi::playdate_graphics::newBitmapTable`].
+	#[doc(alias = "sys::ffi::playdate_graphics::newBitmapTable")]
+	pub fn from_grid(atlas: &impl AnyBitmap, grid: &Grid) -> Result<Self, Error>
+		where Api: Default + crate::api::Api + Clone {
+		let (mut width, mut height) = (0, 0);
+		let f = atlas.api_ref().get_bitmap_data();
+		unsafe {
+			f(
+			  atlas.as_raw(),
+			  &mut width,
+			  &mut height,
+			  core::ptr::null_mut(),
+			  core::ptr::null_mut(),
+			  core::ptr::null_mut(),
+			)
+		};
+		let sheet = Sheet::from_grid(crate::geometry::Size::new(width, height), grid);
+		Self::from_sheet_with(Api::default(), atlas, &sheet)
+	}
+
+	/// Allocates and returns a new [`BitmapTable`] with frames of the `sheet` sliced from the `atlas`.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::newBitmapTable`].
+	#[doc(alias = "sys::ffi::playdate_graphics::newBitmapTable")]
+	pub fn from_sheet(atlas: &impl AnyBitmap, sheet: &Sheet) -> Result<Self, Error>
+		where Api: Default + crate::api::Api + Clone {
+		Self::from_sheet_with(Api::default(), atlas, sheet)
+	}
+
+	/// Allocates and returns a new [`BitmapTable`] with frames of the `sheet` sliced from the `atlas`,
+	/// using the given `api`.
+	///
+	/// Each frame is drawn into its cell at the frame's offset, rest of the cell is transparent.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::newBitmapTable`].
+	#[doc(alias = "sys::ffi::playdate_graphics::newBitmapTable")]
+	pub fn from_sheet_with(api: Api, atlas: &impl AnyBitmap, sheet: &Sheet) -> Result<Self, Error>
+		where Api: crate::api::Api + Clone {
+		let cell = sheet.cell();
+		let gfx = crate::Graphics::new_with(api.clone());
+		let table = Self::new_with(api, sheet.frames().len() as _, cell.width, cell.height)?;
+
+		for (i, frame) in sheet.frames().iter().enumerate() {
+			let bitmap = table.bitmap(i as _)
+			                  .and_then(|bitmap| bitmap.into_bitmap_with(table.1.clone()).ok())
+			                  .ok_or(Error::Alloc)?;
+			bitmap.clear(Color::CLEAR);
+
+			gfx.with_context(&bitmap, |ctx| {
+				   let _clip = ctx.clip_guard(Rect::from_origin_size(frame.offset, frame.rect.size()));
+				   let (x, y) = (frame.offset.x - frame.rect.x, frame.offset.y - frame.rect.y);
+				   ctx.draw(atlas, x, y, BitmapFlip::kBitmapUnflipped);
+			   });
+		}
+		Ok(table)
+	}
 }
 
 impl<Api: api::Api, const FOD: bool> BitmapTable<Api, FOD> {
@@ -151,6 +220,30 @@ impl<Api: api::Api, const FOD: bool> BitmapTable<Api, FOD> {
 		}
 	}
 
+	/// Returns the `index` bitmap in this table borrowed from the table,
+	/// if `index` is out of bounds, the function returns `None`.
+	///
+	/// Unlike [`get`](BitmapTable::get), returned bitmap is never freed on drop.
+	///
+	/// Equivalent to [`sys::ffi::playdate_graphics::getTableBitmap`].
+	#[doc(alias = "sys::ffi::playdate_graphics::getTableBitmap")]
+	pub fn bitmap(&self, index: c_int) -> Option<BitmapRef<'_>> {
+		let f = self.1.get_table_bitmap();
+		let ptr = unsafe { f(self.0, index) };
+		(!ptr.is_null()).then(|| BitmapRef::from(ptr))
+	}
+
+	/// Returns the number of images in this table.
+	///
+	/// Equivalent to [`sys::ffi::playdate_graphics::getBitmapTableInfo`].
+	#[doc(alias = "sys::ffi::playdate_graphics::getBitmapTableInfo")]
+	pub fn count(&self) -> c_int {
+		let mut count = 0;
+		let f = self.1.get_bitmap_table_info();
+		unsafe { f(self.0, &mut count, core::ptr::null_mut()) };
+		count
+	}
+
 	/// Returns the bitmap table’s image count in the `count` if not `None`
 	/// and number of cells across in the `width` (ditto) if not `None` .
 	///
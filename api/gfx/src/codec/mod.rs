@@ -0,0 +1,243 @@
+//! Encoders and decoders of PNG and PBM images.
+//!
+//! Decoded images are 1-bit, greyscale and color input is converted
+//! with [Floyd–Steinberg dithering](Dither::FloydSteinberg), alpha is converted to the mask.
+
+use core::ffi::c_int;
+use core::fmt;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use fs::Path;
+
+use crate::api;
+use crate::bitmap::Bitmap;
+use crate::bitmap::BitmapData;
+use crate::bitmap::ops;
+use crate::bitmap::ops::Dither;
+use crate::error::ApiError;
+use crate::color::Color;
+
+pub mod png;
+pub mod pbm;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+	/// Unknown image format.
+	Format,
+	/// Known format, but unsupported variant of it, e.g. interlaced PNG.
+	Unsupported,
+	/// Image data is truncated or corrupted.
+	Corrupted,
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Format => write!(f, "Unknown image format"),
+			Error::Unsupported => write!(f, "Unsupported image format"),
+			Error::Corrupted => write!(f, "Corrupted image data"),
+		}
+	}
+}
+
+impl core::error::Error for Error {}
+
+
+/// Decoded 1-bit image.
+///
+/// Data and optional mask are packed 1 bit per pixel, rows are `(width + 7) / 8` bytes,
+/// in same format as [`BitmapData`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+	width: c_int,
+	height: c_int,
+	data: Vec<u8>,
+	mask: Option<Vec<u8>>,
+}
+
+
+// ctor //
+
+impl Image {
+	/// Creates white `width` by `height` image without mask.
+	pub fn new(width: c_int, height: c_int) -> Self {
+		let len = row_bytes(width) * height.max(0) as usize;
+		Self { width: width.max(0),
+		       height: height.max(0),
+		       data: vec![0xFF; len],
+		       mask: None }
+	}
+
+	/// Creates image from `luma` values, row by row, dithering them to 1-bit.
+	///
+	/// Pixels with `alpha` less than `128` are transparent.
+	///
+	/// Returns [`Error::Corrupted`] if lengths of the `luma` or `alpha` are not `width * height`.
+	pub fn from_luma(width: c_int, height: c_int, luma: &[u8], alpha: Option<&[u8]>) -> Result<Self, Error> {
+		let len = width.max(0) as usize * height.max(0) as usize;
+		if luma.len() != len || alpha.is_some_and(|alpha| alpha.len() != len) {
+			return Err(Error::Corrupted);
+		}
+
+		let mut image = Self::new(width, height);
+		// Keep padding bits clear, dithering only writes pixels:
+		image.data.fill(0);
+		ops::dither(luma, Dither::FloydSteinberg, &mut image.bitmap_data()).map_err(|_| Error::Corrupted)?;
+		image.mask = alpha.filter(|alpha| alpha.iter().any(|a| *a < 128))
+		                  .map(|alpha| threshold(alpha, width as usize));
+		Ok(image)
+	}
+
+	/// Copies pixels and mask of the `bitmap`.
+	pub fn from_bitmap_data(bitmap: &BitmapData) -> Self {
+		Self { width: bitmap.width(),
+		       height: bitmap.height(),
+		       data: bitmap.to_packed(),
+		       mask: bitmap.mask_to_packed() }
+	}
+
+	/// Decodes PNG or PBM image, detecting format by its signature.
+	pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+		if bytes.starts_with(&png::SIGNATURE) {
+			png::decode(bytes)
+		} else if bytes.first() == Some(&b'P') {
+			pbm::decode(bytes)
+		} else {
+			Err(Error::Format)
+		}
+	}
+}
+
+
+// utils //
+
+impl Image {
+	pub const fn width(&self) -> c_int { self.width }
+	pub const fn height(&self) -> c_int { self.height }
+	/// Row stride of the data and mask in bytes.
+	pub fn row_bytes(&self) -> usize { row_bytes(self.width) }
+	pub fn data(&self) -> &[u8] { &self.data }
+	pub fn mask(&self) -> Option<&[u8]> { self.mask.as_deref() }
+
+	/// Returns pixels of the image as [`BitmapData`] to read or modify them.
+	pub fn bitmap_data(&mut self) -> BitmapData<'_> {
+		let row_bytes = self.row_bytes() as c_int;
+		BitmapData::new(
+		                self.width,
+		                self.height,
+		                row_bytes,
+		                &mut self.data,
+		                self.mask.as_deref_mut(),
+		).expect("image data length")
+	}
+}
+
+
+// impl //
+
+impl Image {
+	/// Encodes the image to PNG.
+	pub fn encode_png(&mut self) -> Vec<u8> { png::encode(&self.bitmap_data()) }
+
+	/// Encodes the image to binary PBM, the mask is lost.
+	pub fn encode_pbm(&mut self) -> Vec<u8> { pbm::encode(&self.bitmap_data()) }
+
+	/// Creates new [`Bitmap`] with the image.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::newBitmap`].
+	#[doc(alias = "sys::ffi::playdate_graphics::newBitmap")]
+	pub fn to_bitmap(&self) -> Result<Bitmap<api::Default, true>, crate::error::Error> {
+		self.to_bitmap_with(api::Default)
+	}
+
+	/// Creates new [`Bitmap`] with the image using given `api`.
+	///
+	/// Bitmap is created with mask only if the image has one.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::newBitmap`].
+	#[doc(alias = "sys::ffi::playdate_graphics::newBitmap")]
+	pub fn to_bitmap_with<Api: api::Api>(&self, api: Api) -> Result<Bitmap<Api, true>, crate::error::Error> {
+		let bg = if self.mask.is_some() {
+			Color::CLEAR
+		} else {
+			Color::WHITE
+		};
+		let mut bitmap = Bitmap::new_with(api, self.width, self.height, bg)?;
+		{
+			let mut data = bitmap.bitmap_data();
+			data.copy_from_packed(&self.data)?;
+			if let Some(mask) = self.mask.as_deref() {
+				data.copy_mask_from_packed(mask)?;
+			}
+		}
+		Ok(bitmap)
+	}
+}
+
+
+/// Loads PNG or PBM image from the file at `path` into new [`Bitmap`].
+///
+/// If `data_dir` is `true`, the file is looked for in the data folder first.
+///
+/// Uses [`fs::read`] and [`Image::to_bitmap`].
+pub fn load<P: AsRef<Path>>(path: P, data_dir: bool) -> Result<Bitmap<api::Default, true>, ApiError> {
+	let bytes = fs::read(path, data_dir).map_err(ApiError::from_err)?;
+	let image = Image::decode(&bytes).map_err(|err| ApiError::Api(crate::error::Error::Codec(err)))?;
+	image.to_bitmap().map_err(ApiError::Api)
+}
+
+/// Writes the `bitmap` with its mask to the file at `path` as PNG.
+///
+/// Uses [`fs::write`].
+pub fn save_png<Api: api::Api, const FOD: bool, P: AsRef<Path>>(bitmap: &mut Bitmap<Api, FOD>,
+                                                                path: P)
+                                                                -> Result<(), ApiError> {
+	let bytes = png::encode(&bitmap.bitmap_data());
+	fs::write(path, bytes).map_err(ApiError::from_err)
+}
+
+/// Writes the `bitmap` to the file at `path` as binary PBM, the mask is lost.
+///
+/// Uses [`fs::write`].
+pub fn save_pbm<Api: api::Api, const FOD: bool, P: AsRef<Path>>(bitmap: &mut Bitmap<Api, FOD>,
+                                                                path: P)
+                                                                -> Result<(), ApiError> {
+	let bytes = pbm::encode(&bitmap.bitmap_data());
+	fs::write(path, bytes).map_err(ApiError::from_err)
+}
+
+
+fn row_bytes(width: c_int) -> usize { (width.max(0) as usize).div_ceil(8) }
+
+/// Packs `values` to 1 bit per pixel, set where the value is `128` or more.
+fn threshold(values: &[u8], width: usize) -> Vec<u8> {
+	let row_bytes = width.div_ceil(8);
+	let mut packed = vec![0; row_bytes * values.len().checked_div(width).unwrap_or(0)];
+	for (i, v) in values.iter().enumerate() {
+		if *v >= 128 {
+			let (x, y) = (i % width, i / width);
+			packed[y * row_bytes + x / 8] |= 0x80 >> (x % 8);
+		}
+	}
+	packed
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	fn from_luma_with_alpha() {
+		let image = Image::from_luma(3, 1, &[255, 0, 255], Some(&[255, 255, 0])).unwrap();
+		assert_eq!(image.data(), [0b1010_0000]);
+		assert_eq!(image.mask(), Some(&[0b1100_0000][..]));
+
+		let opaque = Image::from_luma(1, 1, &[0], Some(&[255])).unwrap();
+		assert_eq!(opaque.mask(), None);
+		assert!(Image::from_luma(2, 1, &[0], None).is_err());
+	}
+}
@@ -0,0 +1,206 @@
+//! Netpbm encoder and decoder.
+//!
+//! Decoder supports bitmaps (PBM, `P1` and `P4`) and greymaps (PGM, `P2` and `P5`).
+//! Encoder writes binary PBM (`P4`).
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::bitmap::BitmapData;
+use super::Error;
+use super::Image;
+
+
+/// Encodes the `bitmap` to binary PBM.
+///
+/// PBM has no transparency, so the mask is lost.
+pub fn encode(bitmap: &BitmapData) -> Vec<u8> {
+	let (width, height) = (bitmap.width().max(0), bitmap.height().max(0));
+	let mut pbm = format!("P4\n{width} {height}\n").into_bytes();
+
+	// In PBM set bit is black:
+	let mut packed = bitmap.to_packed();
+	packed.iter_mut().for_each(|b| *b = !*b);
+	let rem = width % 8;
+	if rem != 0 {
+		let row_bytes = (width as usize).div_ceil(8);
+		packed.chunks_mut(row_bytes)
+		      .for_each(|row| row[row_bytes - 1] &= 0xFF << (8 - rem));
+	}
+	pbm.extend_from_slice(&packed);
+	pbm
+}
+
+
+/// Decodes the PBM or PGM image.
+///
+/// Greymaps are dithered.
+pub fn decode(bytes: &[u8]) -> Result<Image, Error> {
+	let mut reader = Reader { bytes, pos: 0 };
+	let magic = reader.bytes.get(..2).ok_or(Error::Format)?;
+	reader.pos = 2;
+
+	let ascii = match magic {
+		b"P1" | b"P2" => true,
+		b"P4" | b"P5" => false,
+		[b'P', b'3' | b'6' | b'7'] => return Err(Error::Unsupported),
+		_ => return Err(Error::Format),
+	};
+	let bitmap = matches!(magic, b"P1" | b"P4");
+
+	let width = reader.number()?;
+	let height = reader.number()?;
+	let max = if bitmap { 1 } else { reader.number()? };
+	if width > i32::MAX as usize || height > i32::MAX as usize {
+		return Err(Error::Unsupported);
+	}
+	if max == 0 || max > u16::MAX as usize {
+		return Err(Error::Corrupted);
+	}
+	let len = width.checked_mul(height).ok_or(Error::Unsupported)?;
+
+	if !ascii {
+		// Single whitespace before the raster:
+		reader.pos += 1;
+	}
+	let raster = reader.bytes.get(reader.pos..).unwrap_or_default();
+
+	if bitmap && !ascii {
+		let len = width.div_ceil(8).checked_mul(height).ok_or(Error::Unsupported)?;
+		let raster = raster.get(..len).ok_or(Error::Corrupted)?;
+		let mut image = Image::new(width as _, height as _);
+		let data: Vec<u8> = raster.iter().map(|b| !*b).collect();
+		image.bitmap_data()
+		     .copy_from_packed(&data)
+		     .map_err(|_| Error::Corrupted)?;
+		return Ok(image);
+	}
+
+	let mut luma;
+	if ascii {
+		// Each value takes at least one byte:
+		luma = Vec::with_capacity(len.min(raster.len()));
+		for _ in 0..len {
+			let value = if bitmap { reader.bit()? } else { reader.number()? };
+			if value > max {
+				return Err(Error::Corrupted);
+			}
+			luma.push(level(value, max, bitmap));
+		}
+	} else {
+		let size = if max > u8::MAX as usize { 2 } else { 1 };
+		let raster = len.checked_mul(size)
+		                .and_then(|end| raster.get(..end))
+		                .ok_or(Error::Corrupted)?;
+		luma = Vec::with_capacity(len);
+		for sample in raster.chunks(size) {
+			let value = sample.iter().fold(0, |v, b| (v << 8) | *b as usize);
+			luma.push(level(value.min(max), max, false));
+		}
+	}
+
+	Image::from_luma(width as _, height as _, &luma, None)
+}
+
+
+/// Converts the `value` to 8-bit luma.
+fn level(value: usize, max: usize, bitmap: bool) -> u8 {
+	if bitmap {
+		// In PBM `1` is black:
+		if value == 0 { 0xFF } else { 0 }
+	} else {
+		(value * 255 / max) as u8
+	}
+}
+
+
+struct Reader<'t> {
+	bytes: &'t [u8],
+	pos: usize,
+}
+
+impl Reader<'_> {
+	/// Skips whitespace and comments.
+	fn skip(&mut self) {
+		while let Some(b) = self.bytes.get(self.pos) {
+			match b {
+				b'#' => {
+					while self.bytes.get(self.pos).is_some_and(|b| *b != b'\n') {
+						self.pos += 1;
+					}
+				},
+				b if b.is_ascii_whitespace() => self.pos += 1,
+				_ => break,
+			}
+		}
+	}
+
+	fn number(&mut self) -> Result<usize, Error> {
+		self.skip();
+		let start = self.pos;
+		let mut value: usize = 0;
+		while let Some(digit) = self.bytes.get(self.pos).filter(|b| b.is_ascii_digit()) {
+			value = value.checked_mul(10)
+			             .and_then(|v| v.checked_add((digit - b'0') as usize))
+			             .ok_or(Error::Corrupted)?;
+			self.pos += 1;
+		}
+		if self.pos == start {
+			return Err(Error::Corrupted);
+		}
+		Ok(value)
+	}
+
+	/// Reads single digit of ASCII bitmap, digits can be not separated.
+	fn bit(&mut self) -> Result<usize, Error> {
+		self.skip();
+		let digit = match self.bytes.get(self.pos) {
+			Some(b'0') => 0,
+			Some(b'1') => 1,
+			_ => return Err(Error::Corrupted),
+		};
+		self.pos += 1;
+		Ok(digit)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+
+	#[test]
+	fn ascii_and_binary() {
+		let ascii = decode(b"P1\n# comment\n3 2\n1 0 1\n010").unwrap();
+		assert_eq!(ascii.data(), [0b0100_0000, 0b1010_0000]);
+
+		let mut data = vec![0b0100_0000, 0b1010_0000];
+		let bitmap = BitmapData::new(3, 2, 1, &mut data, None).unwrap();
+		let pbm = encode(&bitmap);
+		assert_eq!(pbm, b"P4\n3 2\n\xA0\x40");
+		let mut binary = decode(&pbm).unwrap();
+		assert_eq!(binary.bitmap_data().to_packed(), ascii.data());
+
+		let grey = decode(b"P2 2 1 4 0 4").unwrap();
+		assert_eq!(grey.data(), [0b0100_0000]);
+	}
+
+	#[test]
+	fn truncated() {
+		assert_eq!(decode(b"P4\n3 2\n\xA0").err(), Some(Error::Corrupted));
+		assert_eq!(decode(b"P1 3 2 1 0 1 0").err(), Some(Error::Corrupted));
+		assert_eq!(decode(b"P5 2 2 65535\n\0\0\0").err(), Some(Error::Corrupted));
+		assert_eq!(decode(b"P2 2").err(), Some(Error::Corrupted));
+	}
+
+	#[test]
+	fn oversized() {
+		assert_eq!(decode(b"P2 65535 65535 1").err(), Some(Error::Corrupted));
+		assert_eq!(decode(b"P5 65535 65535 255\n").err(), Some(Error::Corrupted));
+		assert_eq!(decode(b"P4 65535 65535\n").err(), Some(Error::Corrupted));
+		assert_eq!(decode(b"P4 4294967296 1\n").err(), Some(Error::Unsupported));
+		assert_eq!(decode(b"P1 99999999999999999999 1").err(), Some(Error::Corrupted));
+	}
+}
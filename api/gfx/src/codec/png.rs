@@ -0,0 +1,420 @@
+//! PNG encoder and decoder.
+//!
+//! Decoder supports all non-interlaced PNG images:
+//! greyscale, truecolor and indexed, with or without alpha, of any bit depth.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::bitmap::BitmapData;
+use super::Error;
+use super::Image;
+
+
+pub const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+const GREY: u8 = 0;
+const RGB: u8 = 2;
+const INDEXED: u8 = 3;
+const GREY_ALPHA: u8 = 4;
+const RGBA: u8 = 6;
+
+
+/// Encodes the `bitmap` to PNG.
+///
+/// Bitmap without mask is encoded as 1-bit greyscale,
+/// with mask as 2-bit indexed with transparent color.
+pub fn encode(bitmap: &BitmapData) -> Vec<u8> {
+	let (width, height) = (bitmap.width().max(0) as usize, bitmap.height().max(0) as usize);
+	let masked = bitmap.mask().is_some();
+
+	// Filter type byte and pixels for each row:
+	let (depth, color_type) = if masked { (2, INDEXED) } else { (1, GREY) };
+	let stride = (width * depth as usize).div_ceil(8);
+	let mut raw = Vec::with_capacity((stride + 1) * height);
+	for y in 0..height as _ {
+		raw.push(0);
+		if masked {
+			let mut row = vec![0_u8; stride];
+			for (x, color) in bitmap.row_pixels(y).enumerate() {
+				use sys::ffi::LCDSolidColor::*;
+				let index = match color {
+					kColorBlack => 0,
+					kColorWhite => 1,
+					_ => 2,
+				};
+				row[x / 4] |= index << (6 - (x % 4) * 2);
+			}
+			raw.extend_from_slice(&row);
+		} else if let Some(row) = bitmap.row(y) {
+			raw.extend_from_slice(&row[..stride]);
+		}
+	}
+
+	let mut png = Vec::from(SIGNATURE);
+	let mut header = Vec::with_capacity(13);
+	header.extend_from_slice(&(width as u32).to_be_bytes());
+	header.extend_from_slice(&(height as u32).to_be_bytes());
+	header.extend_from_slice(&[depth, color_type, 0, 0, 0]);
+	write_chunk(&mut png, b"IHDR", &header);
+	if masked {
+		write_chunk(&mut png, b"PLTE", &[0, 0, 0, 0xFF, 0xFF, 0xFF, 0, 0, 0]);
+		write_chunk(&mut png, b"tRNS", &[0xFF, 0xFF, 0]);
+	}
+	write_chunk(
+	            &mut png,
+	            b"IDAT",
+	            &miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6),
+	);
+	write_chunk(&mut png, b"IEND", &[]);
+	png
+}
+
+
+/// Decodes the PNG image.
+pub fn decode(bytes: &[u8]) -> Result<Image, Error> {
+	let mut rest = bytes.strip_prefix(&SIGNATURE[..]).ok_or(Error::Format)?;
+
+	let mut header = None;
+	let mut palette: &[u8] = &[];
+	let mut transparency: &[u8] = &[];
+	let mut idat = Vec::new();
+
+	while !rest.is_empty() {
+		let (kind, data, next) = read_chunk(rest)?;
+		rest = next;
+		match kind {
+			b"IHDR" => header = Some(Header::parse(data)?),
+			b"PLTE" => palette = data,
+			b"tRNS" => transparency = data,
+			b"IDAT" => idat.extend_from_slice(data),
+			b"IEND" => break,
+			// Critical chunks have uppercase first letter:
+			kind if kind[0].is_ascii_uppercase() => return Err(Error::Unsupported),
+			_ => {},
+		}
+	}
+
+	let header = header.ok_or(Error::Corrupted)?;
+	let len = header.raw_len().ok_or(Error::Unsupported)?;
+	let raw = miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(&idat, len).map_err(|_| Error::Corrupted)?;
+	let pixels = header.unfilter(&raw)?;
+
+	let (width, height) = (header.width, header.height);
+	let len = width.checked_mul(height).ok_or(Error::Unsupported)?;
+	let mut luma = Vec::with_capacity(len);
+	let mut alpha = Vec::with_capacity(len);
+	let stride = header.stride();
+	for row in pixels.chunks(stride.max(1)) {
+		for x in 0..width {
+			let (l, a) = header.pixel(row, x, palette, transparency)?;
+			luma.push(l);
+			alpha.push(a);
+		}
+	}
+
+	Image::from_luma(width as _, height as _, &luma, Some(&alpha))
+}
+
+
+struct Header {
+	width: usize,
+	height: usize,
+	depth: u8,
+	color_type: u8,
+}
+
+impl Header {
+	fn parse(data: &[u8]) -> Result<Self, Error> {
+		let [
+		     w0,
+		     w1,
+		     w2,
+		     w3,
+		     h0,
+		     h1,
+		     h2,
+		     h3,
+		     depth,
+		     color_type,
+		     compression,
+		     filter,
+		     interlace,
+		] = *data
+		else {
+			return Err(Error::Corrupted);
+		};
+		let width = u32::from_be_bytes([w0, w1, w2, w3]) as usize;
+		let height = u32::from_be_bytes([h0, h1, h2, h3]) as usize;
+
+		let valid = match color_type {
+			GREY => matches!(depth, 1 | 2 | 4 | 8 | 16),
+			INDEXED => matches!(depth, 1 | 2 | 4 | 8),
+			RGB | GREY_ALPHA | RGBA => matches!(depth, 8 | 16),
+			_ => false,
+		};
+		if !valid || compression != 0 || filter != 0 {
+			return Err(Error::Corrupted);
+		}
+		if interlace != 0 || width > i32::MAX as usize || height > i32::MAX as usize {
+			return Err(Error::Unsupported);
+		}
+
+		let header = Self { width,
+		                    height,
+		                    depth,
+		                    color_type };
+		header.raw_len().ok_or(Error::Unsupported)?;
+		Ok(header)
+	}
+
+	const fn channels(&self) -> usize {
+		match self.color_type {
+			RGB => 3,
+			GREY_ALPHA => 2,
+			RGBA => 4,
+			_ => 1,
+		}
+	}
+
+	/// Bytes per row, without the filter type byte.
+	///
+	/// Doesn't overflow for the parsed header, see [`Header::raw_len`].
+	const fn stride(&self) -> usize { (self.width * self.channels() * self.depth as usize).div_ceil(8) }
+
+	/// Bytes of all rows with filter type bytes, or `None` on overflow.
+	fn raw_len(&self) -> Option<usize> {
+		let bits = self.width.checked_mul(self.channels() * self.depth as usize)?;
+		bits.div_ceil(8).checked_add(1)?.checked_mul(self.height)
+	}
+
+	/// Bytes per complete pixel, at least one.
+	const fn bpp(&self) -> usize {
+		let bits = self.channels() * self.depth as usize;
+		if bits < 8 { 1 } else { bits / 8 }
+	}
+
+	/// Reverses filters of the rows, returning pixels without filter type bytes.
+	fn unfilter(&self, raw: &[u8]) -> Result<Vec<u8>, Error> {
+		let (stride, bpp) = (self.stride(), self.bpp());
+		if self.raw_len().is_none_or(|len| raw.len() < len) {
+			return Err(Error::Corrupted);
+		}
+
+		let mut pixels = vec![0_u8; stride * self.height];
+		for y in 0..self.height {
+			let src = &raw[y * (stride + 1)..(y + 1) * (stride + 1)];
+			let (prev, row) = pixels.split_at_mut(y * stride);
+			let prev = prev.get(prev.len().saturating_sub(stride)..).filter(|_| y > 0);
+			let row = &mut row[..stride];
+			row.copy_from_slice(&src[1..]);
+
+			for i in 0..stride {
+				let a = if i >= bpp { row[i - bpp] } else { 0 };
+				let b = prev.map_or(0, |prev| prev[i]);
+				let c = if i >= bpp {
+					prev.map_or(0, |prev| prev[i - bpp])
+				} else {
+					0
+				};
+				let predicted = match src[0] {
+					0 => 0,
+					1 => a,
+					2 => b,
+					3 => ((a as u16 + b as u16) / 2) as u8,
+					4 => paeth(a, b, c),
+					_ => return Err(Error::Corrupted),
+				};
+				row[i] = row[i].wrapping_add(predicted);
+			}
+		}
+		Ok(pixels)
+	}
+
+	/// Returns `index`-th sample of the `row`.
+	fn sample(&self, row: &[u8], index: usize) -> u16 {
+		match self.depth {
+			16 => u16::from_be_bytes([row[index * 2], row[index * 2 + 1]]),
+			8 => row[index] as u16,
+			depth => {
+				let bit = index * depth as usize;
+				let shift = 8 - depth as usize - bit % 8;
+				((row[bit / 8] >> shift) & ((1 << depth) - 1)) as u16
+			},
+		}
+	}
+
+	/// Scales the `sample` to 8 bits.
+	fn scale(&self, sample: u16) -> u8 {
+		match self.depth {
+			16 => (sample >> 8) as u8,
+			depth => (sample as u32 * 255 / ((1 << depth) - 1)) as u8,
+		}
+	}
+
+	/// Returns luma and alpha of the pixel `x` in the `row`.
+	fn pixel(&self, row: &[u8], x: usize, palette: &[u8], transparency: &[u8]) -> Result<(u8, u8), Error> {
+		let channels = self.channels();
+		let sample = |i: usize| self.sample(row, x * channels + i);
+		// Single transparent color, for greyscale and truecolor:
+		let transparent = |samples: &[u16]| {
+			transparency.len() == samples.len() * 2 &&
+			samples.iter()
+			       .zip(transparency.chunks(2))
+			       .all(|(s, t)| *s == u16::from_be_bytes([t[0], t[1]]))
+		};
+
+		Ok(match self.color_type {
+			GREY => {
+				let v = sample(0);
+				(self.scale(v), if transparent(&[v]) { 0 } else { 0xFF })
+			},
+			GREY_ALPHA => (self.scale(sample(0)), self.scale(sample(1))),
+			RGB => {
+				let rgb = [sample(0), sample(1), sample(2)];
+				let alpha = if transparent(&rgb) { 0 } else { 0xFF };
+				(luma(self.scale(rgb[0]), self.scale(rgb[1]), self.scale(rgb[2])), alpha)
+			},
+			RGBA => {
+				let l = luma(
+				             self.scale(sample(0)),
+				             self.scale(sample(1)),
+				             self.scale(sample(2)),
+				);
+				(l, self.scale(sample(3)))
+			},
+			_ => {
+				let i = sample(0) as usize;
+				let rgb = palette.get(i * 3..i * 3 + 3).ok_or(Error::Corrupted)?;
+				let alpha = transparency.get(i).copied().unwrap_or(0xFF);
+				(luma(rgb[0], rgb[1], rgb[2]), alpha)
+			},
+		})
+	}
+}
+
+
+fn luma(r: u8, g: u8, b: u8) -> u8 { ((r as u32 * 77 + g as u32 * 150 + b as u32 * 29) >> 8) as u8 }
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+	let p = a as i16 + b as i16 - c as i16;
+	let (pa, pb, pc) = ((p - a as i16).abs(), (p - b as i16).abs(), (p - c as i16).abs());
+	if pa <= pb && pa <= pc {
+		a
+	} else if pb <= pc {
+		b
+	} else {
+		c
+	}
+}
+
+
+fn crc32(bytes: &[u8]) -> u32 {
+	let mut crc = !0_u32;
+	for byte in bytes {
+		crc ^= *byte as u32;
+		for _ in 0..8 {
+			crc = if crc & 1 != 0 {
+				(crc >> 1) ^ 0xEDB8_8320
+			} else {
+				crc >> 1
+			};
+		}
+	}
+	!crc
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+	png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+	let start = png.len();
+	png.extend_from_slice(kind);
+	png.extend_from_slice(data);
+	let crc = crc32(&png[start..]);
+	png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Type and data of the chunk, and the rest of the bytes after it.
+type Chunk<'t> = (&'t [u8; 4], &'t [u8], &'t [u8]);
+
+/// Returns the first chunk in the `bytes`.
+fn read_chunk(bytes: &[u8]) -> Result<Chunk<'_>, Error> {
+	let len = bytes.get(..4).ok_or(Error::Corrupted)?;
+	let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+	let end = len.checked_add(12)
+	             .filter(|end| *end <= bytes.len())
+	             .ok_or(Error::Corrupted)?;
+
+	let (kind_data, crc) = bytes[4..end].split_at(len + 4);
+	if crc32(kind_data) != u32::from_be_bytes([crc[0], crc[1], crc[2], crc[3]]) {
+		return Err(Error::Corrupted);
+	}
+	let (kind, data) = kind_data.split_at(4);
+	Ok((kind.try_into().map_err(|_| Error::Corrupted)?, data, &bytes[end..]))
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	fn crc() {
+		assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+	}
+
+	#[test]
+	fn roundtrip() {
+		let mut data = vec![0b1010_1010, 0, 0b0101_0101, 0];
+		let mut mask = vec![0xFF, 0, 0b0111_1111, 0];
+		let bitmap = BitmapData::new(8, 2, 2, &mut data, None).unwrap();
+		let mut decoded = decode(&encode(&bitmap)).unwrap();
+		assert_eq!(decoded.mask(), None);
+		assert_eq!(decoded.bitmap_data().to_packed(), bitmap.to_packed());
+
+		let masked = BitmapData::new(8, 2, 2, &mut data, Some(&mut mask)).unwrap();
+		let decoded = decode(&encode(&masked)).unwrap();
+		assert_eq!(decoded.mask(), Some(&[0xFF, 0b0111_1111][..]));
+		// transparent pixel is black in the palette:
+		assert_eq!(decoded.data(), [0b1010_1010, 0b0101_0101]);
+	}
+
+	/// Returns PNG with the given header and raw data.
+	fn png(width: u32, height: u32, depth: u8, color_type: u8, raw: &[u8]) -> Vec<u8> {
+		let mut header = Vec::new();
+		header.extend_from_slice(&width.to_be_bytes());
+		header.extend_from_slice(&height.to_be_bytes());
+		header.extend_from_slice(&[depth, color_type, 0, 0, 0]);
+
+		let mut png = Vec::from(SIGNATURE);
+		write_chunk(&mut png, b"IHDR", &header);
+		write_chunk(
+		            &mut png,
+		            b"IDAT",
+		            &miniz_oxide::deflate::compress_to_vec_zlib(raw, 6),
+		);
+		write_chunk(&mut png, b"IEND", &[]);
+		png
+	}
+
+	#[test]
+	fn truncated() {
+		let grey = png(2, 2, 8, GREY, &[0, 0xFF, 0, 0, 0, 0xFF]);
+		assert!(decode(&grey).is_ok());
+		assert_eq!(decode(&grey[..grey.len() - 20]).err(), Some(Error::Corrupted));
+
+		let short = png(2, 2, 8, GREY, &[0, 0xFF, 0, 0]);
+		assert_eq!(decode(&short).err(), Some(Error::Corrupted));
+	}
+
+	#[test]
+	fn oversized() {
+		let max = i32::MAX as u32;
+		let huge = png(max, max, 16, RGBA, &[0]);
+		assert!(decode(&huge).is_err());
+
+		// Decompressed data is limited by the header:
+		let bomb = png(1, 1, 8, GREY, &vec![0; 1 << 16]);
+		assert_eq!(decode(&bomb).err(), Some(Error::Corrupted));
+	}
+}
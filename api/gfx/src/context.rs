@@ -0,0 +1,250 @@
+//! Scoped drawing state with RAII guards.
+//!
+//! Each guard changes the drawing state and restores the previous one on drop.
+//!
+//! The C-API has no getters for stencil, clip rect and draw offset,
+//! so for them "previous" is the state set by the outer guard within the current context,
+//! or the default state (no stencil, no clip, zero offset) if there is none.
+//! Pushed context starts with the default state, the outer one is kept by the context guard
+//! and restored when it pops the context.
+//! Changes made with raw setters such as [`Graphics::set_clip_rect`] are not tracked.
+//!
+//! Guards should be dropped in reverse order of creation, as they naturally are in nested scopes.
+//!
+//! Guards borrowing a bitmap are created by unsafe functions,
+//! because forgetting such guard leaves the bitmap in use by the C-part after the borrow ends.
+//! Use [`Graphics::with_context`] and [`Graphics::with_stencil`] instead.
+
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr::null_mut;
+
+use sys::ffi::LCDBitmap;
+
+use crate::bitmap::AnyBitmap;
+use crate::geometry::Point;
+use crate::geometry::Rect;
+use crate::BitmapDrawMode;
+use crate::Graphics;
+
+
+/// Drawing state set by the guards.
+#[derive(Clone, Copy)]
+struct State {
+	stencil: *mut LCDBitmap,
+	tiled: bool,
+	clip: Option<Rect>,
+	offset: Point,
+}
+
+impl State {
+	const DEFAULT: Self = Self { stencil: null_mut(),
+	                             tiled: false,
+	                             clip: None,
+	                             offset: Point::ZERO };
+}
+
+static mut STATE: State = State::DEFAULT;
+
+fn state_mut() -> &'static mut State { unsafe { &mut *core::ptr::addr_of_mut!(STATE) } }
+
+
+/// Drawing context pushed for the borrowed target bitmap.
+///
+/// Pops the context on drop, restoring the drawing settings from before the context was pushed.
+///
+/// Derefs to [`Graphics`], so it can be used to draw into the target.
+#[must_use = "Context is popped when dropped"]
+pub struct ContextGuard<'t, Api: crate::api::Api = crate::api::Default> {
+	gfx: Graphics<Api>,
+	/// State of the outer context.
+	outer: State,
+	_target: PhantomData<&'t ()>,
+}
+
+impl<Api: crate::api::Api> Deref for ContextGuard<'_, Api> {
+	type Target = Graphics<Api>;
+	fn deref(&self) -> &Self::Target { &self.gfx }
+}
+
+impl<Api: crate::api::Api> Drop for ContextGuard<'_, Api> {
+	fn drop(&mut self) {
+		self.gfx.pop_context();
+		*state_mut() = self.outer;
+	}
+}
+
+
+/// Stencil set for the lifetime of the guard, borrowing the stencil bitmap.
+#[must_use = "Previous stencil is restored when dropped"]
+pub struct StencilGuard<'t, Api: crate::api::Api = crate::api::Default> {
+	gfx: Graphics<Api>,
+	prev: State,
+	_stencil: PhantomData<&'t ()>,
+}
+
+impl<Api: crate::api::Api> Drop for StencilGuard<'_, Api> {
+	fn drop(&mut self) {
+		let f = self.gfx.0.set_stencil_image();
+		unsafe { f(self.prev.stencil, self.prev.tiled as _) };
+		let state = state_mut();
+		state.stencil = self.prev.stencil;
+		state.tiled = self.prev.tiled;
+	}
+}
+
+
+/// Draw mode set for the lifetime of the guard.
+#[must_use = "Previous draw mode is restored when dropped"]
+pub struct DrawModeGuard<Api: crate::api::Api = crate::api::Default> {
+	gfx: Graphics<Api>,
+	prev: BitmapDrawMode,
+}
+
+impl<Api: crate::api::Api> Drop for DrawModeGuard<Api> {
+	fn drop(&mut self) { let _ = self.gfx.set_draw_mode(self.prev); }
+}
+
+
+/// Clip rect set for the lifetime of the guard.
+#[must_use = "Previous clip rect is restored when dropped"]
+pub struct ClipGuard<Api: crate::api::Api = crate::api::Default> {
+	gfx: Graphics<Api>,
+	prev: Option<Rect>,
+}
+
+impl<Api: crate::api::Api> Drop for ClipGuard<Api> {
+	fn drop(&mut self) {
+		match self.prev {
+			Some(rect) => self.gfx.set_clip(rect),
+			None => self.gfx.clear_clip_rect(),
+		}
+		state_mut().clip = self.prev;
+	}
+}
+
+
+/// Draw offset set for the lifetime of the guard.
+#[must_use = "Previous draw offset is restored when dropped"]
+pub struct DrawOffsetGuard<Api: crate::api::Api = crate::api::Default> {
+	gfx: Graphics<Api>,
+	prev: Point,
+}
+
+impl<Api: crate::api::Api> Drop for DrawOffsetGuard<Api> {
+	fn drop(&mut self) {
+		self.gfx.set_draw_offset_point(self.prev);
+		state_mut().offset = self.prev;
+	}
+}
+
+
+impl<Api: crate::api::Api + Clone> Graphics<Api> {
+	/// Pushes a new drawing context for drawing into the `target`,
+	/// calls `f` and pops the context.
+	///
+	/// Uses [`Graphics::context_guard`].
+	pub fn with_context<R, F>(&self, target: &impl AnyBitmap, f: F) -> R
+		where F: FnOnce(&Graphics<Api>) -> R {
+		let guard = unsafe { self.context_guard(target) };
+		f(&guard)
+	}
+
+	/// Pushes a new drawing context for drawing into the `target`.
+	///
+	/// The `target` is borrowed while the returned guard is alive,
+	/// and the context is popped when the guard dropped.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::pushContext`].
+	///
+	/// # Safety
+	///
+	/// The guard must be dropped, not forgotten or leaked,
+	/// otherwise the `target` stays the drawing target after the borrow ends.
+	/// Prefer [`Graphics::with_context`].
+	#[doc(alias = "sys::ffi::playdate_graphics::pushContext")]
+	pub unsafe fn context_guard<'t>(&self, target: &'t impl AnyBitmap) -> ContextGuard<'t, Api> {
+		self.push_context(target);
+		ContextGuard { gfx: self.clone(),
+		               outer: core::mem::replace(state_mut(), State::DEFAULT),
+		               _target: PhantomData }
+	}
+
+	/// Pushes a new drawing context for drawing into the display framebuffer.
+	///
+	/// The context is popped when the guard dropped.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::pushContext`].
+	#[doc(alias = "sys::ffi::playdate_graphics::pushContext")]
+	pub fn framebuffer_context_guard(&self) -> ContextGuard<'static, Api> {
+		self.push_framebuffer_to_context();
+		ContextGuard { gfx: self.clone(),
+		               outer: core::mem::replace(state_mut(), State::DEFAULT),
+		               _target: PhantomData }
+	}
+
+	/// Sets the stencil used for drawing, calls `f` and restores the previous stencil.
+	///
+	/// Uses [`Graphics::stencil_guard`].
+	pub fn with_stencil<R, F>(&self, image: &impl AnyBitmap, tile: bool, f: F) -> R
+		where F: FnOnce(&Graphics<Api>) -> R {
+		let _guard = unsafe { self.stencil_guard(image, tile) };
+		f(self)
+	}
+
+	/// Sets the stencil used for drawing,
+	/// the `image` is borrowed while the returned guard is alive.
+	///
+	/// If the `tile` is `true` the stencil image will be tiled.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::setStencilImage`].
+	///
+	/// # Safety
+	///
+	/// The guard must be dropped, not forgotten or leaked,
+	/// otherwise the `image` stays the stencil after the borrow ends.
+	/// Prefer [`Graphics::with_stencil`].
+	#[doc(alias = "sys::ffi::playdate_graphics::setStencilImage")]
+	pub unsafe fn stencil_guard<'t>(&self, image: &'t impl AnyBitmap, tile: bool) -> StencilGuard<'t, Api> {
+		self.set_stencil_tiled(image, tile);
+		let state = state_mut();
+		let prev = *state;
+		state.stencil = unsafe { image.as_raw() };
+		state.tiled = tile;
+		StencilGuard { gfx: self.clone(),
+		               prev,
+		               _stencil: PhantomData }
+	}
+
+	/// Sets the mode used for drawing bitmaps.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::setDrawMode`].
+	#[doc(alias = "sys::ffi::playdate_graphics::setDrawMode")]
+	pub fn draw_mode_guard(&self, mode: BitmapDrawMode) -> DrawModeGuard<Api> {
+		let prev = self.set_draw_mode(mode);
+		DrawModeGuard { gfx: self.clone(),
+		                prev }
+	}
+
+	/// Sets the current clip rect, using __world__ coordinates.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::setClipRect`].
+	#[doc(alias = "sys::ffi::playdate_graphics::setClipRect")]
+	pub fn clip_guard(&self, rect: Rect) -> ClipGuard<Api> {
+		self.set_clip(rect);
+		let prev = state_mut().clip.replace(rect);
+		ClipGuard { gfx: self.clone(),
+		            prev }
+	}
+
+	/// Offsets the origin point for all drawing calls to `offset`.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::setDrawOffset`].
+	#[doc(alias = "sys::ffi::playdate_graphics::setDrawOffset")]
+	pub fn draw_offset_guard(&self, offset: Point) -> DrawOffsetGuard<Api> {
+		self.set_draw_offset_point(offset);
+		let prev = core::mem::replace(&mut state_mut().offset, offset);
+		DrawOffsetGuard { gfx: self.clone(),
+		                  prev }
+	}
+}
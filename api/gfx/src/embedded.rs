@@ -0,0 +1,128 @@
+//! [`embedded-graphics`](embedded_graphics_core) integration.
+//!
+//! Implements [`DrawTarget`] with [`BinaryColor`] for the [`FrameBuffer`] and [`BitmapData`].
+//!
+//! [`BinaryColor::On`] is black and [`BinaryColor::Off`] is white,
+//! as ink on the paper-like display.
+
+use core::convert::Infallible;
+use core::ffi::c_int;
+
+use color::Color;
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::OriginDimensions;
+use embedded_graphics_core::geometry::Size;
+use embedded_graphics_core::pixelcolor::BinaryColor;
+use embedded_graphics_core::primitives::Rectangle;
+use embedded_graphics_core::Pixel;
+use sys::ffi::LCDSolidColor;
+
+use crate::bitmap::BitmapData;
+use crate::frame::FrameBuffer;
+use crate::geometry::Rect;
+
+
+const fn solid(color: BinaryColor) -> LCDSolidColor {
+	match color {
+		BinaryColor::On => LCDSolidColor::kColorBlack,
+		BinaryColor::Off => LCDSolidColor::kColorWhite,
+	}
+}
+
+const fn color(color: BinaryColor) -> Color<'static> { Color::Solid(solid(color)) }
+
+fn size(width: c_int, height: c_int) -> Size { Size::new(width.max(0) as _, height.max(0) as _) }
+
+fn rect(area: &Rectangle) -> Rect {
+	Rect::new(
+	          area.top_left.x,
+	          area.top_left.y,
+	          area.size.width.min(c_int::MAX as _) as _,
+	          area.size.height.min(c_int::MAX as _) as _,
+	)
+}
+
+
+impl OriginDimensions for FrameBuffer<'_> {
+	fn size(&self) -> Size { size(self.width(), self.height()) }
+}
+
+/// Updated rows are tracked by the frame buffer,
+/// so for the display frame they are marked updated on flush or drop.
+impl DrawTarget for FrameBuffer<'_> {
+	type Color = BinaryColor;
+	type Error = Infallible;
+
+	fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+		where I: IntoIterator<Item = Pixel<Self::Color>> {
+		for Pixel(point, c) in pixels {
+			self.set_pixel(point.x, point.y, color(c));
+		}
+		Ok(())
+	}
+
+	fn fill_solid(&mut self, area: &Rectangle, c: Self::Color) -> Result<(), Self::Error> {
+		self.fill_rect(rect(area), color(c));
+		Ok(())
+	}
+
+	fn clear(&mut self, c: Self::Color) -> Result<(), Self::Error> {
+		FrameBuffer::clear(self, color(c));
+		Ok(())
+	}
+}
+
+
+impl OriginDimensions for BitmapData<'_> {
+	fn size(&self) -> Size { size(self.width(), self.height()) }
+}
+
+/// Draws into the bitmap's data.
+/// If the bitmap has a mask, drawn pixels are also made opaque.
+impl DrawTarget for BitmapData<'_> {
+	type Color = BinaryColor;
+	type Error = Infallible;
+
+	fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+		where I: IntoIterator<Item = Pixel<Self::Color>> {
+		for Pixel(point, c) in pixels {
+			self.set_pixel(point.x, point.y, solid(c));
+		}
+		Ok(())
+	}
+
+	fn fill_solid(&mut self, area: &Rectangle, c: Self::Color) -> Result<(), Self::Error> {
+		self.fill_rect(rect(area), solid(c));
+		Ok(())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+	use crate::frame::LEN;
+	use crate::frame::STRIDE;
+
+
+	#[test]
+	fn frame_draw_target() {
+		let mut buf = vec![0xFF; LEN];
+		let mut frame = FrameBuffer::new(&mut buf).unwrap();
+		let pixels = [
+		              Pixel((1, 2).into(), BinaryColor::On),
+		              Pixel((-1, 2).into(), BinaryColor::On),
+		];
+		frame.draw_iter(pixels).unwrap();
+		frame.fill_solid(&Rectangle::new((8, 3).into(), Size::new(8, 2)), BinaryColor::On)
+		     .unwrap();
+
+		assert_eq!(frame.dirty_rows(), Some((2, 4)));
+		assert_eq!(frame.size(), Size::new(400, 240));
+		let bytes = frame.as_bytes();
+		assert_eq!(bytes[2 * STRIDE], 0b1011_1111);
+		assert_eq!(&bytes[3 * STRIDE..3 * STRIDE + 3], &[0xFF, 0, 0xFF]);
+		assert_eq!(bytes[4 * STRIDE + 1], 0);
+	}
+}
@@ -18,13 +18,26 @@ pub enum Error {
 	/// Mask must be the same size as the target bitmap.
 	InvalidMask,
 
+	/// Buffer is too small for the frame of given size.
+	InvalidFrame,
+
+	/// Buffer is too small for the bitmap data of given size.
+	InvalidData,
+
 	/// Font error.
 	/// This occurs when char or page not found.
 	Font,
 
+	/// Image encoding or decoding error.
+	#[cfg(feature = "codec")]
+	Codec(crate::codec::Error),
+
 	/// Video error.
 	Video(CString),
 
+	/// Invalid converted Tiled map.
+	Map(crate::tilemap::tiled::ParseError),
+
 	/// Unknown error.
 	Unknown,
 }
@@ -36,12 +49,17 @@ impl fmt::Display for Error {
 			Error::Alloc => write!(f, "Allocation failed"),
 			Error::Font => write!(f, "Font error"),
 			Error::InvalidMask => write!(f, "Mask must be the same size as the target bitmap"),
+			Error::InvalidFrame => write!(f, "Buffer is too small for the frame"),
+			Error::InvalidData => write!(f, "Buffer is too small for the bitmap data"),
+			#[cfg(feature = "codec")]
+			Error::Codec(err) => err.fmt(f),
 			Error::Video(cs) => {
 				match cs.to_str() {
 					Ok(err) => err.fmt(f),
 					Err(_) => f.write_fmt(format_args!("Video error: {cs:?}")),
 				}
 			},
+			Error::Map(err) => err.fmt(f),
 			Error::Unknown => write!(f, "Unknown error"),
 		}
 	}
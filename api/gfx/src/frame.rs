@@ -0,0 +1,537 @@
+//! Software rasterizer over the 1-bit frame buffer.
+//!
+//! Draws directly into the memory returned by [`get_frame`](crate::get_frame),
+//! without calling the C-API for each primitive.
+//!
+//! Pixel format is the same as the display's:
+//! rows of MSB-ordered bytes, set bit is white.
+
+use alloc::boxed::Box;
+use core::ffi::c_int;
+
+use color::Color;
+use sys::ffi::LCDPattern;
+use sys::ffi::LCDSolidColor;
+use sys::ffi::LCD_COLUMNS;
+use sys::ffi::LCD_ROWS;
+use sys::ffi::LCD_ROWSIZE;
+
+use crate::bitmap::BitmapData;
+use crate::error::Error;
+#[cfg(feature = "transform")]
+use crate::geometry::Affine2D;
+use crate::geometry::Point;
+use crate::geometry::Rect;
+
+
+/// Width of the display frame in pixels.
+pub const WIDTH: c_int = LCD_COLUMNS as _;
+/// Height of the display frame in pixels.
+pub const HEIGHT: c_int = LCD_ROWS as _;
+/// Row stride of the display frame in bytes.
+pub const STRIDE: usize = LCD_ROWSIZE as _;
+/// Length of the display frame in bytes.
+pub const LEN: usize = STRIDE * HEIGHT as usize;
+
+
+/// Mutable view of the 1-bit frame buffer.
+///
+/// Tracks updated rows and reports them on [`flush`](FrameBuffer::flush) or drop.
+/// When created for the display with [`Graphics::frame_buffer`](crate::Graphics::frame_buffer),
+/// the rows are marked updated with [`mark_updated_rows`](crate::Graphics::mark_updated_rows).
+///
+/// Can be created over any byte buffer, e.g. `Vec<u8>`:
+/// ```no_run
+/// # use playdate_graphics::frame::*;
+/// # use playdate_graphics::color::Color;
+/// let mut buf = vec![0; LEN];
+/// let mut frame = FrameBuffer::new(&mut buf).unwrap();
+/// frame.clear(Color::WHITE);
+/// ```
+pub struct FrameBuffer<'buf> {
+	buf: &'buf mut [u8],
+	width: c_int,
+	height: c_int,
+	stride: usize,
+	dirty: Option<(c_int, c_int)>,
+	on_flush: Option<Box<dyn FnMut(c_int, c_int) + 'buf>>,
+}
+
+
+// ctor //
+
+impl<'buf> FrameBuffer<'buf> {
+	/// Creates view of display-sized frame, 400x240 with 52 bytes row stride.
+	///
+	/// Returns [`Error::InvalidFrame`] if the `buf` is shorter than [`LEN`].
+	pub fn new(buf: &'buf mut [u8]) -> Result<Self, Error> { Self::with_size(buf, WIDTH, HEIGHT, STRIDE) }
+
+	/// Creates view of `width` by `height` frame with given row `stride` in bytes.
+	///
+	/// Returns [`Error::InvalidFrame`] if the size is negative,
+	/// the `stride` is less than `width` needs,
+	/// or the `buf` is too short.
+	pub fn with_size(buf: &'buf mut [u8], width: c_int, height: c_int, stride: usize) -> Result<Self, Error> {
+		if width < 0 || height < 0 || stride * 8 < width as usize || buf.len() < stride * height as usize {
+			return Err(Error::InvalidFrame);
+		}
+
+		Ok(Self { buf,
+		          width,
+		          height,
+		          stride,
+		          dirty: None,
+		          on_flush: None })
+	}
+
+	/// Sets function called on [`flush`](FrameBuffer::flush) with range of updated rows, both included.
+	pub(crate) fn with_on_flush<F: FnMut(c_int, c_int) + 'buf>(mut self, f: F) -> Self {
+		self.on_flush = Some(Box::new(f));
+		self
+	}
+}
+
+
+impl Drop for FrameBuffer<'_> {
+	fn drop(&mut self) { self.flush() }
+}
+
+impl core::fmt::Debug for FrameBuffer<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("FrameBuffer")
+		 .field("width", &self.width)
+		 .field("height", &self.height)
+		 .field("stride", &self.stride)
+		 .field("dirty", &self.dirty)
+		 .finish_non_exhaustive()
+	}
+}
+
+
+// utils //
+
+impl FrameBuffer<'_> {
+	pub const fn width(&self) -> c_int { self.width }
+	pub const fn height(&self) -> c_int { self.height }
+	/// Row stride in bytes.
+	pub const fn stride(&self) -> usize { self.stride }
+	pub const fn bounds(&self) -> Rect { Rect::new(0, 0, self.width, self.height) }
+
+	pub fn as_bytes(&self) -> &[u8] { self.buf }
+
+	/// Returns raw bytes of the buffer.
+	///
+	/// Changes made through it are not tracked,
+	/// use [`mark_dirty`](FrameBuffer::mark_dirty) for them.
+	pub fn as_bytes_mut(&mut self) -> &mut [u8] { self.buf }
+
+	/// Returns range of updated rows, both included, since last [`flush`](FrameBuffer::flush).
+	pub const fn dirty_rows(&self) -> Option<(c_int, c_int)> { self.dirty }
+
+	/// Marks rows from `start` to `end`, both included, as updated.
+	pub fn mark_dirty(&mut self, start: c_int, end: c_int) {
+		let start = start.max(0);
+		let end = end.min(self.height - 1);
+		if start > end {
+			return;
+		}
+		self.dirty = match self.dirty {
+			Some((a, b)) => Some((a.min(start), b.max(end))),
+			None => Some((start, end)),
+		};
+	}
+
+	/// Reports updated rows and resets tracking.
+	///
+	/// Called automatically on drop.
+	pub fn flush(&mut self) {
+		if let Some((start, end)) = self.dirty.take() {
+			if let Some(f) = self.on_flush.as_mut() {
+				f(start, end)
+			}
+		}
+	}
+}
+
+
+// impl //
+
+impl FrameBuffer<'_> {
+	/// Returns `true` if pixel at `x, y` is white,
+	/// or `None` if it is out of bounds.
+	pub fn pixel(&self, x: c_int, y: c_int) -> Option<bool> {
+		self.bounds().contains(Point::new(x, y)).then(|| {
+			                                        let byte = self.buf[y as usize * self.stride + x as usize / 8];
+			                                        byte & (0x80 >> (x % 8)) != 0
+		                                        })
+	}
+
+	/// Sets pixel at `x, y` to the `color`.
+	///
+	/// Pixels out of bounds are ignored.
+	pub fn set_pixel(&mut self, x: c_int, y: c_int, color: Color) { self.span(y, x, x + 1, &Ink::new(&color, y)) }
+
+	/// Fills entire frame with the `color`.
+	pub fn clear(&mut self, color: Color) { self.fill_rect(self.bounds(), color) }
+
+	/// Fills horizontal span from `x` to `x + len - 1` at row `y`.
+	pub fn fill_span(&mut self, x: c_int, y: c_int, len: c_int, color: Color) {
+		self.span(y, x, x.saturating_add(len), &Ink::new(&color, y))
+	}
+
+	/// Fills the `rect` with the `color`.
+	///
+	/// Patterns are aligned to the frame origin.
+	pub fn fill_rect(&mut self, rect: Rect, color: Color) {
+		if let Some(rect) = rect.intersection(&self.bounds()) {
+			for y in rect.top()..rect.bottom() {
+				self.span(y, rect.left(), rect.right(), &Ink::new(&color, y));
+			}
+		}
+	}
+
+	/// Fills the `rect` with the dithering `pattern`.
+	///
+	/// Shorthand for [`fill_rect`](FrameBuffer::fill_rect) with [`Color::Pattern`].
+	pub fn fill_rect_pattern(&mut self, rect: Rect, pattern: &LCDPattern) {
+		self.fill_rect(rect, Color::Pattern(pattern))
+	}
+
+	/// Draws one pixel wide outline of the `rect`.
+	pub fn draw_rect(&mut self, rect: Rect, color: Color) {
+		if rect.is_empty() {
+			return;
+		}
+		let (left, right) = (rect.left(), rect.right());
+		let (top, bottom) = (rect.top(), rect.bottom() - 1);
+
+		self.span(top, left, right, &Ink::new(&color, top));
+		if bottom > top {
+			self.span(bottom, left, right, &Ink::new(&color, bottom));
+		}
+		for y in (top + 1)..bottom {
+			let ink = Ink::new(&color, y);
+			self.span(y, left, left + 1, &ink);
+			if right - 1 > left {
+				self.span(y, right - 1, right, &ink);
+			}
+		}
+	}
+
+	/// Draws one pixel wide line from `start` to `end`, both included,
+	/// using Bresenham's algorithm.
+	pub fn draw_line(&mut self, start: Point, end: Point, color: Color) {
+		if start.y == end.y {
+			let (left, right) = (start.x.min(end.x), start.x.max(end.x));
+			self.span(start.y, left, right + 1, &Ink::new(&color, start.y));
+			return;
+		}
+
+		let dx = (end.x - start.x).abs();
+		let dy = -(end.y - start.y).abs();
+		let sx = if start.x < end.x { 1 } else { -1 };
+		let sy = if start.y < end.y { 1 } else { -1 };
+		let mut err = dx + dy;
+		let Point { mut x, mut y } = start;
+
+		loop {
+			self.span(y, x, x + 1, &Ink::new(&color, y));
+			if x == end.x && y == end.y {
+				break;
+			}
+			let e2 = err * 2;
+			if e2 >= dy {
+				err += dy;
+				x += sx;
+			}
+			if e2 <= dx {
+				err += dx;
+				y += sy;
+			}
+		}
+	}
+
+	/// Copies the `bitmap` with its top-left corner at `at`.
+	///
+	/// Pixels where the bitmap's mask is not set are left untouched.
+	pub fn blit(&mut self, bitmap: &BitmapData, at: Point) {
+		// Size of the bitmap data is validated on creation.
+		self.blit_unchecked(
+		                    bitmap.data(),
+		                    bitmap.mask(),
+		                    bitmap.row_bytes() as _,
+		                    bitmap.width(),
+		                    bitmap.height(),
+		                    at,
+		)
+	}
+
+	/// Copies raw 1-bit image `width` by `height` with its top-left corner at `at`.
+	///
+	/// The `data` and optional `mask` are in the same format as [`BitmapData`],
+	/// with `row_bytes` row stride.
+	/// Pixels where the `mask` is not set are left untouched.
+	///
+	/// Returns [`Error::InvalidData`] if `row_bytes` is too small for the `width`
+	/// or the `data` is too small for the image, and [`Error::InvalidMask`] if the `mask` is too small.
+	pub fn blit_bits(&mut self,
+	                 data: &[u8],
+	                 mask: Option<&[u8]>,
+	                 row_bytes: usize,
+	                 width: c_int,
+	                 height: c_int,
+	                 at: Point)
+	                 -> Result<(), Error> {
+		if width <= 0 || height <= 0 {
+			return Ok(());
+		}
+		let width_bytes = (width as usize).div_ceil(8);
+		if row_bytes < width_bytes {
+			return Err(Error::InvalidData);
+		}
+		let len = row_bytes.checked_mul(height as usize - 1)
+		                   .and_then(|n| n.checked_add(width_bytes))
+		                   .ok_or(Error::InvalidData)?;
+		if data.len() < len {
+			return Err(Error::InvalidData);
+		}
+		if mask.is_some_and(|mask| mask.len() < len) {
+			return Err(Error::InvalidMask);
+		}
+
+		self.blit_unchecked(data, mask, row_bytes, width, height, at);
+		Ok(())
+	}
+
+	/// Copies raw 1-bit image, `data` and `mask` must contain all rows of the image.
+	fn blit_unchecked(&mut self,
+	                  data: &[u8],
+	                  mask: Option<&[u8]>,
+	                  row_bytes: usize,
+	                  width: c_int,
+	                  height: c_int,
+	                  at: Point) {
+		let Some(area) = Rect::from_origin_size(at, (width, height).into()).intersection(&self.bounds()) else {
+			return;
+		};
+
+		for y in area.top()..area.bottom() {
+			let src_row = (y - at.y) as usize * row_bytes;
+			let dst_row = y as usize * self.stride;
+
+			for x in area.left()..area.right() {
+				let sx = (x - at.x) as usize;
+				let bit = 0x80 >> (sx % 8);
+				if let Some(mask) = mask {
+					if mask[src_row + sx / 8] & bit == 0 {
+						continue;
+					}
+				}
+
+				let dst = &mut self.buf[dst_row + x as usize / 8];
+				let dst_bit = 0x80 >> (x % 8);
+				if data[src_row + sx / 8] & bit != 0 {
+					*dst |= dst_bit;
+				} else {
+					*dst &= !dst_bit;
+				}
+			}
+		}
+
+		self.mark_dirty(area.top(), area.bottom() - 1);
+	}
+
+	/// Draws the `bitmap` mapped by the `transform` from its own coordinates to the frame.
+	///
+	/// Uses nearest-neighbour sampling at pixel centers,
+	/// transparent pixels of the `bitmap` are left untouched.
+	#[cfg(feature = "transform")]
+	pub fn draw_transformed(&mut self, bitmap: &BitmapData, transform: &Affine2D) {
+		let Some(inverse) = transform.invert() else {
+			return;
+		};
+		let src = bitmap.bounds();
+		let Some(area) = transform.apply_rect(&src).intersection(&self.bounds()) else {
+			return;
+		};
+
+		for y in area.top()..area.bottom() {
+			let (mut u, mut v) = inverse.apply(area.left() as f32 + 0.5, y as f32 + 0.5);
+			for x in area.left()..area.right() {
+				let color = bitmap.pixel(libm::floorf(u) as _, libm::floorf(v) as _);
+				if color != LCDSolidColor::kColorClear {
+					self.set_pixel(x, y, Color::Solid(color));
+				}
+				u += inverse.a;
+				v += inverse.c;
+			}
+		}
+	}
+
+
+	/// Applies the `ink` to pixels from `x0` to `x1` (exclusive) at row `y`, clipped to bounds.
+	fn span(&mut self, y: c_int, x0: c_int, x1: c_int, ink: &Ink) {
+		let (x0, x1) = (x0.max(0), x1.min(self.width));
+		if y < 0 || y >= self.height || x0 >= x1 || matches!(ink, Ink::None) {
+			return;
+		}
+
+		let row = y as usize * self.stride;
+		let first = x0 as usize / 8;
+		let last = (x1 - 1) as usize / 8;
+		let head = 0xFF >> (x0 % 8);
+		let tail = 0xFF << (7 - (x1 - 1) % 8);
+
+		let bytes = &mut self.buf[row + first..=row + last];
+		if first == last {
+			ink.apply(&mut bytes[0], head & tail);
+		} else {
+			let (head_byte, rest) = bytes.split_first_mut().unwrap();
+			let (tail_byte, body) = rest.split_last_mut().unwrap();
+			ink.apply(head_byte, head);
+			ink.apply(tail_byte, tail);
+			match ink {
+				Ink::Bits { bits, mask: 0xFF } => body.fill(*bits),
+				_ => body.iter_mut().for_each(|b| ink.apply(b, 0xFF)),
+			}
+		}
+
+		self.mark_dirty(y, y);
+	}
+}
+
+
+/// Color resolved for the one row.
+enum Ink {
+	/// Replace bits where the mask is set.
+	Bits {
+		bits: u8,
+		mask: u8,
+	},
+	Xor,
+	None,
+}
+
+impl Ink {
+	fn new(color: &Color, y: c_int) -> Self {
+		match color {
+			Color::Solid(LCDSolidColor::kColorBlack) => {
+				Self::Bits { bits: 0x00,
+				             mask: 0xFF }
+			},
+			Color::Solid(LCDSolidColor::kColorWhite) => {
+				Self::Bits { bits: 0xFF,
+				             mask: 0xFF }
+			},
+			Color::Solid(LCDSolidColor::kColorXOR) => Self::Xor,
+			Color::Solid(LCDSolidColor::kColorClear) => Self::None,
+			Color::Pattern(pattern) => {
+				let row = y.rem_euclid(8) as usize;
+				Self::Bits { bits: pattern[row],
+				             mask: pattern[row + 8] }
+			},
+		}
+	}
+
+	#[inline(always)]
+	fn apply(&self, byte: &mut u8, span: u8) {
+		match *self {
+			Self::Bits { bits, mask } => {
+				let m = span & mask;
+				*byte = (*byte & !m) | (bits & m);
+			},
+			Self::Xor => *byte ^= span,
+			Self::None => {},
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+
+	#[test]
+	fn span_fill_and_dirty_rows() {
+		let mut buf = vec![0; LEN];
+		let mut frame = FrameBuffer::new(&mut buf).unwrap();
+		frame.fill_span(3, 10, 20, Color::WHITE);
+		assert_eq!(frame.dirty_rows(), Some((10, 10)));
+
+		let row = &frame.as_bytes()[10 * STRIDE..11 * STRIDE];
+		assert_eq!(&row[..4], &[0b0001_1111, 0xFF, 0b1111_1110, 0]);
+		assert_eq!(frame.pixel(2, 10), Some(false));
+		assert_eq!(frame.pixel(3, 10), Some(true));
+		assert_eq!(frame.pixel(22, 10), Some(true));
+		assert_eq!(frame.pixel(23, 10), Some(false));
+		assert_eq!(frame.pixel(WIDTH, 10), None);
+
+		frame.fill_rect(Rect::new(-5, 200, 10, 100), Color::WHITE);
+		assert_eq!(frame.dirty_rows(), Some((10, HEIGHT - 1)));
+	}
+
+	#[test]
+	fn flush_reports_dirty_rows() {
+		let mut buf = vec![0; LEN];
+		let mut marked = None;
+		{
+			let mut frame = FrameBuffer::new(&mut buf).unwrap()
+			                                          .with_on_flush(|a, b| marked = Some((a, b)));
+			frame.draw_line(Point::new(0, 5), Point::new(7, 12), Color::WHITE);
+			assert!(frame.pixel(0, 5).unwrap());
+			assert!(frame.pixel(3, 8).unwrap());
+			assert!(frame.pixel(7, 12).unwrap());
+		}
+		assert_eq!(marked, Some((5, 12)));
+	}
+
+	#[test]
+	fn pattern_and_blit() {
+		let pattern: LCDPattern =
+			[0xAA, 0x55, 0xAA, 0x55, 0xAA, 0x55, 0xAA, 0x55, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+		let mut buf = vec![0; 4 * 2];
+		let mut frame = FrameBuffer::with_size(&mut buf, 16, 2, 4).unwrap();
+		frame.fill_rect_pattern(Rect::new(0, 0, 16, 2), &pattern);
+		assert_eq!(frame.as_bytes(), &[0xAA, 0xAA, 0, 0, 0x55, 0x55, 0, 0]);
+
+		// 4x1 black image with two transparent pixels:
+		frame.blit_bits(&[0x00], Some(&[0xA0]), 1, 4, 1, Point::new(1, 1))
+		     .unwrap();
+		assert_eq!(frame.as_bytes()[4], 0b0000_0101);
+
+		// 9 pixels wide rows don't fit into one byte:
+		assert!(matches!(
+			frame.blit_bits(&[0; 2], None, 1, 9, 2, Point::new(0, 0)),
+			Err(Error::InvalidData)
+		));
+		assert!(matches!(
+			frame.blit_bits(&[0; 3], None, 2, 9, 2, Point::new(0, 0)),
+			Err(Error::InvalidData)
+		));
+		assert!(matches!(
+			frame.blit_bits(&[0; 4], Some(&[0; 3]), 2, 9, 2, Point::new(0, 0)),
+			Err(Error::InvalidMask)
+		));
+
+		assert!(FrameBuffer::with_size(&mut [0; 7], 16, 2, 4).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "transform")]
+	fn transformed() {
+		// 2x1 image, white pixel and black one:
+		let mut data = vec![0x80];
+		let bitmap = BitmapData::new(2, 1, 1, &mut data, None).unwrap();
+
+		let mut buf = vec![0, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF];
+		let mut frame = FrameBuffer::with_size(&mut buf, 16, 4, 2).unwrap();
+		frame.draw_transformed(
+		                       &bitmap,
+		                       &Affine2D::scale(2.0, 1.0).rotate(90.0).translate(1.0, 0.0),
+		);
+		assert_eq!(frame.dirty_rows(), Some((0, 3)));
+		assert_eq!(frame.as_bytes(), &[0x80, 0, 0x80, 0, 0x7F, 0xFF, 0x7F, 0xFF]);
+	}
+}
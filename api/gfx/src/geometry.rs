@@ -0,0 +1,481 @@
+//! Typed geometry primitives for drawing functions.
+
+use core::ffi::c_float;
+use core::ffi::c_int;
+use core::ops::Add;
+use core::ops::Deref;
+#[cfg(feature = "transform")]
+use core::ops::Mul;
+use core::ops::Sub;
+use alloc::vec::Vec;
+
+use sys::ffi::LCDRect;
+use sys::ffi::PDRect;
+
+
+/// Point in the integer coordinate space.
+///
+/// Layout is the same as a pair of `c_int`s, `x` then `y`,
+/// so slice of points can be passed to the C-part as coordinates.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+	pub x: c_int,
+	pub y: c_int,
+}
+
+impl Point {
+	pub const ZERO: Self = Self::new(0, 0);
+
+	pub const fn new(x: c_int, y: c_int) -> Self { Self { x, y } }
+}
+
+impl From<(c_int, c_int)> for Point {
+	fn from((x, y): (c_int, c_int)) -> Self { Self { x, y } }
+}
+
+impl From<Point> for (c_int, c_int) {
+	fn from(p: Point) -> Self { (p.x, p.y) }
+}
+
+impl Add for Point {
+	type Output = Self;
+	fn add(self, rhs: Self) -> Self { Self::new(self.x + rhs.x, self.y + rhs.y) }
+}
+
+impl Sub for Point {
+	type Output = Self;
+	fn sub(self, rhs: Self) -> Self { Self::new(self.x - rhs.x, self.y - rhs.y) }
+}
+
+
+/// Size of the area, `width` by `height`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Size {
+	pub width: c_int,
+	pub height: c_int,
+}
+
+impl Size {
+	pub const fn new(width: c_int, height: c_int) -> Self { Self { width, height } }
+
+	/// Returns `true` if the area is zero or negative.
+	pub const fn is_empty(&self) -> bool { self.width <= 0 || self.height <= 0 }
+}
+
+impl From<(c_int, c_int)> for Size {
+	fn from((width, height): (c_int, c_int)) -> Self { Self { width, height } }
+}
+
+impl From<Size> for (c_int, c_int) {
+	fn from(s: Size) -> Self { (s.width, s.height) }
+}
+
+
+/// Rectangle `width` by `height` at `x, y`.
+///
+/// Right and bottom edges are exclusive,
+/// so the rect contains points from `x` to `x + width - 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rect {
+	pub x: c_int,
+	pub y: c_int,
+	pub width: c_int,
+	pub height: c_int,
+}
+
+impl Rect {
+	pub const fn new(x: c_int, y: c_int, width: c_int, height: c_int) -> Self { Self { x, y, width, height } }
+
+	pub const fn from_origin_size(origin: Point, size: Size) -> Self {
+		Self::new(origin.x, origin.y, size.width, size.height)
+	}
+
+	/// Creates the rect from the `left, top` (inclusive) and `right, bottom` (exclusive) edges.
+	pub const fn from_edges(left: c_int, top: c_int, right: c_int, bottom: c_int) -> Self {
+		Self::new(left, top, right - left, bottom - top)
+	}
+
+	pub const fn origin(&self) -> Point { Point::new(self.x, self.y) }
+	pub const fn size(&self) -> Size { Size::new(self.width, self.height) }
+
+	pub const fn left(&self) -> c_int { self.x }
+	pub const fn top(&self) -> c_int { self.y }
+	/// Exclusive right edge.
+	pub const fn right(&self) -> c_int { self.x + self.width }
+	/// Exclusive bottom edge.
+	pub const fn bottom(&self) -> c_int { self.y + self.height }
+
+	pub const fn center(&self) -> Point { Point::new(self.x + self.width / 2, self.y + self.height / 2) }
+
+	/// Returns `true` if the area is zero or negative.
+	pub const fn is_empty(&self) -> bool { self.size().is_empty() }
+
+	/// Returns `true` if the `point` is inside of the rect.
+	pub const fn contains(&self, point: Point) -> bool {
+		point.x >= self.left() && point.x < self.right() && point.y >= self.top() && point.y < self.bottom()
+	}
+
+	/// Returns `true` if the `other` rect is entirely inside of this one.
+	pub const fn contains_rect(&self, other: &Rect) -> bool {
+		!other.is_empty() &&
+		other.left() >= self.left() &&
+		other.right() <= self.right() &&
+		other.top() >= self.top() &&
+		other.bottom() <= self.bottom()
+	}
+
+	/// Returns `true` if the rects have common area.
+	pub fn intersects(&self, other: &Rect) -> bool { self.intersection(other).is_some() }
+
+	/// Returns common area of the rects, or `None` if they don't intersect.
+	pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+		let rect = Rect::from_edges(
+		                            self.left().max(other.left()),
+		                            self.top().max(other.top()),
+		                            self.right().min(other.right()),
+		                            self.bottom().min(other.bottom()),
+		);
+		(!rect.is_empty()).then_some(rect)
+	}
+
+	/// Returns the smallest rect containing both rects.
+	///
+	/// Empty rects are ignored.
+	pub fn union(&self, other: &Rect) -> Rect {
+		match (self.is_empty(), other.is_empty()) {
+			(true, _) => *other,
+			(_, true) => *self,
+			_ => {
+				Rect::from_edges(
+				                 self.left().min(other.left()),
+				                 self.top().min(other.top()),
+				                 self.right().max(other.right()),
+				                 self.bottom().max(other.bottom()),
+				)
+			},
+		}
+	}
+
+	/// Returns the rect moved by `offset`.
+	pub const fn translate(&self, offset: Point) -> Rect {
+		Self::new(self.x + offset.x, self.y + offset.y, self.width, self.height)
+	}
+
+	/// Returns the rect shrunk by `dx` from left and right, and by `dy` from top and bottom.
+	///
+	/// Negative values grow the rect.
+	pub const fn inset(&self, dx: c_int, dy: c_int) -> Rect {
+		Self::new(
+		          self.x + dx,
+		          self.y + dy,
+		          self.width - dx * 2,
+		          self.height - dy * 2,
+		)
+	}
+}
+
+impl From<LCDRect> for Rect {
+	fn from(r: LCDRect) -> Self { Self::from_edges(r.left, r.top, r.right, r.bottom) }
+}
+
+impl From<Rect> for LCDRect {
+	fn from(r: Rect) -> Self {
+		LCDRect { left: r.left(),
+		          right: r.right(),
+		          top: r.top(),
+		          bottom: r.bottom() }
+	}
+}
+
+/// Converts to the smallest integer rect containing the `PDRect`.
+impl From<PDRect> for Rect {
+	fn from(r: PDRect) -> Self {
+		// Not using `floor` and `ceil` because they're not in the `core`.
+		fn floor(v: c_float) -> c_int {
+			let i = v as c_int;
+			if (i as c_float) > v { i - 1 } else { i }
+		}
+		fn ceil(v: c_float) -> c_int {
+			let i = v as c_int;
+			if (i as c_float) < v { i + 1 } else { i }
+		}
+		Self::from_edges(floor(r.x), floor(r.y), ceil(r.x + r.width), ceil(r.y + r.height))
+	}
+}
+
+impl From<Rect> for PDRect {
+	fn from(r: Rect) -> Self {
+		PDRect { x: r.x as _,
+		         y: r.y as _,
+		         width: r.width as _,
+		         height: r.height as _ }
+	}
+}
+
+
+/// Polygon with vertices at the points.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Polygon(Vec<Point>);
+
+impl Polygon {
+	pub const fn new() -> Self { Self(Vec::new()) }
+
+	pub fn with_points<I: IntoIterator<Item = P>, P: Into<Point>>(points: I) -> Self {
+		Self(points.into_iter().map(Into::into).collect())
+	}
+
+	/// Adds vertex at the `point`.
+	pub fn push(&mut self, point: Point) { self.0.push(point) }
+
+	pub fn points(&self) -> &[Point] { &self.0 }
+
+	pub fn points_mut(&mut self) -> &mut [Point] { &mut self.0 }
+
+	/// Returns the smallest rect containing all points, or `None` if the polygon is empty.
+	pub fn bounds(&self) -> Option<Rect> {
+		let first = *self.0.first()?;
+		let (min, max) = self.0.iter().fold((first, first), |(min, max), p| {
+			                              (Point::new(min.x.min(p.x), min.y.min(p.y)),
+			                               Point::new(max.x.max(p.x), max.y.max(p.y)))
+		                              });
+		Some(Rect::from_edges(min.x, min.y, max.x + 1, max.y + 1))
+	}
+
+	/// Returns the polygon moved by `offset`.
+	pub fn translate(&self, offset: Point) -> Polygon { Self(self.0.iter().map(|p| *p + offset).collect()) }
+}
+
+impl Deref for Polygon {
+	type Target = [Point];
+	fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<P: Into<Point>> FromIterator<P> for Polygon {
+	fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self { Self::with_points(iter) }
+}
+
+impl From<Vec<Point>> for Polygon {
+	fn from(points: Vec<Point>) -> Self { Self(points) }
+}
+
+
+/// Affine transform of the plane.
+///
+/// Maps the point `x, y` to `a * x + b * y + tx, c * x + d * y + ty`.
+///
+/// The Y axis points down, so positive rotation is clockwise on the screen,
+/// same as in [`Bitmap::draw_rotated`](crate::bitmap::Bitmap::draw_rotated).
+#[cfg(feature = "transform")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine2D {
+	pub a: c_float,
+	pub b: c_float,
+	pub c: c_float,
+	pub d: c_float,
+	pub tx: c_float,
+	pub ty: c_float,
+}
+
+#[cfg(feature = "transform")]
+impl Default for Affine2D {
+	fn default() -> Self { Self::IDENTITY }
+}
+
+#[cfg(feature = "transform")]
+impl Affine2D {
+	pub const IDENTITY: Self = Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+	pub const fn new(a: c_float, b: c_float, c: c_float, d: c_float, tx: c_float, ty: c_float) -> Self {
+		Self { a, b, c, d, tx, ty }
+	}
+
+	pub const fn translation(tx: c_float, ty: c_float) -> Self { Self::new(1.0, 0.0, 0.0, 1.0, tx, ty) }
+
+	pub const fn scale(sx: c_float, sy: c_float) -> Self { Self::new(sx, 0.0, 0.0, sy, 0.0, 0.0) }
+
+	/// Rotation by `degrees` clockwise around the origin.
+	pub fn rotation(degrees: c_float) -> Self {
+		let (sin, cos) = libm::sincosf(degrees.to_radians());
+		Self::new(cos, -sin, sin, cos, 0.0, 0.0)
+	}
+
+	/// Rotation by `degrees` clockwise around the point `x, y`.
+	pub fn rotation_about(degrees: c_float, x: c_float, y: c_float) -> Self {
+		Self::translation(-x, -y).rotate(degrees).translate(x, y)
+	}
+
+
+	/// Returns transform applying `self` first and then `other`.
+	pub fn then(&self, other: &Affine2D) -> Self {
+		Self::new(
+		          other.a * self.a + other.b * self.c,
+		          other.a * self.b + other.b * self.d,
+		          other.c * self.a + other.d * self.c,
+		          other.c * self.b + other.d * self.d,
+		          other.a * self.tx + other.b * self.ty + other.tx,
+		          other.c * self.tx + other.d * self.ty + other.ty,
+		)
+	}
+
+	/// Returns the transform followed by translation.
+	pub fn translate(&self, tx: c_float, ty: c_float) -> Self { self.then(&Self::translation(tx, ty)) }
+
+	/// Returns the transform followed by scale.
+	pub fn scale_by(&self, sx: c_float, sy: c_float) -> Self { self.then(&Self::scale(sx, sy)) }
+
+	/// Returns the transform followed by rotation around the origin.
+	pub fn rotate(&self, degrees: c_float) -> Self { self.then(&Self::rotation(degrees)) }
+
+
+	pub fn determinant(&self) -> c_float { self.a * self.d - self.b * self.c }
+
+	/// Returns inverse transform, or `None` if the transform collapses the plane.
+	pub fn invert(&self) -> Option<Self> {
+		let det = self.determinant();
+		if det == 0.0 || !det.is_finite() {
+			return None;
+		}
+		let (a, b, c, d) = (self.d / det, -self.b / det, -self.c / det, self.a / det);
+		Some(Self::new(
+			a,
+			b,
+			c,
+			d,
+			-(a * self.tx + b * self.ty),
+			-(c * self.tx + d * self.ty),
+		))
+	}
+
+
+	/// Transforms the point `x, y`.
+	pub fn apply(&self, x: c_float, y: c_float) -> (c_float, c_float) {
+		(self.a * x + self.b * y + self.tx, self.c * x + self.d * y + self.ty)
+	}
+
+	/// Transforms the `point`, rounding result to the nearest integer point.
+	pub fn apply_point(&self, point: Point) -> Point {
+		let (x, y) = self.apply(point.x as _, point.y as _);
+		Point::new(libm::roundf(x) as _, libm::roundf(y) as _)
+	}
+
+	/// Returns the smallest rect containing the transformed `rect`.
+	pub fn apply_rect(&self, rect: &Rect) -> Rect {
+		let (l, t, r, b) =
+			(rect.left() as c_float, rect.top() as c_float, rect.right() as c_float, rect.bottom() as c_float);
+		let corners = [
+		               self.apply(l, t),
+		               self.apply(r, t),
+		               self.apply(l, b),
+		               self.apply(r, b),
+		];
+		let (x0, y0, x1, y1) =
+			corners.iter().fold(
+			                    (c_float::MAX, c_float::MAX, c_float::MIN, c_float::MIN),
+			                    |(x0, y0, x1, y1), (x, y)| (x0.min(*x), y0.min(*y), x1.max(*x), y1.max(*y)),
+			);
+		// Rounding errors of rotation must not grow the rect:
+		const EPSILON: c_float = 1e-3;
+		Rect::from_edges(
+		                 libm::floorf(x0 + EPSILON) as _,
+		                 libm::floorf(y0 + EPSILON) as _,
+		                 libm::ceilf(x1 - EPSILON) as _,
+		                 libm::ceilf(y1 - EPSILON) as _,
+		)
+	}
+
+
+	/// Returns `true` if the transform only moves points.
+	pub fn is_translation(&self) -> bool { self.a == 1.0 && self.b == 0.0 && self.c == 0.0 && self.d == 1.0 }
+
+	/// Splits the transform into scale followed by rotation,
+	/// as they are applied by [`Bitmap::draw_rotated`](crate::bitmap::Bitmap::draw_rotated).
+	///
+	/// Returns `(degrees, x_scale, y_scale)` with positive scales,
+	/// or `None` if the transform skews or mirrors the plane.
+	/// Translation is ignored.
+	pub fn to_rotate_scale(&self) -> Option<(c_float, c_float, c_float)> {
+		const EPSILON: c_float = 1e-4;
+
+		let x_scale = libm::hypotf(self.a, self.c);
+		let y_scale = libm::hypotf(self.b, self.d);
+		if x_scale == 0.0 || y_scale == 0.0 || self.determinant() <= 0.0 {
+			return None;
+		}
+		// Columns must be perpendicular:
+		if (self.a * self.b + self.c * self.d).abs() > EPSILON * x_scale * y_scale {
+			return None;
+		}
+
+		let degrees = libm::atan2f(self.c, self.a).to_degrees();
+		let degrees = if degrees.abs() < EPSILON { 0.0 } else { degrees };
+		Some((degrees, x_scale, y_scale))
+	}
+}
+
+/// Composes the transforms as matrices, so `lhs * rhs` applies `rhs` first.
+#[cfg(feature = "transform")]
+impl Mul for Affine2D {
+	type Output = Self;
+	fn mul(self, rhs: Self) -> Self { rhs.then(&self) }
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	/// Ensure that slice of points can be passed as coordinates.
+	fn point_layout() {
+		assert_eq!(core::mem::size_of::<Point>(), core::mem::size_of::<[c_int; 2]>());
+		assert_eq!(core::mem::align_of::<Point>(), core::mem::align_of::<c_int>());
+	}
+
+	#[test]
+	fn rect_intersection_union() {
+		let a = Rect::new(0, 0, 10, 10);
+		let b = Rect::new(5, 5, 10, 10);
+		assert_eq!(a.intersection(&b), Some(Rect::new(5, 5, 5, 5)));
+		assert_eq!(a.union(&b), Rect::new(0, 0, 15, 15));
+		assert_eq!(a.intersection(&Rect::new(10, 0, 5, 5)), None);
+		assert!(a.contains(Point::new(9, 9)));
+		assert!(!a.contains(Point::new(10, 9)));
+	}
+
+	#[test]
+	fn rect_from_pd_rect() {
+		let r = PDRect { x: -0.5,
+		                 y: 1.0,
+		                 width: 2.0,
+		                 height: 1.5 };
+		assert_eq!(Rect::from(r), Rect::new(-1, 1, 3, 2));
+	}
+
+	#[test]
+	#[cfg(feature = "transform")]
+	fn affine_compose_invert() {
+		let t = Affine2D::scale(2.0, 3.0).rotate(90.0).translate(10.0, 20.0);
+		let (x, y) = t.apply(1.0, 1.0);
+		assert!((x - 7.0).abs() < 1e-4 && (y - 22.0).abs() < 1e-4);
+		assert_eq!(t.apply_point(Point::new(1, 1)), Point::new(7, 22));
+
+		let inv = t.invert().unwrap();
+		let (x, y) = inv.apply(7.0, 22.0);
+		assert!((x - 1.0).abs() < 1e-4 && (y - 1.0).abs() < 1e-4);
+		assert_eq!(t * inv, inv.then(&t));
+		assert!(Affine2D::scale(0.0, 1.0).invert().is_none());
+
+		let (degrees, sx, sy) = t.to_rotate_scale().unwrap();
+		assert!((degrees - 90.0).abs() < 1e-3 && (sx - 2.0).abs() < 1e-4 && (sy - 3.0).abs() < 1e-4);
+		assert_eq!(Affine2D::scale(-1.0, 1.0).to_rotate_scale(), None);
+		assert_eq!(
+		           Affine2D::new(1.0, 0.5, 0.0, 1.0, 0.0, 0.0).to_rotate_scale(),
+		           None
+		);
+
+		let rect = Affine2D::rotation_about(90.0, 2.0, 1.0).apply_rect(&Rect::new(0, 0, 4, 2));
+		assert_eq!(rect, Rect::new(1, -1, 2, 4));
+	}
+}
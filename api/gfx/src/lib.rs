@@ -6,19 +6,36 @@ extern crate alloc;
 pub extern crate color;
 
 pub mod error;
+pub mod geometry;
+pub mod frame;
+pub mod context;
 pub mod text;
 pub mod bitmap {
 	mod bitmap;
+	mod data;
+	pub mod ops;
 	pub mod api;
 	pub mod table;
+	pub mod animation;
+	pub mod sheet;
 	pub use bitmap::*;
 }
 pub mod video;
+pub mod tilemap;
 pub mod api;
 
+#[cfg(feature = "embedded-graphics")]
+pub mod embedded;
+#[cfg(feature = "codec")]
+pub mod codec;
+#[cfg(feature = "embedded-graphics")]
+pub extern crate embedded_graphics_core;
+
 use core::ffi::c_float;
 use core::ffi::c_int;
 use error::ApiError;
+use geometry::Point;
+use geometry::Rect;
 
 pub use sys::ffi::LCDBitmapFlip as BitmapFlip;
 pub use sys::ffi::LCDBitmapDrawMode as BitmapDrawMode;
@@ -65,6 +82,16 @@ unsafe fn as_slice_mut(buf: *mut u8) -> Result<&'static mut [u8], ApiError> {
 pub fn get_frame() -> Result<&'static mut [u8], ApiError> { Graphics::Default().get_frame() }
 
 
+/// Returns [`FrameBuffer`](frame::FrameBuffer) view of the current display frame buffer.
+///
+/// Rows updated by drawing into the view are marked updated on its flush or drop.
+///
+/// This function is shorthand for [`Graphics::frame_buffer`],
+/// using default ZST end-point.
+#[inline(always)]
+pub fn frame_buffer() -> Result<frame::FrameBuffer<'static>, ApiError> { Graphics::Default().frame_buffer() }
+
+
 /// Returns the raw bits in the display buffer,
 /// __the last completed frame__.
 ///
@@ -383,6 +410,21 @@ impl<Api: api::Api> Graphics<Api> {
 	}
 
 
+	/// Returns [`FrameBuffer`](frame::FrameBuffer) view of the current display frame buffer.
+	///
+	/// Rows updated by drawing into the view are marked updated
+	/// with [`mark_updated_rows`](Graphics::mark_updated_rows) on its flush or drop.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getFrame`].
+	#[doc(alias = "sys::ffi::playdate_graphics::getFrame")]
+	pub fn frame_buffer(&self) -> Result<frame::FrameBuffer<'static>, ApiError>
+		where Api: Clone + 'static {
+		let gfx = self.clone();
+		let frame = frame::FrameBuffer::new(self.get_frame()?).map_err(ApiError::Api)?;
+		Ok(frame.with_on_flush(move |start, end| gfx.mark_updated_rows(start, end)))
+	}
+
+
 	/// Returns the raw bits in the display buffer,
 	/// __the last completed frame__.
 	///
@@ -613,6 +655,122 @@ impl<Api: api::Api> Graphics<Api> {
 }
 
 
+/// Typed geometry variants of the drawing functions.
+impl<Api: api::Api> Graphics<Api> {
+	/// Sets the current clip rect in __screen__ coordinates.
+	///
+	/// See also [`Graphics::set_screen_clip_rect`].
+	#[doc(alias = "sys::ffi::playdate_graphics::setScreenClipRect")]
+	#[inline(always)]
+	pub fn set_screen_clip(&self, rect: Rect) {
+		self.set_screen_clip_rect(rect.x, rect.y, rect.width, rect.height)
+	}
+
+	/// Sets the current clip rect, using __world__ coordinates.
+	///
+	/// See also [`Graphics::set_clip_rect`].
+	#[doc(alias = "sys::ffi::playdate_graphics::setClipRect")]
+	#[inline(always)]
+	pub fn set_clip(&self, rect: Rect) { self.set_clip_rect(rect.x, rect.y, rect.width, rect.height) }
+
+	/// Offsets the origin point for all drawing calls to `offset` (can be negative).
+	///
+	/// See also [`Graphics::set_draw_offset`].
+	#[doc(alias = "sys::ffi::playdate_graphics::setDrawOffset")]
+	#[inline(always)]
+	pub fn set_draw_offset_point(&self, offset: Point) { self.set_draw_offset(offset.x, offset.y) }
+
+	/// Fills the polygon with vertices at the given `points`
+	/// using the given `color` and fill, or winding, `rule`.
+	///
+	/// Accepts [`Polygon`](geometry::Polygon) as well.
+	///
+	/// See also [`Graphics::fill_polygon`].
+	#[doc(alias = "sys::ffi::playdate_graphics::fillPolygon")]
+	pub fn fill_polygon_points(&self, points: &[Point], color: LCDColor, rule: LCDPolygonFillRule) {
+		let f = self.0.fill_polygon();
+		// Point is `repr(C)` pair of `c_int`s, and the C-part doesn't modify coords.
+		unsafe { f(points.len() as _, points.as_ptr() as *mut c_int, color, rule) }
+	}
+
+	/// Draws a line from `start` to `end` with a stroke width of `width`.
+	///
+	/// See also [`Graphics::draw_line`].
+	#[doc(alias = "sys::ffi::playdate_graphics::drawLine")]
+	#[inline(always)]
+	pub fn draw_line_points(&self, start: Point, end: Point, width: c_int, color: LCDColor) {
+		self.draw_line(start.x, start.y, end.x, end.y, width, color)
+	}
+
+	/// Draws a filled triangle with points at `a`, `b` and `c`.
+	///
+	/// See also [`Graphics::fill_triangle`].
+	#[doc(alias = "sys::ffi::playdate_graphics::fillTriangle")]
+	#[inline(always)]
+	pub fn fill_triangle_points(&self, a: Point, b: Point, c: Point, color: LCDColor) {
+		self.fill_triangle(a.x, a.y, b.x, b.y, c.x, c.y, color)
+	}
+
+	/// Draws the `rect`.
+	///
+	/// See also [`Graphics::draw_rect`].
+	#[doc(alias = "sys::ffi::playdate_graphics::drawRect")]
+	#[inline(always)]
+	pub fn draw_rect_in(&self, rect: Rect, color: LCDColor) {
+		self.draw_rect(rect.x, rect.y, rect.width, rect.height, color)
+	}
+
+	/// Draws the filled `rect`.
+	///
+	/// See also [`Graphics::fill_rect`].
+	#[doc(alias = "sys::ffi::playdate_graphics::fillRect")]
+	#[inline(always)]
+	pub fn fill_rect_in(&self, rect: Rect, color: LCDColor) {
+		self.fill_rect(rect.x, rect.y, rect.width, rect.height, color)
+	}
+
+	/// Draws an ellipse inside the `rect` of width `line_width` (inset from the rectangle bounds).
+	///
+	/// See also [`Graphics::draw_ellipse`].
+	#[doc(alias = "sys::ffi::playdate_graphics::drawEllipse")]
+	#[inline(always)]
+	pub fn draw_ellipse_in(&self,
+	                       rect: Rect,
+	                       line_width: c_int,
+	                       start_angle: c_float,
+	                       end_angle: c_float,
+	                       color: LCDColor) {
+		self.draw_ellipse(
+		                  rect.x,
+		                  rect.y,
+		                  rect.width,
+		                  rect.height,
+		                  line_width,
+		                  start_angle,
+		                  end_angle,
+		                  color,
+		)
+	}
+
+	/// Fills an ellipse inside the `rect`.
+	///
+	/// See also [`Graphics::fill_ellipse`].
+	#[doc(alias = "sys::ffi::playdate_graphics::fillEllipse")]
+	#[inline(always)]
+	pub fn fill_ellipse_in(&self, rect: Rect, start_angle: c_float, end_angle: c_float, color: LCDColor) {
+		self.fill_ellipse(
+		                  rect.x,
+		                  rect.y,
+		                  rect.width,
+		                  rect.height,
+		                  start_angle,
+		                  end_angle,
+		                  color,
+		)
+	}
+}
+
+
 pub trait BitmapFlipExt {
 	#![allow(non_upper_case_globals)]
 	const Unflipped: BitmapFlip = BitmapFlip::kBitmapUnflipped;
@@ -1,6 +1,8 @@
 //! Playdate text API
 
 use core::ffi::{c_int, c_char};
+use core::iter::Peekable;
+use core::str::Chars;
 
 use alloc::ffi::NulError;
 use alloc::boxed::Box;
@@ -16,6 +18,9 @@ use crate::Graphics;
 use crate::bitmap::BitmapRef;
 use crate::error::{Error, ApiError};
 
+pub mod layout;
+pub mod fnt;
+
 
 /// Draws the given `text` using the provided coords `x`, `y`.
 ///
@@ -568,6 +573,129 @@ impl AsRaw for FontPage {
 }
 
 
+impl Font {
+	/// Returns the height of the font's line.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getFontHeight`].
+	#[doc(alias = "sys::ffi::playdate_graphics::getFontHeight")]
+	pub fn height(&self) -> u8 { Graphics::Default().get_font_height(self) }
+
+	/// Returns the width of the given `text` in this font.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getTextWidth`].
+	#[doc(alias = "sys::ffi::playdate_graphics::getTextWidth")]
+	pub fn text_width<S: AsRef<str>>(&self, text: S, tracking: c_int) -> Result<c_int, NulError> {
+		Graphics::Default().get_text_width(text, Some(self), tracking)
+	}
+
+	/// Returns the [`FontPage`] containing the character `c`.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getFontPage`].
+	#[doc(alias = "sys::ffi::playdate_graphics::getFontPage")]
+	pub fn page(&self, c: char) -> Result<FontPage, Error> { Graphics::Default().get_font_page(self, c as u32) }
+
+	/// Returns the [`Glyph`] of the character `c` and its advance.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getFontPage`] and [`sys::ffi::playdate_graphics::getPageGlyph`].
+	pub fn glyph(&self, c: char) -> Result<(Glyph, c_int), Error> { self.page(c)?.glyph(c) }
+
+	/// Returns the bitmap of the character `c` and its advance.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getFontPage`] and [`sys::ffi::playdate_graphics::getPageGlyph`].
+	pub fn glyph_bitmap(&self, c: char) -> Result<(BitmapRef<'_>, c_int), Error> {
+		let page = self.page(c)?;
+		let (_, bitmap, advance) = page.glyph_bitmap(c)?;
+		// Bitmap is owned by the font, not by the page:
+		Ok((BitmapRef::from(unsafe { bitmap.as_raw() }), advance))
+	}
+
+	/// Returns the kerning adjustment between characters `c` and `next`,
+	/// or `0` if the font has no glyph for `c`.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getGlyphKerning`].
+	#[doc(alias = "sys::ffi::playdate_graphics::getGlyphKerning")]
+	pub fn kerning(&self, c: char, next: char) -> c_int {
+		self.glyph(c).map_or(0, |(glyph, _)| glyph.kerning(c, next))
+	}
+
+	/// Returns iterator over glyphs of the `text`
+	/// yielding `(char, glyph, advance, kerning)` for each character,
+	/// where `kerning` is the adjustment between the character and the next one.
+	///
+	/// Characters without glyph in the font are skipped.
+	pub fn glyphs<'t>(&'t self, text: &'t str) -> Glyphs<'t> {
+		Glyphs { font: self,
+		         chars: text.chars().peekable(),
+		         page: None }
+	}
+}
+
+impl FontPage {
+	/// Returns the [`Glyph`] of the character `c` and its advance.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getPageGlyph`].
+	#[doc(alias = "sys::ffi::playdate_graphics::getPageGlyph")]
+	pub fn glyph(&self, c: char) -> Result<(Glyph, c_int), Error> {
+		let mut advance = 0;
+		let f = api::Api::get_page_glyph(&crate::api::Default);
+		let ptr = unsafe { f(self.0, c as u32, core::ptr::null_mut(), &mut advance) };
+		if ptr.is_null() {
+			Err(Error::Font)
+		} else {
+			Ok((Glyph(ptr), advance))
+		}
+	}
+
+	/// Returns the [`Glyph`] of the character `c` with its bitmap and advance.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getPageGlyph`].
+	#[doc(alias = "sys::ffi::playdate_graphics::getPageGlyph")]
+	pub fn glyph_bitmap(&self, c: char) -> Result<(Glyph, BitmapRef<'_>, c_int), Error> {
+		let mut advance = 0;
+		let (glyph, bitmap) = Graphics::Default().get_page_glyph_with_bitmap(self, c as u32, &mut advance)?;
+		Ok((glyph, bitmap, advance))
+	}
+}
+
+impl Glyph {
+	/// Returns the kerning adjustment between characters `c` of this glyph and `next`.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::getGlyphKerning`].
+	#[doc(alias = "sys::ffi::playdate_graphics::getGlyphKerning")]
+	pub fn kerning(&self, c: char, next: char) -> c_int {
+		Graphics::Default().get_glyph_kerning(self, c as u32, next as u32)
+	}
+}
+
+
+/// Iterator over glyphs of the text, see [`Font::glyphs`].
+pub struct Glyphs<'t> {
+	font: &'t Font,
+	chars: Peekable<Chars<'t>>,
+	/// Last used page with its index.
+	page: Option<(u32, FontPage)>,
+}
+
+impl Iterator for Glyphs<'_> {
+	type Item = (char, Glyph, c_int, c_int);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let c = self.chars.next()?;
+			let index = c as u32 & !0xFF;
+			if self.page.as_ref().is_none_or(|(i, _)| *i != index) {
+				self.page = self.font.page(c).ok().map(|page| (index, page));
+			}
+			let Some(Ok((glyph, advance))) = self.page.as_ref().map(|(_, page)| page.glyph(c)) else {
+				continue;
+			};
+			let kerning = self.chars.peek().map_or(0, |next| glyph.kerning(c, *next));
+			return Some((c, glyph, advance, kerning));
+		}
+	}
+}
+
+
 pub trait StringEncodingExt {
 	#![allow(non_upper_case_globals)]
 	const ASCII: StringEncoding = StringEncoding::kASCIIEncoding;
@@ -0,0 +1,196 @@
+//! Parser of the `.fnt` font source files.
+//!
+//! Parsed [`FntFont`] provides the same [`Metrics`] as the font loaded by the system,
+//! so the [`TextLayout`](super::layout::TextLayout) can be computed without the device,
+//! e.g. in unit tests.
+//!
+//! Source file contains properties as `key=value`, advances of glyphs as `char<TAB>advance`,
+//! where `space` stands for the space character, and kerning pairs as `chars<TAB>adjustment`.
+//! Lines starting with `--` are comments.
+
+use core::ffi::c_int;
+use core::fmt;
+use alloc::collections::BTreeMap;
+
+use super::layout::Metrics;
+
+
+/// Error of the `.fnt` source parsing with the line number, starting from `1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+	pub line: usize,
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Invalid font source at line {}", self.line)
+	}
+}
+
+impl core::error::Error for ParseError {}
+
+
+/// Font metrics parsed from the `.fnt` source.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FntFont {
+	height: c_int,
+	tracking: c_int,
+	advances: BTreeMap<char, c_int>,
+	kerning: BTreeMap<(char, char), c_int>,
+}
+
+
+// ctor //
+
+impl FntFont {
+	/// Parses the `.fnt` source.
+	///
+	/// The line height is taken from the `height` property,
+	/// fonts with glyphs in separate image table have no such property,
+	/// so set the height of table cells with [`with_height`](FntFont::with_height).
+	pub fn parse(source: &str) -> Result<Self, ParseError> {
+		let mut font = Self::default();
+		for (i, line) in source.lines().enumerate() {
+			let line = line.trim_end_matches(['\r', '\n']);
+			let err = ParseError { line: i + 1 };
+			if line.trim().is_empty() {
+				continue;
+			}
+
+			// Comment, unless it's kerning of two dashes:
+			if let Some(rest) = line.strip_prefix("--") {
+				if !rest.starts_with(char::is_whitespace) || rest.trim().parse::<c_int>().is_err() {
+					continue;
+				}
+			}
+
+			if let Some((key, value)) = line.split_once('=') {
+				if key.len() > 1 && key.chars().all(|c| c.is_ascii_alphabetic()) {
+					font.property(key, value.trim()).map_err(|_| err)?;
+					continue;
+				}
+			}
+
+			// First character of the token can be any but whitespace:
+			let first = line.chars().next().map_or(0, char::len_utf8);
+			let split = line[first..].find(char::is_whitespace).ok_or(err)? + first;
+			let (token, value) = line.split_at(split);
+			let value = value.trim().parse::<c_int>().map_err(|_| err)?;
+
+			let mut chars = token.chars();
+			match (token, chars.next(), chars.next(), chars.next()) {
+				("space", ..) => {
+					font.advances.insert(' ', value);
+				},
+				(_, Some(c), None, _) => {
+					font.advances.insert(c, value);
+				},
+				(_, Some(c), Some(next), None) => {
+					font.kerning.insert((c, next), value);
+				},
+				_ => {
+					let c = token.strip_prefix("U+")
+					             .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+					             .and_then(char::from_u32)
+					             .ok_or(err)?;
+					font.advances.insert(c, value);
+				},
+			}
+		}
+		Ok(font)
+	}
+
+	/// Sets the line height.
+	pub fn with_height(mut self, height: c_int) -> Self {
+		self.height = height;
+		self
+	}
+
+	fn property(&mut self, key: &str, value: &str) -> Result<(), core::num::ParseIntError> {
+		match key {
+			"height" => self.height = value.parse()?,
+			"tracking" => self.tracking = value.parse()?,
+			// Embedded image and other properties are not needed for metrics:
+			_ => {},
+		}
+		Ok(())
+	}
+}
+
+
+// utils //
+
+impl FntFont {
+	/// Returns the line height.
+	pub const fn height(&self) -> c_int { self.height }
+
+	/// Returns the tracking of the font,
+	/// the system adds it to the [text tracking](crate::Graphics::set_text_tracking),
+	/// so pass it to [`TextLayout::tracking`](super::layout::TextLayout::tracking).
+	pub const fn tracking(&self) -> c_int { self.tracking }
+
+	/// Returns the advance of the character `c`.
+	pub fn advance(&self, c: char) -> Option<c_int> { self.advances.get(&c).copied() }
+
+	/// Returns the kerning adjustment between characters `c` and `next`.
+	pub fn kerning(&self, c: char, next: char) -> c_int { self.kerning.get(&(c, next)).copied().unwrap_or(0) }
+
+	/// Returns iterator over characters with glyphs and their advances.
+	pub fn glyphs(&self) -> impl Iterator<Item = (char, c_int)> + '_ {
+		self.advances.iter().map(|(c, a)| (*c, *a))
+	}
+}
+
+
+impl Metrics for FntFont {
+	fn height(&self) -> c_int { self.height }
+	fn advance(&self, c: char) -> Option<c_int> { FntFont::advance(self, c) }
+	fn kerning(&self, c: char, next: char) -> c_int { FntFont::kerning(self, c, next) }
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::text::layout::TextLayout;
+
+
+	const SOURCE: &str = "--metrics={\"baseline\":10}\n\
+	                      tracking=1\n\
+	                      height=14\n\
+	                      space\t4\n\
+	                      A\t8\n\
+	                      V\t8\n\
+	                      -\t5\n\
+	                      U+00E9\t6\n\
+	                      AV\t-2\n\
+	                      --\t-1\n";
+
+
+	#[test]
+	fn parse() {
+		let font = FntFont::parse(SOURCE).unwrap();
+		assert_eq!((font.height(), font.tracking()), (14, 1));
+		assert_eq!(font.advance(' '), Some(4));
+		assert_eq!(font.advance('é'), Some(6));
+		assert_eq!(font.advance('-'), Some(5));
+		assert_eq!(font.kerning('A', 'V'), -2);
+		assert_eq!(font.kerning('-', '-'), -1);
+		assert_eq!(font.glyphs().count(), 5);
+
+		assert_eq!(FntFont::parse("A\t8\nB").unwrap_err(), ParseError { line: 2 });
+		assert_eq!(FntFont::parse("ABC\t1").unwrap_err(), ParseError { line: 1 });
+	}
+
+	#[test]
+	fn layout() {
+		let font = FntFont::parse(SOURCE).unwrap();
+		let tracking = font.tracking();
+		let layout = TextLayout::new(&font).tracking(tracking)
+		                                   .width(30)
+		                                   .layout("AV AV");
+		assert_eq!(layout.line_count(), 2);
+		assert_eq!(layout.lines()[0].width, 8 + 1 - 2 + 8);
+		assert_eq!(layout.size().height, 28);
+	}
+}
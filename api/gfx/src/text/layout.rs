@@ -0,0 +1,657 @@
+//! Text layout computed in Rust: line breaking, alignment and measuring.
+//!
+//! Layout is computed from glyph [`Metrics`] of the fonts,
+//! so the text can be measured and paginated before drawing,
+//! and can mix regular, bold and italic fonts.
+//!
+//! ```ignore
+//! let layout = TextLayout::with_font(&font).bold(FontMetrics::new(&bold))
+//!                                          .markup(true)
+//!                                          .width(200)
+//!                                          .align(TextAlignment::Center);
+//! let lines = layout.layout("Hello, *bold* world!");
+//! layout.draw(&lines, 100, 20)?;
+//! ```
+
+use core::cell::RefCell;
+use core::ffi::c_int;
+use core::ops::Range;
+use alloc::ffi::NulError;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::Graphics;
+use crate::geometry::Rect;
+use crate::geometry::Size;
+use super::Font;
+use super::FontPage;
+use super::TextAlignment;
+use super::TextWrappingMode;
+
+
+/// Glyph metrics of the font used for the layout.
+pub trait Metrics {
+	/// Height of the line in pixels.
+	fn height(&self) -> c_int;
+
+	/// Advance of the character `c` in pixels,
+	/// or `None` if the font has no glyph for it.
+	fn advance(&self, c: char) -> Option<c_int>;
+
+	/// Kerning adjustment between characters `c` and `next`.
+	fn kerning(&self, c: char, next: char) -> c_int;
+}
+
+impl<T: Metrics> Metrics for &'_ T {
+	fn height(&self) -> c_int { (*self).height() }
+	fn advance(&self, c: char) -> Option<c_int> { (*self).advance(c) }
+	fn kerning(&self, c: char, next: char) -> c_int { (*self).kerning(c, next) }
+}
+
+
+/// Glyph metrics of the [`Font`] requested from the system.
+///
+/// Uses [`get_font_page`](Graphics::get_font_page), [`get_page_glyph`](Graphics::get_page_glyph)
+/// and [`get_glyph_kerning`](Graphics::get_glyph_kerning), caching the last used page.
+pub struct FontMetrics<'f, Api: crate::api::Api = crate::api::Default> {
+	font: &'f Font,
+	gfx: Graphics<Api>,
+	page: RefCell<Option<(u32, FontPage)>>,
+}
+
+impl<'f> FontMetrics<'f> {
+	pub fn new(font: &'f Font) -> Self { Self::new_with(crate::api::Default, font) }
+}
+
+impl<'f, Api: crate::api::Api> FontMetrics<'f, Api> {
+	pub fn new_with(api: Api, font: &'f Font) -> Self {
+		Self { font,
+		       gfx: Graphics::new_with(api),
+		       page: RefCell::new(None) }
+	}
+
+	pub const fn font(&self) -> &'f Font { self.font }
+
+	/// Calls `f` with the page containing the character `c`.
+	fn with_page<R>(&self, c: char, f: impl FnOnce(&Graphics<Api>, &FontPage) -> R) -> Option<R> {
+		let index = c as u32 & !0xFF;
+		let mut page = self.page.borrow_mut();
+		if page.as_ref().is_none_or(|(i, _)| *i != index) {
+			*page = Some((index, self.gfx.get_font_page(self.font, c as u32).ok()?));
+		}
+		page.as_ref().map(|(_, page)| f(&self.gfx, page))
+	}
+}
+
+impl<Api: crate::api::Api> Metrics for FontMetrics<'_, Api> {
+	fn height(&self) -> c_int { self.gfx.get_font_height(self.font) as _ }
+
+	fn advance(&self, c: char) -> Option<c_int> {
+		self.with_page(c, |gfx, page| {
+			    let mut advance = 0;
+			    gfx.get_page_glyph_with_bitmap(page, c as u32, &mut advance)
+			       .ok()
+			       .map(|_| advance)
+		    })
+		    .flatten()
+	}
+
+	fn kerning(&self, c: char, next: char) -> c_int {
+		self.with_page(c, |gfx, page| {
+			    gfx.get_page_glyph(page, c as u32)
+			       .map(|glyph| gfx.get_glyph_kerning(&glyph, c as u32, next as u32))
+			       .unwrap_or(0)
+		    })
+		    .unwrap_or(0)
+	}
+}
+
+
+/// Style of the text, selects the font of the [`TextLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Style {
+	#[default]
+	Regular,
+	Bold,
+	Italic,
+	BoldItalic,
+}
+
+impl Style {
+	pub const fn is_bold(&self) -> bool { matches!(self, Self::Bold | Self::BoldItalic) }
+	pub const fn is_italic(&self) -> bool { matches!(self, Self::Italic | Self::BoldItalic) }
+
+	const fn toggle_bold(self) -> Self {
+		match self {
+			Self::Regular => Self::Bold,
+			Self::Bold => Self::Regular,
+			Self::Italic => Self::BoldItalic,
+			Self::BoldItalic => Self::Italic,
+		}
+	}
+
+	const fn toggle_italic(self) -> Self {
+		match self {
+			Self::Regular => Self::Italic,
+			Self::Italic => Self::Regular,
+			Self::Bold => Self::BoldItalic,
+			Self::BoldItalic => Self::Bold,
+		}
+	}
+}
+
+
+/// Builder and engine of the text layout.
+///
+/// By default the text is not limited in size,
+/// wrapped by words and aligned to the left, without tracking and leading.
+pub struct TextLayout<M: Metrics> {
+	regular: M,
+	bold: Option<M>,
+	italic: Option<M>,
+	bold_italic: Option<M>,
+	width: Option<c_int>,
+	height: Option<c_int>,
+	wrap: TextWrappingMode,
+	align: TextAlignment,
+	tracking: c_int,
+	leading: c_int,
+	markup: bool,
+}
+
+
+// ctor //
+
+impl<M: Metrics> TextLayout<M> {
+	pub fn new(regular: M) -> Self {
+		Self { regular,
+		       bold: None,
+		       italic: None,
+		       bold_italic: None,
+		       width: None,
+		       height: None,
+		       wrap: TextWrappingMode::kWrapWord,
+		       align: TextAlignment::kAlignTextLeft,
+		       tracking: 0,
+		       leading: 0,
+		       markup: false }
+	}
+}
+
+impl<'f> TextLayout<FontMetrics<'f>> {
+	/// Creates layout with the regular `font`, using default api end-point.
+	pub fn with_font(font: &'f Font) -> Self { Self::new(FontMetrics::new(font)) }
+}
+
+
+// builder //
+
+impl<M: Metrics> TextLayout<M> {
+	/// Sets font for the [bold](Style::Bold) text.
+	pub fn bold(mut self, font: M) -> Self {
+		self.bold = Some(font);
+		self
+	}
+
+	/// Sets font for the [italic](Style::Italic) text.
+	pub fn italic(mut self, font: M) -> Self {
+		self.italic = Some(font);
+		self
+	}
+
+	/// Sets font for the [bold and italic](Style::BoldItalic) text.
+	///
+	/// If not set, the bold font is used, then the italic one.
+	pub fn bold_italic(mut self, font: M) -> Self {
+		self.bold_italic = Some(font);
+		self
+	}
+
+	/// Sets maximum width of the lines.
+	pub fn width(mut self, width: c_int) -> Self {
+		self.width = Some(width);
+		self
+	}
+
+	/// Sets maximum height of the text,
+	/// lines that do not fit are left for the next page, see [`Layout::overflow`].
+	pub fn height(mut self, height: c_int) -> Self {
+		self.height = Some(height);
+		self
+	}
+
+	/// Sets how the lines longer than the [`width`](TextLayout::width) are wrapped.
+	///
+	/// With [`kWrapClip`](TextWrappingMode::kWrapClip) lines are broken only at new-line characters,
+	/// and clipped to the width when drawn.
+	pub fn wrap(mut self, wrap: TextWrappingMode) -> Self {
+		self.wrap = wrap;
+		self
+	}
+
+	/// Sets alignment of the lines within the [`width`](TextLayout::width),
+	/// or within the widest line if the width is not set.
+	pub fn align(mut self, align: TextAlignment) -> Self {
+		self.align = align;
+		self
+	}
+
+	/// Sets pixels added between characters, same as [`Graphics::set_text_tracking`].
+	pub fn tracking(mut self, tracking: c_int) -> Self {
+		self.tracking = tracking;
+		self
+	}
+
+	/// Sets pixels added between lines, same as [`Graphics::set_text_leading`].
+	pub fn leading(mut self, leading: c_int) -> Self {
+		self.leading = leading;
+		self
+	}
+
+	/// Enables inline font switches in the text.
+	///
+	/// `*` toggles bold and `_` toggles italic, as in the Lua API.
+	/// Doubled `**` and `__` are literal characters.
+	pub fn markup(mut self, markup: bool) -> Self {
+		self.markup = markup;
+		self
+	}
+}
+
+
+// impl //
+
+impl<M: Metrics> TextLayout<M> {
+	/// Returns metrics of the font used for the `style`.
+	pub fn metrics(&self, style: Style) -> &M {
+		let font = match style {
+			Style::Regular => None,
+			Style::Bold => self.bold.as_ref(),
+			Style::Italic => self.italic.as_ref(),
+			Style::BoldItalic => {
+				self.bold_italic
+				    .as_ref()
+				    .or(self.bold.as_ref())
+				    .or(self.italic.as_ref())
+			},
+		};
+		font.unwrap_or(&self.regular)
+	}
+
+	/// Returns size of the `text` laid out.
+	pub fn measure(&self, text: &str) -> Size { self.layout(text).size() }
+
+	/// Computes line breaks and positions of the `text`.
+	pub fn layout(&self, text: &str) -> Layout {
+		let atoms = self.atoms(text);
+		let pos = |i: usize| atoms.get(i).map_or(text.len(), |atom| atom.at);
+
+		// Break paragraphs into visible ranges of atoms:
+		let mut ranges = Vec::new();
+		let mut start = 0;
+		for end in atoms.iter()
+		                .enumerate()
+		                .filter(|(_, atom)| atom.c == '\n' && !atom.escaped)
+		                .map(|(i, _)| i)
+		                .chain((!atoms.is_empty()).then_some(atoms.len()))
+		{
+			if start == end {
+				ranges.push(start..end);
+			}
+			while start < end {
+				let (visible, next) = self.break_line(&atoms[start..end]);
+				ranges.push(start..start + visible);
+				start += next;
+			}
+			start = end + 1;
+		}
+
+		let mut layout = Layout::default();
+		let mut y = 0;
+		for range in ranges {
+			let line = &atoms[range.clone()];
+			let height = line.iter()
+			                 .map(|atom| self.metrics(atom.style).height())
+			                 .max()
+			                 .unwrap_or_else(|| self.regular.height());
+			if self.height.is_some_and(|max| y + height > max) {
+				layout.overflow = Some(pos(range.start));
+				break;
+			}
+
+			let (runs, width) = self.runs(line);
+			let end = line.last().map_or(pos(range.start), |atom| atom.end());
+			layout.lines.push(Line { x: 0,
+			                         y,
+			                         width,
+			                         height,
+			                         range: pos(range.start)..end,
+			                         runs });
+			y += height + self.leading;
+		}
+
+		let area = self.width
+		               .unwrap_or_else(|| layout.lines.iter().map(|line| line.width).max().unwrap_or(0));
+		for line in layout.lines.iter_mut() {
+			line.x = match self.align {
+				TextAlignment::kAlignTextLeft => 0,
+				TextAlignment::kAlignTextCenter => (area - line.width) / 2,
+				TextAlignment::kAlignTextRight => area - line.width,
+			};
+		}
+		layout
+	}
+
+
+	/// Splits the `text` into characters with styles, removing markup.
+	fn atoms(&self, text: &str) -> Vec<Atom> {
+		let mut atoms = Vec::with_capacity(text.len());
+		let mut style = Style::Regular;
+		let mut chars = text.char_indices().peekable();
+		while let Some((at, c)) = chars.next() {
+			let mut escaped = false;
+			if self.markup && (c == '*' || c == '_') {
+				if chars.next_if(|(_, next)| *next == c).is_none() {
+					style = if c == '*' {
+						style.toggle_bold()
+					} else {
+						style.toggle_italic()
+					};
+					continue;
+				}
+				escaped = true;
+			}
+			atoms.push(Atom { at,
+			                  c,
+			                  style,
+			                  escaped });
+		}
+		atoms
+	}
+
+	/// Returns width of the `atom` placed after the `prev` one.
+	fn step(&self, prev: Option<&Atom>, atom: &Atom) -> c_int {
+		let metrics = self.metrics(atom.style);
+		let mut width = metrics.advance(atom.c).unwrap_or(0);
+		if let Some(prev) = prev {
+			width += self.tracking;
+			if prev.style == atom.style {
+				width += metrics.kerning(prev.c, atom.c);
+			}
+		}
+		width
+	}
+
+	/// Finds the end of the first line in the paragraph.
+	///
+	/// Returns number of visible atoms, without trailing whitespace, and the start of the next line.
+	fn break_line(&self, atoms: &[Atom]) -> (usize, usize) {
+		let max = match (&self.wrap, self.width) {
+			(TextWrappingMode::kWrapClip, _) | (_, None) => return (atoms.len(), atoms.len()),
+			(_, Some(max)) => max,
+		};
+		let trim = |end: usize| {
+			atoms[..end].iter()
+			            .rposition(|atom| !atom.is_space())
+			            .map_or(0, |i| i + 1)
+		};
+		let skip = |start: usize| {
+			atoms[start..].iter()
+			              .position(|atom| !atom.is_space())
+			              .map_or(atoms.len(), |i| start + i)
+		};
+
+		let mut width = 0;
+		let mut word = None;
+		let mut text = false;
+		for (i, atom) in atoms.iter().enumerate() {
+			let prev = i.checked_sub(1).map(|i| &atoms[i]);
+			width += self.step(prev, atom);
+
+			if width > max && i > 0 {
+				return match word {
+					_ if atom.is_space() => (trim(i), skip(i)),
+					Some(word) if matches!(self.wrap, TextWrappingMode::kWrapWord) => (trim(word), word),
+					_ => (trim(i), i),
+				};
+			}
+
+			if text && !atom.is_space() && prev.is_some_and(Atom::is_space) {
+				word = Some(i);
+			}
+			text |= !atom.is_space();
+		}
+		(atoms.len(), atoms.len())
+	}
+
+	/// Groups atoms of the line into runs of the same style.
+	fn runs(&self, atoms: &[Atom]) -> (Vec<Run>, c_int) {
+		let mut runs: Vec<Run> = Vec::new();
+		let mut x = 0;
+		let mut prev: Option<&Atom> = None;
+		for atom in atoms {
+			let width = match runs.last_mut() {
+				Some(run) if run.style == atom.style => {
+					let width = self.step(prev, atom);
+					run.width += width;
+					run.text.push(atom.c);
+					width
+				},
+				_ => {
+					if prev.is_some() {
+						x += self.tracking;
+					}
+					let width = self.step(None, atom);
+					runs.push(Run { style: atom.style,
+					                x,
+					                width,
+					                text: atom.c.into(), });
+					width
+				},
+			};
+			x += width;
+			prev = Some(atom);
+		}
+		(runs, x)
+	}
+}
+
+
+impl<Api: crate::api::Api + Clone> TextLayout<FontMetrics<'_, Api>> {
+	/// Draws the `layout` with its top-left corner at `x`, `y`.
+	///
+	/// Runs are drawn with [`Graphics::draw_text`] in fonts of the layout,
+	/// so glyphs are rendered by the system as usual.
+	/// Runs in fonts of different heights are aligned by the bottom of the line.
+	///
+	/// If the wrapping mode is [`kWrapClip`](TextWrappingMode::kWrapClip) and the width is set,
+	/// drawing is clipped to the layout area.
+	///
+	/// Text tracking is restored after drawing, and the regular font is set as the current one.
+	pub fn draw(&self, layout: &Layout, x: c_int, y: c_int) -> Result<(), NulError> {
+		let gfx = &self.regular.gfx;
+		let _clip = match (&self.wrap, self.width) {
+			(TextWrappingMode::kWrapClip, Some(width)) => {
+				let height = self.height.unwrap_or(layout.size().height);
+				Some(gfx.clip_guard(Rect::new(x, y, width, height)))
+			},
+			_ => None,
+		};
+
+		let tracking = gfx.get_text_tracking();
+		gfx.set_text_tracking(self.tracking);
+		let res = layout.lines
+		                .iter()
+		                .flat_map(|line| line.runs.iter().map(move |run| (line, run)))
+		                .try_for_each(|(line, run)| {
+			                let metrics = self.metrics(run.style);
+			                gfx.set_font(metrics.font);
+			                let bottom = line.height - metrics.height();
+			                gfx.draw_text(&run.text, x + line.x + run.x, y + line.y + bottom)
+			                   .map(|_| ())
+		                });
+		gfx.set_font(self.regular.font);
+		gfx.set_text_tracking(tracking);
+		res
+	}
+}
+
+
+/// Character of the text with its style.
+struct Atom {
+	/// Byte offset in the text.
+	at: usize,
+	c: char,
+	style: Style,
+	/// Doubled markup character.
+	escaped: bool,
+}
+
+impl Atom {
+	fn is_space(&self) -> bool { self.c.is_whitespace() }
+
+	/// Byte offset in the text after the atom.
+	fn end(&self) -> usize { self.at + self.c.len_utf8() * if self.escaped { 2 } else { 1 } }
+}
+
+
+/// Computed layout of the text, see [`TextLayout::layout`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Layout {
+	lines: Vec<Line>,
+	overflow: Option<usize>,
+}
+
+impl Layout {
+	pub fn lines(&self) -> &[Line] { &self.lines }
+
+	pub fn line_count(&self) -> usize { self.lines.len() }
+
+	/// Returns the smallest rect containing all lines, relative to the top-left corner of the layout.
+	pub fn bounds(&self) -> Rect {
+		let Some(last) = self.lines.last() else {
+			return Rect::default();
+		};
+		let left = self.lines.iter().map(|line| line.x).min().unwrap_or(0);
+		let right = self.lines
+		                .iter()
+		                .map(|line| line.x + line.width)
+		                .max()
+		                .unwrap_or(0);
+		Rect::from_edges(left, 0, right, last.y + last.height)
+	}
+
+	/// Returns size of the [`bounds`](Layout::bounds).
+	pub fn size(&self) -> Size { self.bounds().size() }
+
+	/// Returns byte offset of the text that did not fit in the [`height`](TextLayout::height),
+	/// so it can be laid out on the next page.
+	pub const fn overflow(&self) -> Option<usize> { self.overflow }
+}
+
+
+/// Line of the [`Layout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+	/// Offset of the line from the left of the layout, by the alignment.
+	pub x: c_int,
+	/// Offset of the line from the top of the layout.
+	pub y: c_int,
+	pub width: c_int,
+	pub height: c_int,
+	/// Byte range of the line in the source text, including markup.
+	pub range: Range<usize>,
+	pub runs: Vec<Run>,
+}
+
+/// Part of the [`Line`] drawn in one font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+	pub style: Style,
+	/// Offset of the run from the start of the line.
+	pub x: c_int,
+	pub width: c_int,
+	/// Text of the run without markup.
+	pub text: String,
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	/// Monospace font with narrow `i` and kerned `AV`.
+	struct Mono(c_int);
+
+	impl Metrics for Mono {
+		fn height(&self) -> c_int { self.0 * 2 }
+
+		fn advance(&self, c: char) -> Option<c_int> {
+			match c {
+				'i' => Some(self.0 / 2),
+				'\n' => None,
+				_ => Some(self.0),
+			}
+		}
+
+		fn kerning(&self, c: char, next: char) -> c_int { if (c, next) == ('A', 'V') { -1 } else { 0 } }
+	}
+
+	fn texts(layout: &Layout) -> Vec<String> {
+		layout.lines()
+		      .iter()
+		      .map(|line| line.runs.iter().map(|run| run.text.as_str()).collect())
+		      .collect()
+	}
+
+
+	#[test]
+	fn wrap_and_align() {
+		let words = TextLayout::new(Mono(4)).width(24).tracking(1);
+		let layout = words.layout("AV ab  cdef\n\nghijklm");
+		assert_eq!(texts(&layout), ["AV ab", "cdef", "", "ghijk", "lm"]);
+		assert_eq!(layout.lines()[0].width, 5 * 4 + 4 - 1);
+		assert_eq!(layout.lines()[1].range, 7..11);
+		assert_eq!(layout.lines()[3].width, 4 * 4 + 2 + 4);
+		assert_eq!(layout.lines()[4].y, 4 * 8);
+		assert_eq!(layout.size(), Size::new(23, 5 * 8));
+
+		let chars = TextLayout::new(Mono(4)).width(10)
+		                                    .wrap(TextWrappingMode::kWrapCharacter);
+		assert_eq!(texts(&chars.layout("abc de")), ["ab", "c", "de"]);
+
+		let clip = TextLayout::new(Mono(4)).width(10)
+		                                   .wrap(TextWrappingMode::kWrapClip);
+		assert_eq!(clip.layout("abc de").line_count(), 1);
+
+		let right = TextLayout::new(Mono(4)).align(TextAlignment::kAlignTextRight)
+		                                    .leading(2);
+		let layout = right.layout("abc\ni");
+		assert_eq!(layout.lines()[1].x, 10);
+		assert_eq!(layout.lines()[1].y, 10);
+		assert_eq!(layout.bounds(), Rect::new(0, 0, 12, 18));
+	}
+
+	#[test]
+	fn markup_and_pages() {
+		let layout = TextLayout::new(Mono(4)).bold(Mono(5)).markup(true).tracking(1);
+		let lines = layout.layout("a *b_c_* 2**3");
+		let runs = &lines.lines()[0].runs;
+		assert_eq!(
+		           runs.iter().map(|run| run.style).collect::<Vec<_>>(),
+		           [Style::Regular, Style::Bold, Style::BoldItalic, Style::Regular]
+		);
+		assert_eq!(runs[3].text, " 2*3");
+		assert_eq!((runs[1].x, runs[1].width), (10, 5));
+		assert_eq!(runs[2].x, 16);
+		assert_eq!(lines.lines()[0].height, 10);
+
+		let page = TextLayout::new(Mono(4)).width(8).height(20);
+		let lines = page.layout("ab cd ef");
+		assert_eq!(lines.line_count(), 2);
+		assert_eq!(lines.overflow(), Some(6));
+		assert_eq!(page.layout("ab").overflow(), None);
+		assert_eq!(page.layout("").line_count(), 0);
+	}
+}
@@ -0,0 +1,494 @@
+//! Tile maps, layers of tiles drawn from a [`BitmapTable`].
+//!
+//! ```ignore
+//! let tiles = BitmapTable::load("images/tiles")?;
+//! let mut map = TileMap::new(tiles, Size::new(16, 16));
+//! map.push_layer(TileLayer::from_indices(64, 32, &indices).unwrap());
+//!
+//! // in the update callback:
+//! map.set_camera(Point::new(player.x - 200, player.y - 120));
+//! map.draw();
+//! ```
+
+use core::ffi::c_int;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use sys::ffi::PDRect;
+
+use crate::Graphics;
+use crate::bitmap::BitmapFlip;
+use crate::bitmap::animation::FrameTable;
+use crate::bitmap::table::BitmapTable;
+use crate::geometry::Point;
+use crate::geometry::Rect;
+use crate::geometry::Size;
+
+pub mod tiled;
+
+
+/// Tile of the layer, index in the table with flip flags.
+///
+/// Packed the same way as global tile ids of the [Tiled](https://www.mapeditor.org) maps:
+/// the highest bit is horizontal flip, the next one is vertical flip,
+/// and the rest is the index plus one, zero is an empty tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Tile(u32);
+
+impl Tile {
+	pub const EMPTY: Self = Self(0);
+
+	const FLIP_X: u32 = 1 << 31;
+	const FLIP_Y: u32 = 1 << 30;
+	const FLAGS: u32 = 0xF000_0000;
+
+	/// Creates tile with the `index` in the table, without flip.
+	///
+	/// Negative `index` is an empty tile.
+	pub const fn new(index: c_int) -> Self {
+		if index < 0 {
+			return Self::EMPTY;
+		}
+		Self((index as u32).wrapping_add(1) & !Self::FLAGS)
+	}
+
+	/// Creates tile from the packed value.
+	///
+	/// Flags not supported by the tile map, such as diagonal flip of Tiled, are dropped.
+	pub const fn from_raw(raw: u32) -> Self { Self(raw & !(Self::FLAGS & !(Self::FLIP_X | Self::FLIP_Y))) }
+
+	pub const fn into_raw(self) -> u32 { self.0 }
+
+	/// Returns the tile flipped horizontally if `x`, vertically if `y`.
+	pub const fn flipped(self, x: bool, y: bool) -> Self {
+		let mut raw = self.0;
+		if x {
+			raw ^= Self::FLIP_X;
+		}
+		if y {
+			raw ^= Self::FLIP_Y;
+		}
+		Self(raw)
+	}
+
+	pub const fn is_empty(&self) -> bool { self.0 & !Self::FLAGS == 0 }
+
+	/// Returns index in the table, `None` for the empty tile.
+	pub const fn index(&self) -> Option<c_int> {
+		match self.0 & !Self::FLAGS {
+			0 => None,
+			i => Some(i as c_int - 1),
+		}
+	}
+
+	pub const fn flip(&self) -> BitmapFlip {
+		match (self.0 & Self::FLIP_X != 0, self.0 & Self::FLIP_Y != 0) {
+			(false, false) => BitmapFlip::kBitmapUnflipped,
+			(true, false) => BitmapFlip::kBitmapFlippedX,
+			(false, true) => BitmapFlip::kBitmapFlippedY,
+			(true, true) => BitmapFlip::kBitmapFlippedXY,
+		}
+	}
+}
+
+
+/// Layer of the tile map, `width` by `height` tiles, row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TileLayer {
+	width: c_int,
+	height: c_int,
+	tiles: Vec<Tile>,
+	/// Hidden layers are not drawn.
+	pub visible: bool,
+	/// Offset of the layer in pixels.
+	pub offset: Point,
+}
+
+
+// ctor //
+
+impl TileLayer {
+	/// Creates layer of empty tiles.
+	///
+	/// Returns `None` if number of tiles `width * height` overflows.
+	pub fn new(width: c_int, height: c_int) -> Option<Self> {
+		let (width, height) = (width.max(0), height.max(0));
+		let len = Self::tiles_count(width, height)?;
+		Some(Self { width,
+		            height,
+		            tiles: vec![Tile::EMPTY; len],
+		            visible: true,
+		            offset: Point::ZERO })
+	}
+
+	/// Creates layer of the `tiles`, returns `None` if number of tiles is not `width * height`.
+	pub fn from_tiles(width: c_int, height: c_int, tiles: Vec<Tile>) -> Option<Self> {
+		if Self::tiles_count(width, height)? != tiles.len() {
+			return None;
+		}
+		Some(Self { width,
+		            height,
+		            tiles,
+		            visible: true,
+		            offset: Point::ZERO })
+	}
+
+	/// Creates layer of unflipped tiles with the `indices`, negative index is an empty tile.
+	///
+	/// Returns `None` if number of indices is not `width * height`.
+	pub fn from_indices(width: c_int, height: c_int, indices: &[c_int]) -> Option<Self> {
+		let tiles = indices.iter().map(|i| Tile::new(*i)).collect();
+		Self::from_tiles(width, height, tiles)
+	}
+
+	/// Number of tiles in the `width` by `height` layer,
+	/// `None` if either is negative or the number doesn't fit `c_int` used for the tile positions.
+	fn tiles_count(width: c_int, height: c_int) -> Option<usize> {
+		if width < 0 || height < 0 {
+			return None;
+		}
+		(width as usize).checked_mul(height as usize)
+		                .filter(|len| *len <= c_int::MAX as usize)
+	}
+}
+
+
+// utils //
+
+impl TileLayer {
+	pub const fn width(&self) -> c_int { self.width }
+	pub const fn height(&self) -> c_int { self.height }
+
+	pub fn tiles(&self) -> &[Tile] { &self.tiles }
+	pub fn tiles_mut(&mut self) -> &mut [Tile] { &mut self.tiles }
+
+	fn position(&self, x: c_int, y: c_int) -> Option<usize> {
+		(x >= 0 && y >= 0 && x < self.width && y < self.height).then(|| (y * self.width + x) as usize)
+	}
+
+	/// Returns the tile at column `x` and row `y`, `None` if it's outside of the layer.
+	pub fn get(&self, x: c_int, y: c_int) -> Option<Tile> { self.position(x, y).map(|i| self.tiles[i]) }
+
+	/// Sets the tile at column `x` and row `y`, tiles outside of the layer are ignored.
+	pub fn set(&mut self, x: c_int, y: c_int, tile: Tile) {
+		if let Some(i) = self.position(x, y) {
+			self.tiles[i] = tile;
+		}
+	}
+
+	/// Returns rects of the `solid` tiles, in tiles.
+	///
+	/// Adjacent solid tiles are merged, first into horizontal runs,
+	/// then runs of the same span in consecutive rows into one rect,
+	/// to keep the number of rects low.
+	pub fn solid_rects<F: Fn(Tile) -> bool>(&self, solid: F) -> Vec<Rect> {
+		let mut rects: Vec<Rect> = Vec::new();
+		for y in 0..self.height {
+			let mut x = 0;
+			while x < self.width {
+				if !solid(self.tiles[(y * self.width + x) as usize]) {
+					x += 1;
+					continue;
+				}
+				let start = x;
+				while x < self.width && solid(self.tiles[(y * self.width + x) as usize]) {
+					x += 1;
+				}
+
+				// Extend the rect of the same span ending at the previous row:
+				let above = rects.iter_mut()
+				                 .rev()
+				                 .find(|r| r.bottom() == y && r.x == start && r.right() == x);
+				match above {
+					Some(rect) => rect.height += 1,
+					None => rects.push(Rect::new(start, y, x - start, 1)),
+				}
+			}
+		}
+		rects.sort_by_key(|r| (r.y, r.x));
+		rects
+	}
+}
+
+
+/// Tile map, layers of tiles from the same table, drawn from back to front.
+pub struct TileMap<T: FrameTable = BitmapTable> {
+	table: T,
+	tile: Size,
+	layers: Vec<TileLayer>,
+	camera: Point,
+	viewport: Size,
+}
+
+
+// ctor //
+
+impl<T: FrameTable> TileMap<T> {
+	/// Creates tile map without layers, with tiles of the `tile` size from the `table`.
+	///
+	/// Viewport is the size of the display.
+	pub fn new(table: T, tile: Size) -> Self {
+		Self { table,
+		       tile,
+		       layers: Vec::new(),
+		       camera: Point::ZERO,
+		       viewport: Size::new(sys::ffi::LCD_COLUMNS as _, sys::ffi::LCD_ROWS as _) }
+	}
+
+	pub fn with_layers(mut self, layers: Vec<TileLayer>) -> Self {
+		self.layers = layers;
+		self
+	}
+
+	pub fn into_table(self) -> T { self.table }
+}
+
+
+// utils //
+
+impl<T: FrameTable> TileMap<T> {
+	pub fn table(&self) -> &T { &self.table }
+
+	/// Size of tiles in pixels.
+	pub const fn tile_size(&self) -> Size { self.tile }
+
+	pub fn layers(&self) -> &[TileLayer] { &self.layers }
+	pub fn layers_mut(&mut self) -> &mut Vec<TileLayer> { &mut self.layers }
+	pub fn layer(&self, index: usize) -> Option<&TileLayer> { self.layers.get(index) }
+	pub fn layer_mut(&mut self, index: usize) -> Option<&mut TileLayer> { self.layers.get_mut(index) }
+
+	/// Adds the `layer` on top of others, returns its index.
+	pub fn push_layer(&mut self, layer: TileLayer) -> usize {
+		self.layers.push(layer);
+		self.layers.len() - 1
+	}
+
+	/// Returns size of the map in pixels, the size of its largest layer.
+	pub fn size(&self) -> Size {
+		self.layers.iter().fold(Size::new(0, 0), |size, layer| {
+			                  Size::new(
+			                            size.width.max(layer.width * self.tile.width),
+			                            size.height.max(layer.height * self.tile.height),
+			)
+		                  })
+	}
+
+	/// Position of the top-left corner of the viewport on the map.
+	pub const fn camera(&self) -> Point { self.camera }
+	pub fn set_camera(&mut self, camera: Point) { self.camera = camera }
+
+	/// Moves the camera so the `point` on the map is at the center of the viewport,
+	/// but not beyond the edges of the map.
+	pub fn center_camera(&mut self, point: Point) {
+		let size = self.size();
+		let clamp = |v: c_int, view: c_int, len: c_int| v.min(len - view).max(0);
+		self.camera = Point::new(
+		                         clamp(point.x - self.viewport.width / 2, self.viewport.width, size.width),
+		                         clamp(
+			point.y - self.viewport.height / 2,
+			self.viewport.height,
+			size.height,
+		),
+		);
+	}
+
+	/// Size of the area on the screen where the map is drawn.
+	pub const fn viewport(&self) -> Size { self.viewport }
+	pub fn set_viewport(&mut self, viewport: Size) { self.viewport = viewport }
+
+	/// Converts the `point` on the map to the screen coordinates.
+	pub fn to_screen(&self, point: Point) -> Point { point - self.camera }
+
+	/// Converts the `point` on the screen to the map coordinates.
+	pub fn to_map(&self, point: Point) -> Point { point + self.camera }
+
+	/// Returns column and row of the tile at the `point` on the map in the `layer`,
+	/// `None` if there is no such tile.
+	pub fn tile_at(&self, layer: usize, point: Point) -> Option<(c_int, c_int)> {
+		let layer = self.layers.get(layer)?;
+		let point = point - layer.offset;
+		let (x, y) = (point.x.div_euclid(self.tile.width), point.y.div_euclid(self.tile.height));
+		layer.get(x, y).map(|_| (x, y))
+	}
+
+	/// Returns ranges of columns and rows of tiles of the `layer` visible in the `rect` on the screen.
+	pub fn visible_tiles(&self,
+	                     layer: &TileLayer,
+	                     rect: Rect)
+	                     -> (core::ops::Range<c_int>, core::ops::Range<c_int>) {
+		if self.tile.is_empty() || rect.is_empty() {
+			return (0..0, 0..0);
+		}
+		let origin = self.camera - layer.offset;
+		let range = |start: c_int, len: c_int, tile: c_int, count: c_int| {
+			let first = start.div_euclid(tile).clamp(0, count);
+			let last = (start + len + tile - 1).div_euclid(tile).clamp(0, count);
+			first..last
+		};
+		(range(rect.x + origin.x, rect.width, self.tile.width, layer.width),
+		 range(rect.y + origin.y, rect.height, self.tile.height, layer.height))
+	}
+
+	/// Returns rects of the `solid` tiles of the `layer` on the map, in pixels,
+	/// e.g. to set as collide rects of static sprites of the collision world.
+	///
+	/// Adjacent tiles are merged, see [`TileLayer::solid_rects`].
+	pub fn collision_rects<F: Fn(Tile) -> bool>(&self, layer: usize, solid: F) -> Vec<PDRect> {
+		let Some(layer) = self.layers.get(layer) else {
+			return Vec::new();
+		};
+		layer.solid_rects(solid)
+		     .into_iter()
+		     .map(|r| {
+			     Rect::new(
+			               r.x * self.tile.width,
+			               r.y * self.tile.height,
+			               r.width * self.tile.width,
+			               r.height * self.tile.height,
+			).translate(layer.offset)
+			     .into()
+		     })
+		     .collect()
+	}
+}
+
+
+// impl //
+
+impl<T: FrameTable> TileMap<T> {
+	/// Draws tiles of visible layers in the viewport at the top-left corner of the screen.
+	///
+	/// Only tiles visible in the viewport are drawn.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::drawBitmap`] for each tile.
+	#[doc(alias = "sys::ffi::playdate_graphics::drawBitmap")]
+	pub fn draw(&self) { self.draw_rect(Rect::from_origin_size(Point::ZERO, self.viewport)) }
+
+	/// Draws tiles of visible layers intersecting the `rect` on the screen,
+	/// e.g. the dirty rect of the sprite's draw callback.
+	///
+	/// Tiles are not clipped to the `rect`, set the clip rect if needed.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::drawBitmap`] for each tile.
+	#[doc(alias = "sys::ffi::playdate_graphics::drawBitmap")]
+	pub fn draw_rect(&self, rect: Rect) {
+		let Some(rect) = rect.intersection(&Rect::from_origin_size(Point::ZERO, self.viewport)) else {
+			return;
+		};
+		let gfx = Graphics::Default();
+		for layer in self.layers.iter().filter(|layer| layer.visible) {
+			let origin = layer.offset - self.camera;
+			let (columns, rows) = self.visible_tiles(layer, rect);
+			for y in rows {
+				for x in columns.clone() {
+					let tile = layer.tiles[(y * layer.width + x) as usize];
+					let Some(bitmap) = tile.index().and_then(|i| self.table.frame(i)) else {
+						continue;
+					};
+					let (sx, sy) = (origin.x + x * self.tile.width, origin.y + y * self.tile.height);
+					gfx.draw(&bitmap, sx, sy, tile.flip());
+				}
+			}
+		}
+	}
+}
+
+impl<T: FrameTable> core::fmt::Debug for TileMap<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("TileMap")
+		 .field("tile", &self.tile)
+		 .field("layers", &self.layers.len())
+		 .field("camera", &self.camera)
+		 .field("viewport", &self.viewport)
+		 .finish()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::bitmap::BitmapRef;
+
+
+	struct Tiles;
+
+	impl FrameTable for Tiles {
+		fn count(&self) -> c_int { 4 }
+		fn frame(&self, _: c_int) -> Option<BitmapRef<'_>> { None }
+	}
+
+
+	#[test]
+	fn tile() {
+		let tile = Tile::new(5).flipped(true, false);
+		assert_eq!(tile.index(), Some(5));
+		assert!(matches!(tile.flip(), BitmapFlip::kBitmapFlippedX));
+		assert_eq!(tile.into_raw(), 0x8000_0006);
+		assert!(Tile::from_raw(0x8000_0000).is_empty());
+		// Diagonal flip of Tiled is dropped:
+		assert_eq!(Tile::from_raw(0x2000_0001), Tile::new(0));
+		assert!(Tile::new(-1).is_empty());
+	}
+
+	#[test]
+	fn layer_size() {
+		assert!(TileLayer::new(c_int::MAX, c_int::MAX).is_none());
+		assert_eq!(TileLayer::new(-1, 10).map(|layer| layer.tiles().len()), Some(0));
+		assert!(TileLayer::from_tiles(c_int::MAX, 2, Vec::new()).is_none());
+		assert!(TileLayer::from_tiles(-2, -2, vec![Tile::EMPTY; 4]).is_none());
+		assert!(TileLayer::from_indices(2, 1, &[-1, 3]).is_some());
+	}
+
+	#[test]
+	fn visible_tiles() {
+		let mut map = TileMap::new(Tiles, Size::new(16, 16));
+		map.push_layer(TileLayer::new(100, 10).unwrap());
+		map.set_camera(Point::new(8, -20));
+		let layer = &map.layers()[0];
+		assert_eq!(
+		           map.visible_tiles(layer, Rect::new(0, 0, 400, 240)),
+		           (0..26, 0..10)
+		);
+		assert_eq!(map.visible_tiles(layer, Rect::new(8, 20, 16, 16)), (1..2, 0..1));
+		assert_eq!(map.tile_at(0, Point::new(17, 159)), Some((1, 9)));
+		assert_eq!(map.tile_at(0, Point::new(-1, 0)), None);
+
+		map.center_camera(Point::new(10, 10));
+		assert_eq!(map.camera(), Point::new(0, 0));
+		map.center_camera(Point::new(1590, 100));
+		assert_eq!(map.camera(), Point::new(1600 - 400, 0));
+	}
+
+	#[test]
+	fn collision() {
+		#[rustfmt::skip]
+		let layer = TileLayer::from_indices(5, 4, &[
+			1, 1, -1, 1, 1,
+			1, 1, -1, -1, 1,
+			-1, 0, 0, -1, 1,
+			-1, 0, 0, -1, -1,
+		]).unwrap();
+		let rects = layer.solid_rects(|tile| tile.index() == Some(1));
+		assert_eq!(
+		           rects,
+		           [
+			Rect::new(0, 0, 2, 2),
+			Rect::new(3, 0, 2, 1),
+			Rect::new(4, 1, 1, 2)
+		]
+		);
+		assert_eq!(
+		           layer.solid_rects(|tile| tile.index() == Some(0)),
+		           [Rect::new(1, 2, 2, 2)]
+		);
+
+		let mut map = TileMap::new(Tiles, Size::new(8, 8)).with_layers(vec![layer]);
+		map.layers_mut()[0].offset = Point::new(0, 4);
+		let rects = map.collision_rects(0, |tile| tile.index() == Some(0));
+		assert_eq!(
+		           (rects[0].x, rects[0].y, rects[0].width, rects[0].height),
+		           (8.0, 20.0, 16.0, 16.0)
+		);
+	}
+}
@@ -0,0 +1,706 @@
+//! [Tiled](https://www.mapeditor.org) maps converted by the assets pipeline of `playdate-build`.
+//!
+//! Maps declared in the assets as `"levels/one.pdtm" = "maps/one.tmj"` are converted into
+//! the compact binary format, with images of the tilesets next to them as image tables.
+//!
+//! ```ignore
+//! let level = TiledMap::load("levels/one.pdtm", false)?;
+//! let mut tables = level.load_tables("levels")?;
+//! let map = level.tile_map(tables.remove(0), 0);
+//! let walls = level.collision_rects(level.layer("ground").unwrap());
+//! let spawns = level.object_group("spawns").unwrap_or_default();
+//! ```
+
+use core::ffi::c_int;
+use core::fmt;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use fs::Path;
+use sys::ffi::PDRect;
+
+use crate::bitmap::BitmapFlip;
+use crate::bitmap::animation::FrameTable;
+use crate::bitmap::table::BitmapTable;
+use crate::error::ApiError;
+use crate::geometry::Point;
+use crate::geometry::Rect;
+use crate::geometry::Size;
+use super::Tile;
+use super::TileLayer;
+use super::TileMap;
+
+
+/// Magic bytes of the converted map.
+pub const MAGIC: &[u8; 4] = b"PDTM";
+/// Supported version of the converted map format.
+pub const VERSION: u8 = 1;
+
+/// Flip flags of the global tile ids, including diagonal flip.
+const FLAGS: u32 = 0xF000_0000;
+
+
+/// Error of the converted map parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+	/// Data is not a converted map.
+	Magic,
+	/// Unsupported version of the format.
+	Version(u8),
+	/// Unexpected end of data at the byte offset.
+	Truncated { offset: usize },
+	/// Invalid string or value at the byte offset.
+	Invalid { offset: usize },
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ParseError::Magic => write!(f, "Not a tile map"),
+			ParseError::Version(v) => write!(f, "Unsupported tile map version {v}"),
+			ParseError::Truncated { offset } => write!(f, "Unexpected end of tile map at offset {offset}"),
+			ParseError::Invalid { offset } => write!(f, "Invalid tile map data at offset {offset}"),
+		}
+	}
+}
+
+impl core::error::Error for ParseError {}
+
+
+/// Tiled map with its tilesets, tile layers and object groups.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TiledMap {
+	/// Width in tiles.
+	pub width: c_int,
+	/// Height in tiles.
+	pub height: c_int,
+	/// Size of tiles in pixels.
+	pub tile: Size,
+	pub properties: Properties,
+	/// Tilesets ordered by the first global tile id.
+	pub tilesets: Vec<Tileset>,
+	/// Layers from back to front, groups are flattened.
+	pub layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tileset {
+	/// Path of the image table, relative to the map.
+	pub table: String,
+	/// Global id of the first tile.
+	pub first_gid: u32,
+	pub count: u32,
+	pub columns: u32,
+	/// Size of tiles in pixels.
+	pub tile: Size,
+	/// Collision rects relative to the tile, by local tile id.
+	collision: Vec<(u32, Vec<Rect>)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+	pub name: String,
+	pub visible: bool,
+	/// Offset in pixels.
+	pub offset: Point,
+	pub properties: Properties,
+	pub kind: LayerKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayerKind {
+	/// Global tile ids with flip flags, row by row.
+	Tiles {
+		width: c_int,
+		height: c_int,
+		gids: Vec<u32>,
+	},
+	Objects(Vec<Object>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Object {
+	pub id: u32,
+	pub name: String,
+	pub class: String,
+	/// Position and size in pixels.
+	pub rect: Rect,
+	/// Global tile id of the tile object, zero for others.
+	pub gid: u32,
+	pub shape: Shape,
+	/// Points of polygon and polyline, relative to the object position.
+	pub points: Vec<Point>,
+	pub properties: Properties,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shape {
+	Rect,
+	Ellipse,
+	Point,
+	Polygon,
+	Polyline,
+}
+
+/// Custom properties in order of declaration.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Properties(pub Vec<(String, PropertyValue)>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+	String(String),
+	Int(i32),
+	Float(f32),
+	Bool(bool),
+	/// ARGB color.
+	Color(u32),
+	/// Path relative to the source map.
+	File(String),
+	/// Id of the referenced object.
+	Object(u32),
+}
+
+
+// ctor //
+
+impl TiledMap {
+	/// Parses the converted map.
+	pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+		let mut r = Reader { data, pos: 0 };
+		if r.bytes(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+			return Err(ParseError::Magic);
+		}
+		match r.u8()? {
+			VERSION => {},
+			v => return Err(ParseError::Version(v)),
+		}
+
+		let width = r.int()?;
+		let height = r.int()?;
+		let tile = Size::new(r.u16()? as _, r.u16()? as _);
+		let properties = r.props()?;
+
+		let tilesets = (0..r.u16()?).map(|_| r.tileset()).collect::<Result<_, _>>()?;
+		let layers = (0..r.u16()?).map(|_| r.layer()).collect::<Result<_, _>>()?;
+
+		if r.pos != data.len() {
+			return Err(ParseError::Invalid { offset: r.pos });
+		}
+		Ok(Self { width,
+		          height,
+		          tile,
+		          properties,
+		          tilesets,
+		          layers })
+	}
+
+	/// Reads and parses the converted map at `path`.
+	///
+	/// If `data_dir` is `true`, the file is looked for in the data folder first.
+	///
+	/// Uses [`fs::read`].
+	pub fn load<P: AsRef<Path>>(path: P, data_dir: bool) -> Result<Self, ApiError> {
+		let data = fs::read(path, data_dir).map_err(ApiError::from_err)?;
+		Self::parse(&data).map_err(|err| ApiError::Api(crate::error::Error::Map(err)))
+	}
+}
+
+
+// utils //
+
+impl TiledMap {
+	/// Returns size of the map in pixels, saturated at `c_int::MAX`.
+	pub const fn size(&self) -> Size {
+		Size::new(
+		          self.width.saturating_mul(self.tile.width),
+		          self.height.saturating_mul(self.tile.height),
+		)
+	}
+
+	/// Returns the first layer named `name`.
+	pub fn layer(&self, name: &str) -> Option<&Layer> { self.layers.iter().find(|layer| layer.name == name) }
+
+	/// Returns objects of the first object group named `name`.
+	pub fn object_group(&self, name: &str) -> Option<&[Object]> {
+		self.layers.iter().find_map(|layer| {
+			                  match &layer.kind {
+				                  LayerKind::Objects(objects) if layer.name == name => Some(&objects[..]),
+			                     _ => None,
+			                  }
+		                  })
+	}
+
+	/// Returns index of the tileset containing the global tile id `gid`.
+	pub fn tileset_of(&self, gid: u32) -> Option<usize> {
+		let gid = gid & !FLAGS;
+		self.tilesets
+		    .iter()
+		    .rposition(|ts| ts.first_gid <= gid)
+		    .filter(|i| gid - self.tilesets[*i].first_gid < self.tilesets[*i].count)
+	}
+
+	/// Returns the tile `layer` with tiles of the tileset at `tileset` index,
+	/// tiles of other tilesets are empty.
+	///
+	/// Returns `None` if the `layer` is an object group.
+	pub fn tile_layer(&self, layer: &Layer, tileset: usize) -> Option<TileLayer> {
+		let LayerKind::Tiles { width, height, gids } = &layer.kind else {
+			return None;
+		};
+		let tiles = gids.iter().map(|gid| self.local_tile(*gid, tileset)).collect();
+		let mut tiles = TileLayer::from_tiles(*width, *height, tiles)?;
+		tiles.visible = layer.visible;
+		tiles.offset = layer.offset;
+		Some(tiles)
+	}
+
+	/// Creates [`TileMap`] of the tile layers with tiles of the tileset at `tileset` index,
+	/// drawn from the `table`.
+	pub fn tile_map<T: FrameTable>(&self, table: T, tileset: usize) -> TileMap<T> {
+		let tile = self.tilesets.get(tileset).map_or(self.tile, |ts| ts.tile);
+		let layers = self.layers
+		                 .iter()
+		                 .filter_map(|layer| self.tile_layer(layer, tileset))
+		                 .collect();
+		TileMap::new(table, tile).with_layers(layers)
+	}
+
+	/// Loads image tables of the tilesets from the directory of the map, `dir`.
+	///
+	/// Calls [`sys::ffi::playdate_graphics::loadBitmapTable`] for each tileset.
+	#[doc(alias = "sys::ffi::playdate_graphics::loadBitmapTable")]
+	pub fn load_tables(&self, dir: &str) -> Result<Vec<BitmapTable>, ApiError> {
+		let dir = dir.trim_end_matches('/');
+		self.tilesets
+		    .iter()
+		    .map(|ts| {
+			    match dir {
+				    "" => BitmapTable::load(&ts.table),
+			       dir => BitmapTable::load(format!("{dir}/{}", ts.table)),
+			    }
+		    })
+		    .collect()
+	}
+
+	/// Returns collision rects of the tiles of the `layer` on the map, in pixels,
+	/// e.g. to set as collide rects of static sprites of the collision world.
+	///
+	/// Adjacent entirely solid tiles are merged, see [`TileLayer::solid_rects`],
+	/// other collision shapes are returned per tile, flipped with the tile.
+	pub fn collision_rects(&self, layer: &Layer) -> Vec<PDRect> {
+		let LayerKind::Tiles { width, height, gids } = &layer.kind else {
+			return Vec::new();
+		};
+		let shapes = |tile: Tile| {
+			let gid = tile.index()? as u32 + 1;
+			let ts = &self.tilesets[self.tileset_of(gid)?];
+			Some((ts, ts.collision(gid - ts.first_gid)))
+		};
+		let full = |tile: Tile| matches!(shapes(tile), Some((ts, [rect])) if *rect == Rect::new(0, 0, ts.tile.width, ts.tile.height));
+
+		let tiles = gids.iter().map(|gid| Tile::from_raw(*gid)).collect();
+		let Some(tiles) = TileLayer::from_tiles(*width, *height, tiles) else {
+			return Vec::new();
+		};
+		let tile = self.tile;
+		let mut rects: Vec<Rect> = tiles.solid_rects(full)
+		                                .into_iter()
+		                                .map(|r| {
+			                                Rect::new(
+			                                          r.x * tile.width,
+			                                          r.y * tile.height,
+			                                          r.width * tile.width,
+			                                          r.height * tile.height,
+			)
+		                                })
+		                                .collect();
+
+		for y in 0..*height {
+			for x in 0..*width {
+				let Some(t) = tiles.get(x, y).filter(|t| !full(*t)) else {
+					continue;
+				};
+				let Some((ts, shapes)) = shapes(t) else { continue };
+				// Tiles larger than the grid are aligned to the bottom-left corner of the cell:
+				let origin = Point::new(x * tile.width, (y + 1) * tile.height - ts.tile.height);
+				let flip = t.flip();
+				let (flip_x, flip_y) = (matches!(flip, BitmapFlip::kBitmapFlippedX | BitmapFlip::kBitmapFlippedXY),
+				                        matches!(flip, BitmapFlip::kBitmapFlippedY | BitmapFlip::kBitmapFlippedXY));
+				rects.extend(shapes.iter().map(|r| {
+					                          let rx = if flip_x {
+						                          ts.tile.width - r.x - r.width
+					                          } else {
+						                          r.x
+					                          };
+					                          let ry = if flip_y {
+						                          ts.tile.height - r.y - r.height
+					                          } else {
+						                          r.y
+					                          };
+					                          Rect::new(rx, ry, r.width, r.height).translate(origin)
+				                          }));
+			}
+		}
+
+		rects.into_iter()
+		     .map(|r| r.translate(layer.offset).into())
+		     .collect()
+	}
+
+	fn local_tile(&self, gid: u32, tileset: usize) -> Tile {
+		match self.tilesets.get(tileset) {
+			Some(ts) if self.tileset_of(gid) == Some(tileset) => {
+				Tile::from_raw((gid & FLAGS) | ((gid & !FLAGS) - ts.first_gid + 1))
+			},
+			_ => Tile::EMPTY,
+		}
+	}
+}
+
+impl Tileset {
+	/// Returns collision rects of the tile with the local id, relative to the tile.
+	pub fn collision(&self, id: u32) -> &[Rect] {
+		self.collision
+		    .binary_search_by_key(&id, |(id, _)| *id)
+		    .map_or(&[], |i| &self.collision[i].1)
+	}
+}
+
+impl Layer {
+	/// Returns objects of the object group, `None` for tile layers.
+	pub fn objects(&self) -> Option<&[Object]> {
+		match &self.kind {
+			LayerKind::Objects(objects) => Some(objects),
+			LayerKind::Tiles { .. } => None,
+		}
+	}
+}
+
+impl Properties {
+	pub fn get(&self, name: &str) -> Option<&PropertyValue> {
+		self.0.iter().find(|(k, _)| k == name).map(|(_, v)| v)
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &PropertyValue)> {
+		self.0.iter().map(|(k, v)| (k.as_str(), v))
+	}
+}
+
+impl PropertyValue {
+	/// Returns the string or file path.
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			PropertyValue::String(s) | PropertyValue::File(s) => Some(s),
+			_ => None,
+		}
+	}
+
+	pub fn as_int(&self) -> Option<i32> {
+		match self {
+			PropertyValue::Int(v) => Some(*v),
+			_ => None,
+		}
+	}
+
+	/// Returns the float or int as float.
+	pub fn as_float(&self) -> Option<f32> {
+		match self {
+			PropertyValue::Float(v) => Some(*v),
+			PropertyValue::Int(v) => Some(*v as _),
+			_ => None,
+		}
+	}
+
+	pub fn as_bool(&self) -> Option<bool> {
+		match self {
+			PropertyValue::Bool(v) => Some(*v),
+			_ => None,
+		}
+	}
+}
+
+
+struct Reader<'d> {
+	data: &'d [u8],
+	pos: usize,
+}
+
+impl<'d> Reader<'d> {
+	fn bytes(&mut self, len: usize) -> Result<&'d [u8], ParseError> {
+		let bytes = self.data
+		                .get(self.pos..self.pos + len)
+		                .ok_or(ParseError::Truncated { offset: self.pos })?;
+		self.pos += len;
+		Ok(bytes)
+	}
+
+	fn array<const N: usize>(&mut self) -> Result<[u8; N], ParseError> {
+		let mut array = [0; N];
+		array.copy_from_slice(self.bytes(N)?);
+		Ok(array)
+	}
+
+	fn u8(&mut self) -> Result<u8, ParseError> { self.array().map(|[v]| v) }
+	fn u16(&mut self) -> Result<u16, ParseError> { self.array().map(u16::from_le_bytes) }
+	fn i16(&mut self) -> Result<c_int, ParseError> { self.array().map(i16::from_le_bytes).map(Into::into) }
+	fn u32(&mut self) -> Result<u32, ParseError> { self.array().map(u32::from_le_bytes) }
+	fn i32(&mut self) -> Result<i32, ParseError> { self.array().map(i32::from_le_bytes) }
+	fn f32(&mut self) -> Result<f32, ParseError> { self.array().map(f32::from_le_bytes) }
+
+	/// Reads `u32` that should fit into `c_int`.
+	fn int(&mut self) -> Result<c_int, ParseError> {
+		let offset = self.pos;
+		c_int::try_from(self.u32()?).map_err(|_| ParseError::Invalid { offset })
+	}
+
+	fn str(&mut self) -> Result<String, ParseError> {
+		let len = self.u16()? as usize;
+		let offset = self.pos;
+		let bytes = self.bytes(len)?;
+		core::str::from_utf8(bytes).map(Into::into)
+		                           .map_err(|_| ParseError::Invalid { offset })
+	}
+
+	fn point(&mut self) -> Result<Point, ParseError> { Ok(Point::new(self.i32()?, self.i32()?)) }
+
+	fn props(&mut self) -> Result<Properties, ParseError> {
+		let props = (0..self.u16()?).map(|_| {
+			                            let name = self.str()?;
+			                            let offset = self.pos;
+			                            let value = match self.u8()? {
+				                            0 => PropertyValue::String(self.str()?),
+			                               1 => PropertyValue::Int(self.i32()?),
+			                               2 => PropertyValue::Float(self.f32()?),
+			                               3 => PropertyValue::Bool(self.u8()? != 0),
+			                               4 => PropertyValue::Color(self.u32()?),
+			                               5 => PropertyValue::File(self.str()?),
+			                               6 => PropertyValue::Object(self.u32()?),
+			                               _ => return Err(ParseError::Invalid { offset }),
+			                            };
+			                            Ok((name, value))
+		                            });
+		props.collect::<Result<_, _>>().map(Properties)
+	}
+
+	fn tileset(&mut self) -> Result<Tileset, ParseError> {
+		let table = self.str()?;
+		let first_gid = self.u32()?;
+		let count = self.u32()?;
+		let columns = self.u32()?;
+		let tile = Size::new(self.u16()? as _, self.u16()? as _);
+		let collision = (0..self.u32()?).map(|_| {
+			                                let id = self.u32()?;
+			                                let rects = (0..self.u16()?).map(|_| {
+				                                                            Ok(Rect::new(
+				                                                                         self.i16()?,
+				                                                                         self.i16()?,
+				                                                                         self.i16()?,
+				                                                                         self.i16()?,
+				))
+			                                                            });
+			                                Ok((id, rects.collect::<Result<_, _>>()?))
+		                                });
+		Ok(Tileset { table,
+		             first_gid,
+		             count,
+		             columns,
+		             tile,
+		             collision: collision.collect::<Result<_, _>>()? })
+	}
+
+	fn layer(&mut self) -> Result<Layer, ParseError> {
+		let offset = self.pos;
+		let kind = self.u8()?;
+		let name = self.str()?;
+		let visible = self.u8()? != 0;
+		let position = self.point()?;
+		let properties = self.props()?;
+
+		let kind = match kind {
+			0 => {
+				let width = self.int()?;
+				let height = self.int()?;
+				let len = (width as usize).checked_mul(height as usize)
+				                          .filter(|len| {
+					                          len.checked_mul(4)
+					                             .is_some_and(|n| n <= self.data.len() - self.pos)
+				                          })
+				                          .ok_or(ParseError::Truncated { offset: self.pos })?;
+				let gids = (0..len).map(|_| self.u32()).collect::<Result<_, _>>()?;
+				LayerKind::Tiles { width, height, gids }
+			},
+			1 => {
+				LayerKind::Objects((0..self.u32()?).map(|_| self.object())
+				                                   .collect::<Result<_, _>>()?)
+			},
+			_ => return Err(ParseError::Invalid { offset }),
+		};
+		Ok(Layer { name,
+		           visible,
+		           offset: position,
+		           properties,
+		           kind })
+	}
+
+	fn object(&mut self) -> Result<Object, ParseError> {
+		let id = self.u32()?;
+		let name = self.str()?;
+		let class = self.str()?;
+		let rect = Rect::new(self.i32()?, self.i32()?, self.i32()?, self.i32()?);
+		let gid = self.u32()?;
+		let offset = self.pos;
+		let shape = match self.u8()? {
+			0 => Shape::Rect,
+			1 => Shape::Ellipse,
+			2 => Shape::Point,
+			3 => Shape::Polygon,
+			4 => Shape::Polyline,
+			_ => return Err(ParseError::Invalid { offset }),
+		};
+		let points = (0..self.u32()?).map(|_| self.point()).collect::<Result<_, _>>()?;
+		Ok(Object { id,
+		            name,
+		            class,
+		            rect,
+		            gid,
+		            shape,
+		            points,
+		            properties: self.props()? })
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+
+	/// Map of 3x2 tiles 8x8 with two tilesets, a tile layer and an object group.
+	fn data() -> Vec<u8> {
+		fn str(out: &mut Vec<u8>, s: &str) {
+			out.extend_from_slice(&(s.len() as u16).to_le_bytes());
+			out.extend_from_slice(s.as_bytes());
+		}
+		let u16 = |out: &mut Vec<u8>, v: u16| out.extend_from_slice(&v.to_le_bytes());
+		let u32 = |out: &mut Vec<u8>, v: u32| out.extend_from_slice(&v.to_le_bytes());
+
+		let mut out = b"PDTM\x01".to_vec();
+		[3, 2].into_iter().for_each(|v| u32(&mut out, v));
+		[8, 8].into_iter().for_each(|v| u16(&mut out, v));
+		u16(&mut out, 1);
+		str(&mut out, "music");
+		out.push(5);
+		str(&mut out, "sfx/theme");
+
+		u16(&mut out, 2);
+		// tileset "walls", gids 1..=4, tile 1 is solid, tile 2 has a shape:
+		str(&mut out, "walls");
+		[1, 4, 2].into_iter().for_each(|v| u32(&mut out, v));
+		[8, 8].into_iter().for_each(|v| u16(&mut out, v));
+		u32(&mut out, 2);
+		u32(&mut out, 1);
+		u16(&mut out, 1);
+		[0, 0, 8, 8].into_iter().for_each(|v| u16(&mut out, v));
+		u32(&mut out, 2);
+		u16(&mut out, 1);
+		[0, 6, 8, 2].into_iter().for_each(|v| u16(&mut out, v));
+		// tileset "props", gids 5..=6:
+		str(&mut out, "props");
+		[5, 2, 2].into_iter().for_each(|v| u32(&mut out, v));
+		[8, 8].into_iter().for_each(|v| u16(&mut out, v));
+		u32(&mut out, 0);
+
+		u16(&mut out, 2);
+		out.push(0);
+		str(&mut out, "ground");
+		out.push(1);
+		[0, 4].into_iter().for_each(|v| u32(&mut out, v));
+		u16(&mut out, 0);
+		[3, 2].into_iter().for_each(|v| u32(&mut out, v));
+		[2, 2, 6, 0, 0x4000_0003, 0].into_iter()
+		                            .for_each(|v| u32(&mut out, v));
+
+		out.push(1);
+		str(&mut out, "spawns");
+		out.push(0);
+		[0, 0].into_iter().for_each(|v| u32(&mut out, v));
+		u16(&mut out, 0);
+		u32(&mut out, 1);
+		u32(&mut out, 7);
+		str(&mut out, "hero");
+		str(&mut out, "player");
+		[10, 20, 0, 0, 0].into_iter().for_each(|v| u32(&mut out, v));
+		out.push(2);
+		u32(&mut out, 0);
+		u16(&mut out, 1);
+		str(&mut out, "hp");
+		out.push(1);
+		u32(&mut out, -3_i32 as u32);
+		out
+	}
+
+
+	#[test]
+	fn parse() {
+		let map = TiledMap::parse(&data()).unwrap();
+		assert_eq!(map.size(), Size::new(24, 16));
+		assert_eq!(
+		           map.properties.get("music").and_then(|v| v.as_str()),
+		           Some("sfx/theme")
+		);
+		assert_eq!(map.tilesets[0].collision(2), [Rect::new(0, 6, 8, 2)]);
+		assert!(map.tilesets[0].collision(3).is_empty());
+		assert_eq!(map.tileset_of(0x8000_0006), Some(1));
+		assert_eq!(map.tileset_of(7), None);
+
+		let spawns = map.object_group("spawns").unwrap();
+		assert_eq!(
+		           (spawns[0].class.as_str(), spawns[0].shape),
+		           ("player", Shape::Point)
+		);
+		assert_eq!(spawns[0].rect, Rect::new(10, 20, 0, 0));
+		assert_eq!(spawns[0].properties.get("hp").and_then(|v| v.as_int()), Some(-3));
+		assert!(!map.layer("spawns").unwrap().visible);
+
+		let data = data();
+		assert_eq!(
+		           TiledMap::parse(&data[..data.len() - 1]),
+		           Err(ParseError::Truncated { offset: data.len() - 4 })
+		);
+
+		let mut data = self::data();
+		data[5..9].copy_from_slice(&(c_int::MAX as u32).to_le_bytes());
+		assert_eq!(TiledMap::parse(&data).unwrap().size(), Size::new(c_int::MAX, 16));
+		// Ground layer of `c_int::MAX` by `c_int::MAX` tiles:
+		let width = data.windows(6).position(|w| w == b"ground").unwrap() + 6 + 1 + 8 + 2;
+		let huge = (c_int::MAX as u32).to_le_bytes();
+		data[width..width + 4].copy_from_slice(&huge);
+		data[width + 4..width + 8].copy_from_slice(&huge);
+		assert_eq!(
+		           TiledMap::parse(&data),
+		           Err(ParseError::Truncated { offset: width + 8 })
+		);
+		assert_eq!(TiledMap::parse(b"PDTM\x02"), Err(ParseError::Version(2)));
+		assert_eq!(TiledMap::parse(b"PNG"), Err(ParseError::Magic));
+	}
+
+	#[test]
+	fn layers() {
+		let map = TiledMap::parse(&data()).unwrap();
+		let ground = map.layer("ground").unwrap();
+
+		let walls = map.tile_layer(ground, 0).unwrap();
+		assert_eq!(walls.offset, Point::new(0, 4));
+		assert_eq!(walls.tiles()[..3], [Tile::new(1), Tile::new(1), Tile::EMPTY]);
+		assert_eq!(walls.get(1, 1), Some(Tile::new(2).flipped(false, true)));
+		let props = map.tile_layer(ground, 1).unwrap();
+		assert_eq!(props.get(2, 0), Some(Tile::new(1)));
+		assert!(map.tile_layer(map.layer("spawns").unwrap(), 0).is_none());
+
+		let rects = map.collision_rects(ground);
+		let rects: Vec<_> = rects.into_iter().map(Rect::from).collect();
+		assert_eq!(rects, vec![Rect::new(0, 4, 16, 8), Rect::new(8, 12, 8, 2)]);
+	}
+}
@@ -1,13 +1,15 @@
 //! Playdate video API
 
 use core::ffi::{c_char, c_int, c_float};
+use core::time::Duration;
 
 use sys::ffi::LCDVideoPlayer;
 use sys::ffi::{CString, CStr};
 use fs::Path;
 
 use crate::Graphics;
-use crate::bitmap::{AnyBitmap, BitmapRef};
+use crate::bitmap::{AnyBitmap, BitmapFlip, BitmapRef};
+use crate::bitmap::animation::LoopMode;
 use crate::error::ApiError;
 use crate::error::Error;
 
@@ -211,6 +213,22 @@ impl<Api: api::Api, const FOD: bool> VideoPlayer<Api, FOD> {
 		};
 	}
 
+	/// Retrieves size, frame rate and number of frames of the video.
+	///
+	/// Calls [`sys::ffi::playdate_video::getInfo`].
+	#[doc(alias = "sys::ffi::playdate_video::getInfo")]
+	pub fn video_info(&self) -> VideoInfo { VideoInfo::from(self.info()) }
+
+	/// Returns index of the current frame, last rendered one.
+	///
+	/// Calls [`sys::ffi::playdate_video::getInfo`].
+	#[doc(alias = "sys::ffi::playdate_video::getInfo")]
+	pub fn current_frame(&self) -> c_int {
+		let mut current = 0;
+		self.info_raw(None, None, None, None, Some(&mut current));
+		current
+	}
+
 	/// Retrieves information about the video, by passing optional mutable references.
 	///
 	/// Example:
@@ -286,6 +304,316 @@ pub struct VideoPlayerOutInfo {
 }
 
 
+/// Size, frame rate and number of frames of the video.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct VideoInfo {
+	pub width: c_int,
+	pub height: c_int,
+	/// Frames per second.
+	pub frame_rate: c_float,
+	pub frame_count: c_int,
+}
+
+impl From<VideoPlayerOutInfo> for VideoInfo {
+	fn from(info: VideoPlayerOutInfo) -> Self {
+		Self { width: info.width,
+		       height: info.height,
+		       frame_rate: info.frame_rate,
+		       frame_count: info.frame_count }
+	}
+}
+
+impl VideoInfo {
+	/// Returns duration of the video, zero if frame rate is unknown.
+	pub fn duration(&self) -> Duration { self.frame_time(self.frame_count) }
+
+	/// Returns time when the frame `n` starts.
+	pub fn frame_time(&self, n: c_int) -> Duration {
+		if self.frame_rate > 0.0 {
+			Duration::from_secs_f64(n.max(0) as f64 / self.frame_rate as f64)
+		} else {
+			Duration::ZERO
+		}
+	}
+
+	/// Returns number of the frame at `time` from the start of endless video,
+	/// without wrapping by the number of frames.
+	pub fn frame_at(&self, time: Duration) -> u64 {
+		if self.frame_rate > 0.0 {
+			(time.as_secs_f64() * self.frame_rate as f64) as u64
+		} else {
+			0
+		}
+	}
+
+	/// Returns index of the frame at `time` played in the `mode`,
+	/// and `true` if the video in [`LoopMode::Once`] is over.
+	pub fn frame_at_with(&self, time: Duration, mode: LoopMode) -> (c_int, bool) {
+		let count = self.frame_count.max(0) as u64;
+		if count == 0 {
+			return (0, true);
+		}
+		let n = self.frame_at(time);
+		let (frame, finished) = match mode {
+			LoopMode::Loop => (n % count, false),
+			LoopMode::Once => (n.min(count - 1), n >= count),
+			LoopMode::PingPong => {
+				let period = (count - 1) * 2;
+				let n = n.checked_rem(period).unwrap_or(0);
+				(if n < count { n } else { period - n }, false)
+			},
+		};
+		(frame as c_int, finished)
+	}
+}
+
+
+/// Playback of the video, rendering frames by time.
+///
+/// Frames are rendered into the player's context, set by [`VideoPlayback::set_context`],
+/// or directly to the display after [`VideoPlayback::use_screen_context`].
+///
+/// Playback is driven by [`advance`](VideoPlayback::advance) with time passed since the last call,
+/// by [`update`](VideoPlayback::update) with the current time,
+/// or by [`sync`](VideoPlayback::sync) with offset of the paired soundtrack.
+///
+/// ```ignore
+/// let mut playback = VideoPlayback::new(VideoPlayer::load("video.pdv")?);
+/// playback.use_screen_context();
+/// soundtrack.play(1)?;
+///
+/// // in the update callback:
+/// playback.sync(soundtrack.offset())?;
+/// ```
+pub struct VideoPlayback<Api: api::Api = api::Default, const FOD: bool = true> {
+	player: VideoPlayer<Api, FOD>,
+	info: VideoInfo,
+	mode: LoopMode,
+	elapsed: Duration,
+	/// Time of the last [`update`](VideoPlayback::update).
+	last: Option<Duration>,
+	/// Last rendered frame.
+	frame: Option<c_int>,
+	/// Frames are rendered directly to the display.
+	screen: bool,
+	paused: bool,
+	finished: bool,
+}
+
+
+// ctor //
+
+impl<Api: api::Api, const FOD: bool> VideoPlayback<Api, FOD> {
+	/// Creates playback of the video from its first frame, looping by default.
+	///
+	/// Calls [`sys::ffi::playdate_video::getInfo`].
+	#[doc(alias = "sys::ffi::playdate_video::getInfo")]
+	pub fn new(player: VideoPlayer<Api, FOD>) -> Self {
+		let info = player.video_info();
+		Self { player,
+		       info,
+		       mode: LoopMode::Loop,
+		       elapsed: Duration::ZERO,
+		       last: None,
+		       frame: None,
+		       screen: false,
+		       paused: false,
+		       finished: false }
+	}
+
+	/// Sets what happens at the end of the video.
+	pub fn with_mode(mut self, mode: LoopMode) -> Self {
+		self.mode = mode;
+		self
+	}
+
+	pub fn into_player(self) -> VideoPlayer<Api, FOD> { self.player }
+}
+
+
+// utils //
+
+impl<Api: api::Api, const FOD: bool> VideoPlayback<Api, FOD> {
+	pub fn player(&self) -> &VideoPlayer<Api, FOD> { &self.player }
+	pub const fn info(&self) -> &VideoInfo { &self.info }
+
+	pub const fn mode(&self) -> LoopMode { self.mode }
+	pub fn set_mode(&mut self, mode: LoopMode) { self.mode = mode }
+
+	/// Returns time from the start of the playback, excluding pauses.
+	pub const fn elapsed(&self) -> Duration { self.elapsed }
+
+	/// Returns index of the frame rendered last.
+	pub const fn frame(&self) -> Option<c_int> { self.frame }
+
+	/// Returns `true` if frames are rendered directly to the display.
+	pub const fn is_screen_context(&self) -> bool { self.screen }
+
+	pub const fn is_paused(&self) -> bool { self.paused }
+
+	/// Returns `true` if the playback in [`LoopMode::Once`] reached the end of the video.
+	pub const fn is_finished(&self) -> bool { self.finished }
+}
+
+
+// impl //
+
+impl<Api: api::Api, const FOD: bool> VideoPlayback<Api, FOD> {
+	/// Pauses the playback, time passed to [`advance`](VideoPlayback::advance) is ignored.
+	pub fn pause(&mut self) { self.paused = true }
+
+	/// Resumes the paused playback.
+	///
+	/// Time between the pause and the next [`update`](VideoPlayback::update) is skipped.
+	pub fn resume(&mut self) {
+		self.paused = false;
+		self.last = None;
+	}
+
+	/// Moves the playback to the `time` from the start, the frame is rendered by the next update.
+	pub fn seek(&mut self, time: Duration) {
+		self.elapsed = time;
+		self.finished = false;
+	}
+
+	/// Starts the playback over.
+	pub fn reset(&mut self) { self.seek(Duration::ZERO) }
+
+	/// Sets the `bitmap` as the rendering destination, see [`VideoPlayer::set_context`].
+	pub fn set_context<'a, 'b: 'a>(&'a mut self, bitmap: &'b impl AnyBitmap) -> Result<(), Error> {
+		self.player.set_context(bitmap)?;
+		self.screen = false;
+		Ok(())
+	}
+
+	/// Renders frames directly to the display, see [`VideoPlayer::use_screen_context`].
+	pub fn use_screen_context(&mut self) {
+		self.player.use_screen_context();
+		self.screen = true;
+	}
+
+	/// Advances the playback to the current time `now`,
+	/// by time passed since the previous call.
+	///
+	/// The first call only remembers the time and renders the current frame.
+	///
+	/// Returns `true` if new frame was rendered.
+	pub fn update(&mut self, now: Duration) -> Result<bool, Error> {
+		let dt = self.last.map_or(Duration::ZERO, |last| now.saturating_sub(last));
+		self.last = Some(now);
+		self.advance(dt)
+	}
+
+	/// Advances the playback by `dt` and renders the frame at new time if it changed.
+	///
+	/// Returns `true` if new frame was rendered.
+	pub fn advance(&mut self, dt: Duration) -> Result<bool, Error> {
+		if !self.paused && !self.finished {
+			self.elapsed += dt;
+		}
+		self.render_if_changed()
+	}
+
+	/// Moves the playback to the `offset` in seconds of the paired soundtrack,
+	/// e.g. [`fp::Player::offset`](https://docs.rs/playdate-sound),
+	/// and renders the frame at the offset if it changed.
+	///
+	/// Pause is ignored, the soundtrack leads the playback.
+	///
+	/// Returns `true` if new frame was rendered.
+	pub fn sync(&mut self, offset: c_float) -> Result<bool, Error> {
+		self.seek(Duration::from_secs_f32(offset.max(0.0)));
+		self.render_if_changed()
+	}
+
+	fn render_if_changed(&mut self) -> Result<bool, Error> {
+		let (frame, finished) = self.info.frame_at_with(self.elapsed, self.mode);
+		self.finished = finished;
+		if self.frame == Some(frame) {
+			return Ok(false);
+		}
+		self.render_frame(frame)?;
+		Ok(true)
+	}
+
+	/// Renders the frame `n` into the player's context, regardless of the time.
+	///
+	/// Calls [`sys::ffi::playdate_video::renderFrame`].
+	#[doc(alias = "sys::ffi::playdate_video::renderFrame")]
+	pub fn render_frame(&mut self, n: c_int) -> Result<(), Error> {
+		self.player.render_frame(n)?;
+		self.frame = Some(n);
+		Ok(())
+	}
+
+	/// Draws the player's context bitmap with its upper-left corner at `x`, `y`.
+	///
+	/// Does nothing if frames are rendered directly to the display
+	/// after [`VideoPlayback::use_screen_context`].
+	///
+	/// Calls [`sys::ffi::playdate_video::getContext`] and [`sys::ffi::playdate_graphics::drawBitmap`].
+	#[doc(alias = "sys::ffi::playdate_graphics::drawBitmap")]
+	pub fn draw(&self, x: c_int, y: c_int) -> Result<(), Error> {
+		if self.screen {
+			return Ok(());
+		}
+		let context = self.player.get_context()?;
+		Graphics::Default().draw(&context, x, y, BitmapFlip::kBitmapUnflipped);
+		Ok(())
+	}
+}
+
+impl<Api: api::Api, const FOD: bool> core::fmt::Debug for VideoPlayback<Api, FOD> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("VideoPlayback")
+		 .field("info", &self.info)
+		 .field("mode", &self.mode)
+		 .field("elapsed", &self.elapsed)
+		 .field("frame", &self.frame)
+		 .field("screen", &self.screen)
+		 .field("paused", &self.paused)
+		 .field("finished", &self.finished)
+		 .finish()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	fn frames_by_time() {
+		let info = VideoInfo { width: 400,
+		                       height: 240,
+		                       frame_rate: 10.0,
+		                       frame_count: 4 };
+		assert_eq!(info.duration(), Duration::from_millis(400));
+		let at = |ms, mode| info.frame_at_with(Duration::from_millis(ms), mode);
+
+		assert_eq!(at(0, LoopMode::Loop), (0, false));
+		assert_eq!(at(250, LoopMode::Loop), (2, false));
+		assert_eq!(at(450, LoopMode::Loop), (0, false));
+		assert_eq!(at(399, LoopMode::Once), (3, false));
+		assert_eq!(at(400, LoopMode::Once), (3, true));
+		let ping_pong: Vec<_> = (0..8).map(|i| at(i * 100, LoopMode::PingPong).0).collect();
+		assert_eq!(ping_pong, [0, 1, 2, 3, 2, 1, 0, 1]);
+
+		let single = VideoInfo { frame_count: 1,
+		                         ..info };
+		assert_eq!(
+		           single.frame_at_with(Duration::from_secs(1), LoopMode::PingPong),
+		           (0, false)
+		);
+		assert_eq!(
+		           VideoInfo::default().frame_at_with(Duration::ZERO, LoopMode::Loop),
+		           (0, true)
+		);
+	}
+}
+
+
 pub mod api {
 	use core::ffi::c_char;
 	use core::ffi::c_float;
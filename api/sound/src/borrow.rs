@@ -0,0 +1,47 @@
+//! Borrowed wrappers of the objects owned by other objects
+
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::SoundSource as OpaqueSoundSource;
+use sys::traits::AsRaw;
+
+use crate::signal::Signal;
+use crate::source::AsSoundSource;
+
+
+/// Non-owning wrapper `T` of the object owned by the `'owner`,
+/// e.g. [`Envelope`](crate::envelope::Envelope) of the [`Synth`](crate::synth::Synth)
+/// or [`Track`](crate::track::Track) of the [`Sequence`](crate::sequence::Sequence).
+///
+/// Can't outlive its owner and gives only shared access to the wrapper.
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Ref<'owner, T>(T, PhantomData<&'owner ()>);
+
+impl<T> Ref<'_, T> {
+	/// Wraps `inner` borrowed from the `'owner`.
+	///
+	/// # Safety
+	/// `inner` must not free the object on drop
+	/// and the object must be valid while the `'owner` is borrowed.
+	pub(crate) unsafe fn new(inner: T) -> Self { Self(inner, PhantomData) }
+}
+
+impl<T> Deref for Ref<'_, T> {
+	type Target = T;
+	fn deref(&self) -> &T { &self.0 }
+}
+
+impl<T: AsRaw> AsRaw for Ref<'_, T> {
+	type Type = T::Type;
+	unsafe fn as_raw(&self) -> *mut T::Type { self.0.as_raw() }
+}
+
+impl<T: Signal> Signal for Ref<'_, T> {
+	unsafe fn as_signal_raw(&self) -> *mut PDSynthSignalValue { self.0.as_signal_raw() }
+}
+
+impl<T: AsSoundSource> AsSoundSource for Ref<'_, T> {
+	unsafe fn as_source_raw(&self) -> *mut OpaqueSoundSource { self.0.as_source_raw() }
+}
@@ -0,0 +1,282 @@
+//! Playdate sound channel API
+
+use core::ffi::c_float;
+use core::ffi::c_void;
+
+use sys::ffi::AudioSourceFunction;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::SoundChannel;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+use crate::effect::SoundEffect;
+use crate::source::AsSoundSource;
+use crate::source::SoundSource;
+
+pub mod api;
+
+
+/// Sound channel, a bus that mixes sound sources with shared volume, pan and effects.
+///
+/// Owned channel (`FREE_ON_DROP = true`) is removed from the sound engine and freed on drop,
+/// the [default channel](crate::Sound::default_channel) is not owned and will not be freed.
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Channel<Api: api::Api = api::Default, const FREE_ON_DROP: bool = true>(*mut SoundChannel, Api);
+
+impl<Api: api::Api, const FOD: bool> AsRaw for Channel<Api, FOD> {
+	type Type = SoundChannel;
+	unsafe fn as_raw(&self) -> *mut SoundChannel { self.0 }
+}
+
+impl<Api: api::Api + Default, const FOD: bool> From<*mut SoundChannel> for Channel<Api, FOD> {
+	fn from(ptr: *mut SoundChannel) -> Self { Self(ptr, Api::default()) }
+}
+
+impl<Api: api::Api, const FOD: bool> Channel<Api, FOD> {
+	/// Wraps the raw `ptr` to the channel with given `api`.
+	///
+	/// # Safety
+	/// `ptr` must point to a valid channel.
+	/// If `FREE_ON_DROP` is `true`, the wrapper takes ownership of it and frees it on drop,
+	/// otherwise the channel must outlive the wrapper.
+	pub unsafe fn from_with(api: Api, ptr: *mut SoundChannel) -> Self { Self(ptr, api) }
+}
+
+
+// ctor //
+
+impl<Api> Channel<Api, true> where Api: api::Api {
+	/// Creates a new channel.
+	///
+	/// To hear it, the channel should be added to the sound engine with [`Sound::add_channel`](crate::Sound::add_channel).
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::newChannel`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::newChannel")]
+	pub fn new() -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new channel with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::newChannel`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::newChannel")]
+	pub fn new_with(api: Api) -> Result<Self, Error> {
+		let f = api.new_channel();
+		let channel = unsafe { f() };
+		if channel.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(Channel(channel, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api, const FOD: bool> Drop for Channel<Api, FOD> {
+	fn drop(&mut self) {
+		if FOD && !self.0.is_null() {
+			let f = self.api().remove_channel();
+			unsafe { f(self.0) };
+
+			let f = self.api().free_channel();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api, const FOD: bool> Channel<Api, FOD> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api, const FOD: bool> Channel<Api, FOD> {
+	/// Adds the given `source` (player, synth, etc.) to the channel.
+	///
+	/// If a source is not assigned to a channel, it plays on the default global channel.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::addSource`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::addSource")]
+	pub fn add_source<S: AsSoundSource>(&self, source: &S) -> bool {
+		let f = self.api().add_source();
+		unsafe { f(self.0, source.as_source_raw()) == 1 }
+	}
+
+	/// Removes the given `source` from the channel.
+	///
+	/// Returns `true` if the source was found in (and removed from) the channel.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::removeSource`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::removeSource")]
+	pub fn remove_source<S: AsSoundSource>(&self, source: &S) -> bool {
+		let f = self.api().remove_source();
+		unsafe { f(self.0, source.as_source_raw()) == 1 }
+	}
+
+	/// Creates a new [`SoundSource`] using the given data provider `callback` and adds it to the channel.
+	///
+	/// See also [`Sound::add_source_raw`](crate::Sound::add_source_raw).
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::addCallbackSource`]
+	///
+	/// # Safety
+	/// `context` is passed to the `callback` on the audio thread as is,
+	/// so it must stay valid while the source is in the channel.
+	#[doc(alias = "sys::ffi::playdate_sound_channel::addCallbackSource")]
+	pub unsafe fn add_callback_source_raw(&self,
+	                                      callback: AudioSourceFunction,
+	                                      context: *mut c_void,
+	                                      stereo: bool)
+	                                      -> SoundSource {
+		let f = self.api().add_callback_source();
+		f(self.0, callback, context, stereo.into()).into()
+	}
+
+
+	/// Adds the given `effect` to the channel.
+	///
+	/// The effect should outlive its presence in the channel.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::addEffect`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::addEffect")]
+	pub fn add_effect<E: SoundEffect>(&self, effect: &E) {
+		let f = self.api().add_effect();
+		unsafe { f(self.0, effect.as_effect_raw()) }
+	}
+
+	/// Removes the given `effect` from the channel.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::removeEffect`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::removeEffect")]
+	pub fn remove_effect<E: SoundEffect>(&self, effect: &E) {
+		let f = self.api().remove_effect();
+		unsafe { f(self.0, effect.as_effect_raw()) }
+	}
+
+
+	/// Gets the volume for the channel, in the range `0.0` - `1.0`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::getVolume`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::getVolume")]
+	pub fn volume(&self) -> c_float {
+		let f = self.api().get_volume();
+		unsafe { f(self.0) }
+	}
+
+	/// Sets the volume for the channel, in the range `0.0` - `1.0`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::setVolume`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::setVolume")]
+	pub fn set_volume(&self, volume: c_float) {
+		let f = self.api().set_volume();
+		unsafe { f(self.0, volume) }
+	}
+
+	/// Sets a `signal` to modulate the channel volume.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_volume_modulator_raw`](Self::set_volume_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the channel is freed.
+	pub unsafe fn set_volume_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_volume_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the channel volume.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::setVolumeModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_channel::setVolumeModulator")]
+	pub unsafe fn set_volume_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_volume_modulator();
+		f(self.0, signal)
+	}
+
+	/// Gets a signal modulating the channel volume.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::getVolumeModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::getVolumeModulator")]
+	pub fn volume_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_volume_modulator();
+		unsafe { f(self.0) }
+	}
+
+	/// Sets the pan parameter for the channel.
+	///
+	/// Valid values are in the range `-1.0` - `1.0`, where `-1.0` is left, `0.0` is center, and `1.0` is right.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::setPan`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::setPan")]
+	pub fn set_pan(&self, pan: c_float) {
+		let f = self.api().set_pan();
+		unsafe { f(self.0, pan) }
+	}
+
+	/// Sets a `signal` to modulate the channel pan.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_pan_modulator_raw`](Self::set_pan_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the channel is freed.
+	pub unsafe fn set_pan_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_pan_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the channel pan.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::setPanModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_channel::setPanModulator")]
+	pub unsafe fn set_pan_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_pan_modulator();
+		f(self.0, signal)
+	}
+
+	/// Gets a signal modulating the channel pan.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::getPanModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::getPanModulator")]
+	pub fn pan_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_pan_modulator();
+		unsafe { f(self.0) }
+	}
+
+	/// Returns a signal that follows the volume of the channel before effects are applied.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::getDryLevelSignal`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::getDryLevelSignal")]
+	pub fn dry_level_signal_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_dry_level_signal();
+		unsafe { f(self.0) }
+	}
+
+	/// Returns a signal that follows the volume of the channel after effects are applied.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_channel::getWetLevelSignal`]
+	#[doc(alias = "sys::ffi::playdate_sound_channel::getWetLevelSignal")]
+	pub fn wet_level_signal_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_wet_level_signal();
+		unsafe { f(self.0) }
+	}
+}
@@ -0,0 +1,98 @@
+use core::ffi::c_float;
+use core::ffi::c_int;
+use core::ptr::NonNull;
+
+use sys::ffi::ControlSignal;
+use sys::ffi::playdate_control_signal;
+
+
+/// Default control signal api end-point, ZST.
+///
+/// All calls approximately costs ~4 derefs.
+#[derive(Debug, Clone, Copy, core::default::Default)]
+pub struct Default;
+impl Api for Default {}
+
+
+/// Cached control signal api end-point.
+///
+/// Stores one reference, so size on stack is eq `usize`.
+///
+/// All calls approximately costs ~1 deref.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Cache(&'static playdate_control_signal);
+
+impl core::default::Default for Cache {
+	fn default() -> Self { Self(sys::api!(sound.controlsignal)) }
+}
+
+impl From<*const playdate_control_signal> for Cache {
+	#[inline(always)]
+	fn from(ptr: *const playdate_control_signal) -> Self { Self(unsafe { ptr.as_ref() }.expect("controlsignal")) }
+}
+
+impl From<&'static playdate_control_signal> for Cache {
+	#[inline(always)]
+	fn from(r: &'static playdate_control_signal) -> Self { Self(r) }
+}
+
+impl From<NonNull<playdate_control_signal>> for Cache {
+	#[inline(always)]
+	fn from(ptr: NonNull<playdate_control_signal>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+impl From<&'_ NonNull<playdate_control_signal>> for Cache {
+	#[inline(always)]
+	fn from(ptr: &NonNull<playdate_control_signal>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+
+impl Api for Cache {
+	fn new_signal(&self) -> FnNewSignal { self.0.newSignal.expect("newSignal") }
+	fn free_signal(&self) -> FnFreeSignal { self.0.freeSignal.expect("freeSignal") }
+	fn clear_events(&self) -> FnClearEvents { self.0.clearEvents.expect("clearEvents") }
+	fn add_event(&self) -> FnAddEvent { self.0.addEvent.expect("addEvent") }
+	fn remove_event(&self) -> FnRemoveEvent { self.0.removeEvent.expect("removeEvent") }
+	fn get_midi_controller_number(&self) -> FnGetMidiControllerNumber {
+		self.0.getMIDIControllerNumber.expect("getMIDIControllerNumber")
+	}
+}
+
+
+pub trait Api {
+	/// Returns [`sys::ffi::playdate_control_signal::newSignal`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::newSignal")]
+	fn new_signal(&self) -> FnNewSignal { *sys::api!(sound.controlsignal.newSignal) }
+
+	/// Returns [`sys::ffi::playdate_control_signal::freeSignal`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::freeSignal")]
+	fn free_signal(&self) -> FnFreeSignal { *sys::api!(sound.controlsignal.freeSignal) }
+
+	/// Returns [`sys::ffi::playdate_control_signal::clearEvents`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::clearEvents")]
+	fn clear_events(&self) -> FnClearEvents { *sys::api!(sound.controlsignal.clearEvents) }
+
+	/// Returns [`sys::ffi::playdate_control_signal::addEvent`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::addEvent")]
+	fn add_event(&self) -> FnAddEvent { *sys::api!(sound.controlsignal.addEvent) }
+
+	/// Returns [`sys::ffi::playdate_control_signal::removeEvent`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::removeEvent")]
+	fn remove_event(&self) -> FnRemoveEvent { *sys::api!(sound.controlsignal.removeEvent) }
+
+	/// Returns [`sys::ffi::playdate_control_signal::getMIDIControllerNumber`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::getMIDIControllerNumber")]
+	fn get_midi_controller_number(&self) -> FnGetMidiControllerNumber {
+		*sys::api!(sound.controlsignal.getMIDIControllerNumber)
+	}
+}
+
+
+type FnNewSignal = unsafe extern "C" fn() -> *mut ControlSignal;
+type FnFreeSignal = unsafe extern "C" fn(signal: *mut ControlSignal);
+type FnClearEvents = unsafe extern "C" fn(signal: *mut ControlSignal);
+type FnAddEvent =
+	unsafe extern "C" fn(signal: *mut ControlSignal, step: c_int, value: c_float, interpolate: c_int);
+type FnRemoveEvent = unsafe extern "C" fn(signal: *mut ControlSignal, step: c_int);
+type FnGetMidiControllerNumber = unsafe extern "C" fn(signal: *mut ControlSignal) -> c_int;
@@ -0,0 +1,138 @@
+//! Playdate control signal API
+
+use core::ffi::c_float;
+use core::ffi::c_int;
+
+use sys::ffi::ControlSignal as OpaqueControlSignal;
+use sys::ffi::PDSynthSignalValue;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+use crate::track::Step;
+
+pub mod api;
+
+
+/// Signal with a timeline of values at steps, such as MIDI controller events.
+///
+/// Owned signal (`FREE_ON_DROP = true`) frees the C-part on drop,
+/// signals borrowed from a [`Track`](crate::track::Track) are not owned and will not be freed.
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct ControlSignal<Api: api::Api = api::Default, const FREE_ON_DROP: bool = true>(*mut OpaqueControlSignal,
+                                                                                        Api);
+
+impl<Api: api::Api, const FOD: bool> AsRaw for ControlSignal<Api, FOD> {
+	type Type = OpaqueControlSignal;
+	unsafe fn as_raw(&self) -> *mut OpaqueControlSignal { self.0 }
+}
+
+impl<Api: api::Api, const FOD: bool> Signal for ControlSignal<Api, FOD> {
+	unsafe fn as_signal_raw(&self) -> *mut PDSynthSignalValue { self.0 as _ }
+}
+
+impl<Api: api::Api + Default, const FOD: bool> From<*mut OpaqueControlSignal> for ControlSignal<Api, FOD> {
+	fn from(ptr: *mut OpaqueControlSignal) -> Self { Self(ptr, Api::default()) }
+}
+
+impl<Api: api::Api, const FOD: bool> ControlSignal<Api, FOD> {
+	/// Wraps the raw `ptr` to the control signal with given `api`.
+	///
+	/// # Safety
+	/// `ptr` must point to a valid control signal.
+	/// If `FREE_ON_DROP` is `true`, the wrapper takes ownership of it and frees it on drop,
+	/// otherwise the control signal must outlive the wrapper.
+	pub unsafe fn from_with(api: Api, ptr: *mut OpaqueControlSignal) -> Self { Self(ptr, api) }
+}
+
+
+// ctor //
+
+impl<Api> ControlSignal<Api, true> where Api: api::Api {
+	/// Creates a new control signal.
+	///
+	/// Equivalent to [`sys::ffi::playdate_control_signal::newSignal`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::newSignal")]
+	pub fn new() -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new control signal with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_control_signal::newSignal`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::newSignal")]
+	pub fn new_with(api: Api) -> Result<Self, Error> {
+		let f = api.new_signal();
+		let signal = unsafe { f() };
+		if signal.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(ControlSignal(signal, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api, const FOD: bool> Drop for ControlSignal<Api, FOD> {
+	fn drop(&mut self) {
+		if FOD && !self.0.is_null() {
+			let f = self.api().free_signal();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api, const FOD: bool> ControlSignal<Api, FOD> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api, const FOD: bool> ControlSignal<Api, FOD> {
+	/// Adds a `value` to the signal's timeline at the given `step`.
+	///
+	/// If `interpolate` is set, the value is interpolated between the previous step+value and this one.
+	///
+	/// Equivalent to [`sys::ffi::playdate_control_signal::addEvent`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::addEvent")]
+	pub fn add_event(&self, step: Step, value: c_float, interpolate: bool) {
+		let f = self.api().add_event();
+		unsafe { f(self.0, step.0 as _, value, interpolate.into()) }
+	}
+
+	/// Removes the control event at the given `step`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_control_signal::removeEvent`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::removeEvent")]
+	pub fn remove_event(&self, step: Step) {
+		let f = self.api().remove_event();
+		unsafe { f(self.0, step.0 as _) }
+	}
+
+	/// Clears all events from the signal.
+	///
+	/// Equivalent to [`sys::ffi::playdate_control_signal::clearEvents`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::clearEvents")]
+	pub fn clear_events(&self) {
+		let f = self.api().clear_events();
+		unsafe { f(self.0) }
+	}
+
+	/// Returns the MIDI controller number for this signal,
+	/// if it was created from a MIDI file via [`Sequence::load_midi_file`](crate::sequence::Sequence::load_midi_file).
+	///
+	/// Equivalent to [`sys::ffi::playdate_control_signal::getMIDIControllerNumber`]
+	#[doc(alias = "sys::ffi::playdate_control_signal::getMIDIControllerNumber")]
+	pub fn midi_controller_number(&self) -> c_int {
+		let f = self.api().get_midi_controller_number();
+		unsafe { f(self.0) }
+	}
+}
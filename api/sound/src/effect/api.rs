@@ -0,0 +1,101 @@
+use core::ffi::c_float;
+use core::ffi::c_void;
+use core::ptr::NonNull;
+
+use sys::ffi::effectProc;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::SoundEffect;
+use sys::ffi::playdate_sound_effect;
+
+
+/// Default sound effect api end-point, ZST.
+///
+/// All calls approximately costs ~4 derefs.
+#[derive(Debug, Clone, Copy, core::default::Default)]
+pub struct Default;
+impl Api for Default {}
+
+
+/// Cached sound effect api end-point.
+///
+/// Stores one reference, so size on stack is eq `usize`.
+///
+/// All calls approximately costs ~1 deref.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Cache(&'static playdate_sound_effect);
+
+impl core::default::Default for Cache {
+	fn default() -> Self { Self(sys::api!(sound.effect)) }
+}
+
+impl From<*const playdate_sound_effect> for Cache {
+	#[inline(always)]
+	fn from(ptr: *const playdate_sound_effect) -> Self { Self(unsafe { ptr.as_ref() }.expect("effect")) }
+}
+
+impl From<&'static playdate_sound_effect> for Cache {
+	#[inline(always)]
+	fn from(r: &'static playdate_sound_effect) -> Self { Self(r) }
+}
+
+impl From<NonNull<playdate_sound_effect>> for Cache {
+	#[inline(always)]
+	fn from(ptr: NonNull<playdate_sound_effect>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+impl From<&'_ NonNull<playdate_sound_effect>> for Cache {
+	#[inline(always)]
+	fn from(ptr: &NonNull<playdate_sound_effect>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+
+impl Api for Cache {
+	fn new_effect(&self) -> FnNewEffect { self.0.newEffect.expect("newEffect") }
+	fn free_effect(&self) -> FnFreeEffect { self.0.freeEffect.expect("freeEffect") }
+	fn set_mix(&self) -> FnSetMix { self.0.setMix.expect("setMix") }
+	fn set_mix_modulator(&self) -> FnSetMixModulator { self.0.setMixModulator.expect("setMixModulator") }
+	fn get_mix_modulator(&self) -> FnGetMixModulator { self.0.getMixModulator.expect("getMixModulator") }
+	fn set_userdata(&self) -> FnSetUserdata { self.0.setUserdata.expect("setUserdata") }
+	fn get_userdata(&self) -> FnGetUserdata { self.0.getUserdata.expect("getUserdata") }
+}
+
+
+pub trait Api {
+	/// Returns [`sys::ffi::playdate_sound_effect::newEffect`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::newEffect")]
+	fn new_effect(&self) -> FnNewEffect { *sys::api!(sound.effect.newEffect) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect::freeEffect`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::freeEffect")]
+	fn free_effect(&self) -> FnFreeEffect { *sys::api!(sound.effect.freeEffect) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect::setMix`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::setMix")]
+	fn set_mix(&self) -> FnSetMix { *sys::api!(sound.effect.setMix) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect::setMixModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::setMixModulator")]
+	fn set_mix_modulator(&self) -> FnSetMixModulator { *sys::api!(sound.effect.setMixModulator) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect::getMixModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::getMixModulator")]
+	fn get_mix_modulator(&self) -> FnGetMixModulator { *sys::api!(sound.effect.getMixModulator) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect::setUserdata`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::setUserdata")]
+	fn set_userdata(&self) -> FnSetUserdata { *sys::api!(sound.effect.setUserdata) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect::getUserdata`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::getUserdata")]
+	fn get_userdata(&self) -> FnGetUserdata { *sys::api!(sound.effect.getUserdata) }
+}
+
+
+type FnNewEffect = unsafe extern "C" fn(proc_: effectProc, userdata: *mut c_void) -> *mut SoundEffect;
+type FnFreeEffect = unsafe extern "C" fn(effect: *mut SoundEffect);
+type FnSetMix = unsafe extern "C" fn(effect: *mut SoundEffect, level: c_float);
+type FnSetMixModulator = unsafe extern "C" fn(effect: *mut SoundEffect, signal: *mut PDSynthSignalValue);
+type FnGetMixModulator = unsafe extern "C" fn(effect: *mut SoundEffect) -> *mut PDSynthSignalValue;
+type FnSetUserdata = unsafe extern "C" fn(effect: *mut SoundEffect, userdata: *mut c_void);
+type FnGetUserdata = unsafe extern "C" fn(effect: *mut SoundEffect) -> *mut c_void;
@@ -0,0 +1,128 @@
+use core::ffi::c_float;
+use core::ptr::NonNull;
+
+use sys::ffi::BitCrusher;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::playdate_sound_effect_bitcrusher;
+
+
+/// Default bitcrusher effect api end-point, ZST.
+///
+/// All calls approximately costs ~4 derefs.
+#[derive(Debug, Clone, Copy, core::default::Default)]
+pub struct Default;
+impl Api for Default {}
+impl crate::effect::api::Api for Default {}
+
+
+/// Cached bitcrusher effect api end-point.
+///
+/// Stores one reference, so size on stack is eq `usize`.
+///
+/// All calls approximately costs ~1 deref.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Cache(&'static playdate_sound_effect_bitcrusher);
+
+impl core::default::Default for Cache {
+	fn default() -> Self { Self(sys::api!(sound.effect.bitcrusher)) }
+}
+
+impl From<*const playdate_sound_effect_bitcrusher> for Cache {
+	#[inline(always)]
+	fn from(ptr: *const playdate_sound_effect_bitcrusher) -> Self {
+		Self(unsafe { ptr.as_ref() }.expect("bitcrusher"))
+	}
+}
+
+impl From<&'static playdate_sound_effect_bitcrusher> for Cache {
+	#[inline(always)]
+	fn from(r: &'static playdate_sound_effect_bitcrusher) -> Self { Self(r) }
+}
+
+impl From<NonNull<playdate_sound_effect_bitcrusher>> for Cache {
+	#[inline(always)]
+	fn from(ptr: NonNull<playdate_sound_effect_bitcrusher>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+impl From<&'_ NonNull<playdate_sound_effect_bitcrusher>> for Cache {
+	#[inline(always)]
+	fn from(ptr: &NonNull<playdate_sound_effect_bitcrusher>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+
+/// Common effect functions are not cached and cost as [`crate::effect::api::Default`].
+impl crate::effect::api::Api for Cache {}
+
+impl Api for Cache {
+	fn new_bit_crusher(&self) -> FnNewBitCrusher { self.0.newBitCrusher.expect("newBitCrusher") }
+	fn free_bit_crusher(&self) -> FnFreeBitCrusher { self.0.freeBitCrusher.expect("freeBitCrusher") }
+	fn set_amount(&self) -> FnSetAmount { self.0.setAmount.expect("setAmount") }
+	fn set_amount_modulator(&self) -> FnSetAmountModulator {
+		self.0.setAmountModulator.expect("setAmountModulator")
+	}
+	fn get_amount_modulator(&self) -> FnGetAmountModulator {
+		self.0.getAmountModulator.expect("getAmountModulator")
+	}
+	fn set_undersampling(&self) -> FnSetUndersampling { self.0.setUndersampling.expect("setUndersampling") }
+	fn set_undersample_modulator(&self) -> FnSetUndersampleModulator {
+		self.0.setUndersampleModulator.expect("setUndersampleModulator")
+	}
+	fn get_undersample_modulator(&self) -> FnGetUndersampleModulator {
+		self.0.getUndersampleModulator.expect("getUndersampleModulator")
+	}
+}
+
+
+/// Effect-specific api, extends the common [effect api](crate::effect::api::Api).
+pub trait Api: crate::effect::api::Api {
+	/// Returns [`sys::ffi::playdate_sound_effect_bitcrusher::newBitCrusher`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::newBitCrusher")]
+	fn new_bit_crusher(&self) -> FnNewBitCrusher { *sys::api!(sound.effect.bitcrusher.newBitCrusher) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_bitcrusher::freeBitCrusher`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::freeBitCrusher")]
+	fn free_bit_crusher(&self) -> FnFreeBitCrusher { *sys::api!(sound.effect.bitcrusher.freeBitCrusher) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_bitcrusher::setAmount`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::setAmount")]
+	fn set_amount(&self) -> FnSetAmount { *sys::api!(sound.effect.bitcrusher.setAmount) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_bitcrusher::setAmountModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::setAmountModulator")]
+	fn set_amount_modulator(&self) -> FnSetAmountModulator {
+		*sys::api!(sound.effect.bitcrusher.setAmountModulator)
+	}
+
+	/// Returns [`sys::ffi::playdate_sound_effect_bitcrusher::getAmountModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::getAmountModulator")]
+	fn get_amount_modulator(&self) -> FnGetAmountModulator {
+		*sys::api!(sound.effect.bitcrusher.getAmountModulator)
+	}
+
+	/// Returns [`sys::ffi::playdate_sound_effect_bitcrusher::setUndersampling`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::setUndersampling")]
+	fn set_undersampling(&self) -> FnSetUndersampling { *sys::api!(sound.effect.bitcrusher.setUndersampling) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_bitcrusher::setUndersampleModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::setUndersampleModulator")]
+	fn set_undersample_modulator(&self) -> FnSetUndersampleModulator {
+		*sys::api!(sound.effect.bitcrusher.setUndersampleModulator)
+	}
+
+	/// Returns [`sys::ffi::playdate_sound_effect_bitcrusher::getUndersampleModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::getUndersampleModulator")]
+	fn get_undersample_modulator(&self) -> FnGetUndersampleModulator {
+		*sys::api!(sound.effect.bitcrusher.getUndersampleModulator)
+	}
+}
+
+
+type FnNewBitCrusher = unsafe extern "C" fn() -> *mut BitCrusher;
+type FnFreeBitCrusher = unsafe extern "C" fn(filter: *mut BitCrusher);
+type FnSetAmount = unsafe extern "C" fn(filter: *mut BitCrusher, amount: c_float);
+type FnSetAmountModulator = unsafe extern "C" fn(filter: *mut BitCrusher, signal: *mut PDSynthSignalValue);
+type FnGetAmountModulator = unsafe extern "C" fn(filter: *mut BitCrusher) -> *mut PDSynthSignalValue;
+type FnSetUndersampling = unsafe extern "C" fn(filter: *mut BitCrusher, undersampling: c_float);
+type FnSetUndersampleModulator = unsafe extern "C" fn(filter: *mut BitCrusher, signal: *mut PDSynthSignalValue);
+type FnGetUndersampleModulator = unsafe extern "C" fn(filter: *mut BitCrusher) -> *mut PDSynthSignalValue;
@@ -0,0 +1,175 @@
+//! Playdate bitcrusher effect API
+
+use core::ffi::c_float;
+
+use sys::ffi::BitCrusher as OpaqueBitCrusher;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::SoundEffect as OpaqueSoundEffect;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+use super::SoundEffect;
+
+pub mod api;
+
+
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct BitCrusher<Api: api::Api = api::Default>(*mut OpaqueBitCrusher, Api);
+
+impl<Api: api::Api> AsRaw for BitCrusher<Api> {
+	type Type = OpaqueBitCrusher;
+	unsafe fn as_raw(&self) -> *mut OpaqueBitCrusher { self.0 }
+}
+
+impl<Api: api::Api> SoundEffect for BitCrusher<Api> {
+	type Api = Api;
+
+	unsafe fn as_effect_raw(&self) -> *mut OpaqueSoundEffect { self.0 as _ }
+	fn effect_api(&self) -> &Api { &self.1 }
+}
+
+
+// ctor //
+
+impl<Api> BitCrusher<Api> where Api: api::Api {
+	/// Creates a new BitCrusher effect.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_bitcrusher::newBitCrusher`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::newBitCrusher")]
+	pub fn new() -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new BitCrusher effect with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_bitcrusher::newBitCrusher`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::newBitCrusher")]
+	pub fn new_with(api: Api) -> Result<Self, Error> {
+		let f = api.new_bit_crusher();
+		let ptr = unsafe { f() };
+		if ptr.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(BitCrusher(ptr, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api> Drop for BitCrusher<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			let f = self.api().free_bit_crusher();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api> BitCrusher<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api> BitCrusher<Api> {
+	/// Sets the amount of crushing to `amount`.
+	///
+	/// Valid values are `0.0` (no effect) to `1.0` (quantizing output to 1-bit).
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_bitcrusher::setAmount`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::setAmount")]
+	pub fn set_amount(&self, amount: c_float) {
+		let f = self.api().set_amount();
+		unsafe { f(self.0, amount) }
+	}
+
+	/// Sets a `signal` to modulate the crushing amount.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_amount_modulator_raw`](Self::set_amount_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the effect is freed.
+	pub unsafe fn set_amount_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_amount_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the crushing amount.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_bitcrusher::setAmountModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::setAmountModulator")]
+	pub unsafe fn set_amount_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_amount_modulator();
+		f(self.0, signal)
+	}
+
+	/// Returns the currently set amount modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_bitcrusher::getAmountModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::getAmountModulator")]
+	pub fn amount_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_amount_modulator();
+		unsafe { f(self.0) }
+	}
+
+	/// Sets the number of samples to repeat, quantizing the input in time.
+	///
+	/// A value of `0.0` produces no undersampling, `1.0` repeats every other sample, etc.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_bitcrusher::setUndersampling`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::setUndersampling")]
+	pub fn set_undersampling(&self, undersampling: c_float) {
+		let f = self.api().set_undersampling();
+		unsafe { f(self.0, undersampling) }
+	}
+
+	/// Sets a `signal` to modulate the undersampling amount.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_undersample_modulator_raw`](Self::set_undersample_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the effect is freed.
+	pub unsafe fn set_undersample_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_undersample_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the undersampling amount.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_bitcrusher::setUndersampleModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::setUndersampleModulator")]
+	pub unsafe fn set_undersample_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_undersample_modulator();
+		f(self.0, signal)
+	}
+
+	/// Returns the currently set undersampling modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_bitcrusher::getUndersampleModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_bitcrusher::getUndersampleModulator")]
+	pub fn undersample_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_undersample_modulator();
+		unsafe { f(self.0) }
+	}
+}
@@ -0,0 +1,224 @@
+//! Playdate delay line effect API
+
+use core::ffi::c_float;
+use core::ffi::c_int;
+use core::marker::PhantomData;
+
+use sys::ffi::DelayLine as OpaqueDelayLine;
+use sys::ffi::DelayLineTap as OpaqueDelayLineTap;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::SoundEffect as OpaqueSoundEffect;
+use sys::ffi::SoundSource as OpaqueSoundSource;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+use crate::source::AsSoundSource;
+use crate::source::SoundSource;
+use super::SoundEffect;
+
+pub mod api;
+
+
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct DelayLine<Api: api::Api = api::Default>(*mut OpaqueDelayLine, Api);
+
+impl<Api: api::Api> AsRaw for DelayLine<Api> {
+	type Type = OpaqueDelayLine;
+	unsafe fn as_raw(&self) -> *mut OpaqueDelayLine { self.0 }
+}
+
+impl<Api: api::Api> SoundEffect for DelayLine<Api> {
+	type Api = Api;
+
+	unsafe fn as_effect_raw(&self) -> *mut OpaqueSoundEffect { self.0 as _ }
+	fn effect_api(&self) -> &Api { &self.1 }
+}
+
+
+// ctor //
+
+impl<Api> DelayLine<Api> where Api: api::Api {
+	/// Creates a new delay line effect.
+	///
+	/// The `length` parameter is given in samples.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_delayline::newDelayLine`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_delayline::newDelayLine")]
+	pub fn new(length: c_int, stereo: bool) -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api, length, stereo)
+	}
+
+	/// Creates a new delay line effect with given `api`.
+	///
+	/// The `length` parameter is given in samples.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_delayline::newDelayLine`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_delayline::newDelayLine")]
+	pub fn new_with(api: Api, length: c_int, stereo: bool) -> Result<Self, Error> {
+		let f = api.new_delay_line();
+		let ptr = unsafe { f(length, stereo.into()) };
+		if ptr.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(DelayLine(ptr, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api> Drop for DelayLine<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			let f = self.api().free_delay_line();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api> DelayLine<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api> DelayLine<Api> {
+	/// Changes the length of the delay line, clearing its contents.
+	///
+	/// This function reallocates the audio buffer, so it is not safe to call while the delay line is in use.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_delayline::setLength`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_delayline::setLength")]
+	pub fn set_length(&mut self, frames: c_int) {
+		let f = self.api().set_length();
+		unsafe { f(self.0, frames) }
+	}
+
+	/// Sets the feedback level of the delay line.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_delayline::setFeedback`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_delayline::setFeedback")]
+	pub fn set_feedback(&self, feedback: c_float) {
+		let f = self.api().set_feedback();
+		unsafe { f(self.0, feedback) }
+	}
+
+	/// Returns a new tap on the delay line, at the given position.
+	///
+	/// `delay` must be less than or equal to the length of the delay line.
+	///
+	/// Returned tap is bound to the delay line and can't outlive it.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_delayline::addTap`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_delayline::addTap")]
+	pub fn add_tap(&self, delay: c_int) -> Result<Tap<'_, Api>, Error>
+		where Api: Clone {
+		let f = self.api().add_tap();
+		let ptr = unsafe { f(self.0, delay) };
+		if ptr.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(Tap(ptr, self.1.clone(), PhantomData))
+		}
+	}
+}
+
+
+/// Tap on the [`DelayLine`].
+///
+/// Tap is a sound source and can be added to a channel.
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Tap<'dl, Api: api::Api = api::Default>(*mut OpaqueDelayLineTap, Api, PhantomData<&'dl DelayLine<Api>>);
+
+impl<Api: api::Api> AsRaw for Tap<'_, Api> {
+	type Type = OpaqueDelayLineTap;
+	unsafe fn as_raw(&self) -> *mut OpaqueDelayLineTap { self.0 }
+}
+
+impl<Api: api::Api> AsSoundSource for Tap<'_, Api> {
+	unsafe fn as_source_raw(&self) -> *mut OpaqueSoundSource { self.0 as _ }
+}
+
+impl<Api: api::Api> Drop for Tap<'_, Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			let f = self.api().free_tap();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+impl<Api: api::Api> Tap<'_, Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+
+	/// Returns the tap as [`SoundSource`].
+	pub fn as_source(&self) -> SoundSource { SoundSource::from(self.0 as *mut OpaqueSoundSource) }
+
+
+	/// Sets the position of the tap on the delay line, up to the delay line's length.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_delayline::setTapDelay`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_delayline::setTapDelay")]
+	pub fn set_delay(&self, frames: c_int) {
+		let f = self.api().set_tap_delay();
+		unsafe { f(self.0, frames) }
+	}
+
+	/// Sets a `signal` to modulate the tap delay.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_delay_modulator_raw`](Self::set_delay_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the tap is freed.
+	pub unsafe fn set_delay_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_delay_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the tap delay.
+	/// Set to `null` to clear the modulator.
+	///
+	/// If the signal is continuous (e.g. an envelope or a triangle LFO, but not a square LFO)
+	/// playback is sped up or slowed down to compress or expand time.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_delayline::setTapDelayModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_effect_delayline::setTapDelayModulator")]
+	pub unsafe fn set_delay_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_tap_delay_modulator();
+		f(self.0, signal)
+	}
+
+	/// Returns the current delay modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_delayline::getTapDelayModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_delayline::getTapDelayModulator")]
+	pub fn delay_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_tap_delay_modulator();
+		unsafe { f(self.0) }
+	}
+
+	/// If the delay line is stereo and `flip` is set,
+	/// the tap outputs the delay line's left channel to its right output and vice versa.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_delayline::setTapChannelsFlipped`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_delayline::setTapChannelsFlipped")]
+	pub fn set_channels_flipped(&self, flip: bool) {
+		let f = self.api().set_tap_channels_flipped();
+		unsafe { f(self.0, flip.into()) }
+	}
+}
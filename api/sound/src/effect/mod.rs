@@ -0,0 +1,221 @@
+//! Playdate sound effects API
+
+use core::ffi::c_float;
+use core::ffi::c_int;
+use core::ffi::c_void;
+use alloc::boxed::Box;
+
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::SoundEffect as OpaqueSoundEffect;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+
+pub mod api;
+pub mod bitcrusher;
+pub mod delay_line;
+pub mod one_pole;
+pub mod overdrive;
+pub mod ring_mod;
+pub mod two_pole;
+
+pub use bitcrusher::BitCrusher;
+pub use delay_line::DelayLine;
+pub use delay_line::Tap;
+pub use one_pole::OnePoleFilter;
+pub use overdrive::Overdrive;
+pub use ring_mod::RingModulator;
+pub use two_pole::TwoPoleFilter;
+
+
+/// Common interface of all sound effects,
+/// built-in and [custom](Effect).
+///
+/// Effect should outlive every channel it is attached to.
+pub trait SoundEffect {
+	/// Api end-point of the effect.
+	type Api: api::Api;
+
+	/// Returns raw pointer to the underlying effect.
+	///
+	/// Built-in effects are "subclasses" of [`sys::ffi::SoundEffect`],
+	/// so their pointers are casted.
+	///
+	/// # Safety
+	/// Returned pointer is valid while the effect is alive.
+	unsafe fn as_effect_raw(&self) -> *mut OpaqueSoundEffect;
+
+	/// Returns api end-point of the effect.
+	fn effect_api(&self) -> &Self::Api;
+
+
+	/// Sets the wet/dry mix for the effect.
+	///
+	/// A level of `1.0` (full wet) replaces the input with the effect output;
+	/// `0.0` leaves the effect out of the mix.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect::setMix`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::setMix")]
+	fn set_mix(&self, level: c_float) {
+		use api::Api;
+		let f = self.effect_api().set_mix();
+		unsafe { f(self.as_effect_raw(), level) }
+	}
+
+	/// Sets a `signal` to modulate the effect's mix level.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_mix_modulator_raw`](Self::set_mix_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the effect is freed.
+	unsafe fn set_mix_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_mix_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the effect's mix level.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect::setMixModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_effect::setMixModulator")]
+	unsafe fn set_mix_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		use api::Api;
+		let f = self.effect_api().set_mix_modulator();
+		f(self.as_effect_raw(), signal)
+	}
+
+	/// Returns the current mix modulator for the effect.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect::getMixModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::getMixModulator")]
+	fn mix_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		use api::Api;
+		let f = self.effect_api().get_mix_modulator();
+		unsafe { f(self.as_effect_raw()) }
+	}
+}
+
+
+/// Custom processing function of the [`Effect`].
+///
+/// Implemented for all `FnMut(left, right, active) -> bool` closures.
+pub trait Dsp: 'static {
+	/// Processes `left` and `right` (if the effect is on a stereo channel) sample buffers in Q8.24 format.
+	///
+	/// `active` is `true` if samples have been set in the buffers.
+	///
+	/// Should return `true` if it changed the buffer samples.
+	fn process(&mut self, left: &mut [i32], right: Option<&mut [i32]>, active: bool) -> bool;
+}
+
+impl<F> Dsp for F where F: 'static + FnMut(&mut [i32], Option<&mut [i32]>, bool) -> bool {
+	#[inline(always)]
+	fn process(&mut self, left: &mut [i32], right: Option<&mut [i32]>, active: bool) -> bool {
+		self(left, right, active)
+	}
+}
+
+
+/// Custom sound effect, driven by the Rust [`Dsp`] function.
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Effect<Api: api::Api = api::Default>(*mut OpaqueSoundEffect, Api);
+
+impl<Api: api::Api> AsRaw for Effect<Api> {
+	type Type = OpaqueSoundEffect;
+	unsafe fn as_raw(&self) -> *mut OpaqueSoundEffect { self.0 }
+}
+
+impl<Api: api::Api> SoundEffect for Effect<Api> {
+	type Api = Api;
+
+	unsafe fn as_effect_raw(&self) -> *mut OpaqueSoundEffect { self.0 }
+	fn effect_api(&self) -> &Api { &self.1 }
+}
+
+
+// ctor //
+
+impl<Api> Effect<Api> where Api: api::Api {
+	/// Creates a new effect using the given processing function.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect::newEffect`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::newEffect")]
+	pub fn new<F: Dsp>(dsp: F) -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api, dsp)
+	}
+
+	/// Creates a new effect using the given processing function and `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect::newEffect`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect::newEffect")]
+	pub fn new_with<F: Dsp>(api: Api, dsp: F) -> Result<Self, Error> {
+		let dsp: Box<Box<dyn Dsp>> = Box::new(Box::new(dsp));
+		let userdata = Box::into_raw(dsp) as *mut c_void;
+
+		let f = api.new_effect();
+		let effect = unsafe { f(Some(proxy_dsp), userdata) };
+		if effect.is_null() {
+			drop(unsafe { Box::from_raw(userdata as *mut Box<dyn Dsp>) });
+			Err(Error::Alloc)
+		} else {
+			Ok(Effect(effect, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api> Drop for Effect<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			let userdata = {
+				let f = self.api().get_userdata();
+				unsafe { f(self.0) }
+			};
+
+			let f = self.api().free_effect();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+
+			if !userdata.is_null() {
+				drop(unsafe { Box::from_raw(userdata as *mut Box<dyn Dsp>) });
+			}
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api> Effect<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+unsafe extern "C" fn proxy_dsp(effect: *mut OpaqueSoundEffect,
+                               left: *mut i32,
+                               right: *mut i32,
+                               nsamples: c_int,
+                               active: c_int)
+                               -> c_int {
+	use api::Api;
+	let f = api::Default.get_userdata();
+	let Some(dsp) = (f(effect) as *mut Box<dyn Dsp>).as_mut() else {
+		return 0;
+	};
+	if left.is_null() {
+		return 0;
+	}
+	let len = nsamples.max(0) as usize;
+	let left = core::slice::from_raw_parts_mut(left, len);
+	let right = (!right.is_null()).then(|| core::slice::from_raw_parts_mut(right, len));
+	dsp.process(left, right, active == 1).into()
+}
@@ -0,0 +1,102 @@
+use core::ffi::c_float;
+use core::ptr::NonNull;
+
+use sys::ffi::OnePoleFilter;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::playdate_sound_effect_onepolefilter;
+
+
+/// Default one pole filter effect api end-point, ZST.
+///
+/// All calls approximately costs ~4 derefs.
+#[derive(Debug, Clone, Copy, core::default::Default)]
+pub struct Default;
+impl Api for Default {}
+impl crate::effect::api::Api for Default {}
+
+
+/// Cached one pole filter effect api end-point.
+///
+/// Stores one reference, so size on stack is eq `usize`.
+///
+/// All calls approximately costs ~1 deref.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Cache(&'static playdate_sound_effect_onepolefilter);
+
+impl core::default::Default for Cache {
+	fn default() -> Self { Self(sys::api!(sound.effect.onepolefilter)) }
+}
+
+impl From<*const playdate_sound_effect_onepolefilter> for Cache {
+	#[inline(always)]
+	fn from(ptr: *const playdate_sound_effect_onepolefilter) -> Self {
+		Self(unsafe { ptr.as_ref() }.expect("onepolefilter"))
+	}
+}
+
+impl From<&'static playdate_sound_effect_onepolefilter> for Cache {
+	#[inline(always)]
+	fn from(r: &'static playdate_sound_effect_onepolefilter) -> Self { Self(r) }
+}
+
+impl From<NonNull<playdate_sound_effect_onepolefilter>> for Cache {
+	#[inline(always)]
+	fn from(ptr: NonNull<playdate_sound_effect_onepolefilter>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+impl From<&'_ NonNull<playdate_sound_effect_onepolefilter>> for Cache {
+	#[inline(always)]
+	fn from(ptr: &NonNull<playdate_sound_effect_onepolefilter>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+
+/// Common effect functions are not cached and cost as [`crate::effect::api::Default`].
+impl crate::effect::api::Api for Cache {}
+
+impl Api for Cache {
+	fn new_filter(&self) -> FnNewFilter { self.0.newFilter.expect("newFilter") }
+	fn free_filter(&self) -> FnFreeFilter { self.0.freeFilter.expect("freeFilter") }
+	fn set_parameter(&self) -> FnSetParameter { self.0.setParameter.expect("setParameter") }
+	fn set_parameter_modulator(&self) -> FnSetParameterModulator {
+		self.0.setParameterModulator.expect("setParameterModulator")
+	}
+	fn get_parameter_modulator(&self) -> FnGetParameterModulator {
+		self.0.getParameterModulator.expect("getParameterModulator")
+	}
+}
+
+
+/// Effect-specific api, extends the common [effect api](crate::effect::api::Api).
+pub trait Api: crate::effect::api::Api {
+	/// Returns [`sys::ffi::playdate_sound_effect_onepolefilter::newFilter`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_onepolefilter::newFilter")]
+	fn new_filter(&self) -> FnNewFilter { *sys::api!(sound.effect.onepolefilter.newFilter) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_onepolefilter::freeFilter`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_onepolefilter::freeFilter")]
+	fn free_filter(&self) -> FnFreeFilter { *sys::api!(sound.effect.onepolefilter.freeFilter) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_onepolefilter::setParameter`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_onepolefilter::setParameter")]
+	fn set_parameter(&self) -> FnSetParameter { *sys::api!(sound.effect.onepolefilter.setParameter) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_onepolefilter::setParameterModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_onepolefilter::setParameterModulator")]
+	fn set_parameter_modulator(&self) -> FnSetParameterModulator {
+		*sys::api!(sound.effect.onepolefilter.setParameterModulator)
+	}
+
+	/// Returns [`sys::ffi::playdate_sound_effect_onepolefilter::getParameterModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_onepolefilter::getParameterModulator")]
+	fn get_parameter_modulator(&self) -> FnGetParameterModulator {
+		*sys::api!(sound.effect.onepolefilter.getParameterModulator)
+	}
+}
+
+
+type FnNewFilter = unsafe extern "C" fn() -> *mut OnePoleFilter;
+type FnFreeFilter = unsafe extern "C" fn(filter: *mut OnePoleFilter);
+type FnSetParameter = unsafe extern "C" fn(filter: *mut OnePoleFilter, parameter: c_float);
+type FnSetParameterModulator = unsafe extern "C" fn(filter: *mut OnePoleFilter, signal: *mut PDSynthSignalValue);
+type FnGetParameterModulator = unsafe extern "C" fn(filter: *mut OnePoleFilter) -> *mut PDSynthSignalValue;
@@ -0,0 +1,129 @@
+//! Playdate one pole filter effect API
+
+use core::ffi::c_float;
+
+use sys::ffi::OnePoleFilter as OpaqueOnePoleFilter;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::SoundEffect as OpaqueSoundEffect;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+use super::SoundEffect;
+
+pub mod api;
+
+
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct OnePoleFilter<Api: api::Api = api::Default>(*mut OpaqueOnePoleFilter, Api);
+
+impl<Api: api::Api> AsRaw for OnePoleFilter<Api> {
+	type Type = OpaqueOnePoleFilter;
+	unsafe fn as_raw(&self) -> *mut OpaqueOnePoleFilter { self.0 }
+}
+
+impl<Api: api::Api> SoundEffect for OnePoleFilter<Api> {
+	type Api = Api;
+
+	unsafe fn as_effect_raw(&self) -> *mut OpaqueSoundEffect { self.0 as _ }
+	fn effect_api(&self) -> &Api { &self.1 }
+}
+
+
+// ctor //
+
+impl<Api> OnePoleFilter<Api> where Api: api::Api {
+	/// Creates a new one pole filter.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_onepolefilter::newFilter`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_onepolefilter::newFilter")]
+	pub fn new() -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new one pole filter with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_onepolefilter::newFilter`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_onepolefilter::newFilter")]
+	pub fn new_with(api: Api) -> Result<Self, Error> {
+		let f = api.new_filter();
+		let ptr = unsafe { f() };
+		if ptr.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(OnePoleFilter(ptr, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api> Drop for OnePoleFilter<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			let f = self.api().free_filter();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api> OnePoleFilter<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api> OnePoleFilter<Api> {
+	/// Sets the filter's single parameter (cutoff frequency).
+	///
+	/// Values above `0.0` (up to `1.0`) are high-pass, values below `0.0` (down to `-1.0`) are low-pass.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_onepolefilter::setParameter`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_onepolefilter::setParameter")]
+	pub fn set_parameter(&self, parameter: c_float) {
+		let f = self.api().set_parameter();
+		unsafe { f(self.0, parameter) }
+	}
+
+	/// Sets a `signal` to modulate the filter parameter.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_parameter_modulator_raw`](Self::set_parameter_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the effect is freed.
+	pub unsafe fn set_parameter_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_parameter_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the filter parameter.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_onepolefilter::setParameterModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_effect_onepolefilter::setParameterModulator")]
+	pub unsafe fn set_parameter_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_parameter_modulator();
+		f(self.0, signal)
+	}
+
+	/// Returns the filter's current parameter modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_onepolefilter::getParameterModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_onepolefilter::getParameterModulator")]
+	pub fn parameter_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_parameter_modulator();
+		unsafe { f(self.0) }
+	}
+}
@@ -0,0 +1,126 @@
+use core::ffi::c_float;
+use core::ptr::NonNull;
+
+use sys::ffi::Overdrive;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::playdate_sound_effect_overdrive;
+
+
+/// Default overdrive effect api end-point, ZST.
+///
+/// All calls approximately costs ~4 derefs.
+#[derive(Debug, Clone, Copy, core::default::Default)]
+pub struct Default;
+impl Api for Default {}
+impl crate::effect::api::Api for Default {}
+
+
+/// Cached overdrive effect api end-point.
+///
+/// Stores one reference, so size on stack is eq `usize`.
+///
+/// All calls approximately costs ~1 deref.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Cache(&'static playdate_sound_effect_overdrive);
+
+impl core::default::Default for Cache {
+	fn default() -> Self { Self(sys::api!(sound.effect.overdrive)) }
+}
+
+impl From<*const playdate_sound_effect_overdrive> for Cache {
+	#[inline(always)]
+	fn from(ptr: *const playdate_sound_effect_overdrive) -> Self {
+		Self(unsafe { ptr.as_ref() }.expect("overdrive"))
+	}
+}
+
+impl From<&'static playdate_sound_effect_overdrive> for Cache {
+	#[inline(always)]
+	fn from(r: &'static playdate_sound_effect_overdrive) -> Self { Self(r) }
+}
+
+impl From<NonNull<playdate_sound_effect_overdrive>> for Cache {
+	#[inline(always)]
+	fn from(ptr: NonNull<playdate_sound_effect_overdrive>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+impl From<&'_ NonNull<playdate_sound_effect_overdrive>> for Cache {
+	#[inline(always)]
+	fn from(ptr: &NonNull<playdate_sound_effect_overdrive>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+
+/// Common effect functions are not cached and cost as [`crate::effect::api::Default`].
+impl crate::effect::api::Api for Cache {}
+
+impl Api for Cache {
+	fn new_overdrive(&self) -> FnNewOverdrive { self.0.newOverdrive.expect("newOverdrive") }
+	fn free_overdrive(&self) -> FnFreeOverdrive { self.0.freeOverdrive.expect("freeOverdrive") }
+	fn set_gain(&self) -> FnSetGain { self.0.setGain.expect("setGain") }
+	fn set_limit(&self) -> FnSetLimit { self.0.setLimit.expect("setLimit") }
+	fn set_limit_modulator(&self) -> FnSetLimitModulator { self.0.setLimitModulator.expect("setLimitModulator") }
+	fn get_limit_modulator(&self) -> FnGetLimitModulator { self.0.getLimitModulator.expect("getLimitModulator") }
+	fn set_offset(&self) -> FnSetOffset { self.0.setOffset.expect("setOffset") }
+	fn set_offset_modulator(&self) -> FnSetOffsetModulator {
+		self.0.setOffsetModulator.expect("setOffsetModulator")
+	}
+	fn get_offset_modulator(&self) -> FnGetOffsetModulator {
+		self.0.getOffsetModulator.expect("getOffsetModulator")
+	}
+}
+
+
+/// Effect-specific api, extends the common [effect api](crate::effect::api::Api).
+pub trait Api: crate::effect::api::Api {
+	/// Returns [`sys::ffi::playdate_sound_effect_overdrive::newOverdrive`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::newOverdrive")]
+	fn new_overdrive(&self) -> FnNewOverdrive { *sys::api!(sound.effect.overdrive.newOverdrive) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_overdrive::freeOverdrive`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::freeOverdrive")]
+	fn free_overdrive(&self) -> FnFreeOverdrive { *sys::api!(sound.effect.overdrive.freeOverdrive) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_overdrive::setGain`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::setGain")]
+	fn set_gain(&self) -> FnSetGain { *sys::api!(sound.effect.overdrive.setGain) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_overdrive::setLimit`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::setLimit")]
+	fn set_limit(&self) -> FnSetLimit { *sys::api!(sound.effect.overdrive.setLimit) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_overdrive::setLimitModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::setLimitModulator")]
+	fn set_limit_modulator(&self) -> FnSetLimitModulator { *sys::api!(sound.effect.overdrive.setLimitModulator) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_overdrive::getLimitModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::getLimitModulator")]
+	fn get_limit_modulator(&self) -> FnGetLimitModulator { *sys::api!(sound.effect.overdrive.getLimitModulator) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_overdrive::setOffset`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::setOffset")]
+	fn set_offset(&self) -> FnSetOffset { *sys::api!(sound.effect.overdrive.setOffset) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_overdrive::setOffsetModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::setOffsetModulator")]
+	fn set_offset_modulator(&self) -> FnSetOffsetModulator {
+		*sys::api!(sound.effect.overdrive.setOffsetModulator)
+	}
+
+	/// Returns [`sys::ffi::playdate_sound_effect_overdrive::getOffsetModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::getOffsetModulator")]
+	fn get_offset_modulator(&self) -> FnGetOffsetModulator {
+		*sys::api!(sound.effect.overdrive.getOffsetModulator)
+	}
+}
+
+
+type FnNewOverdrive = unsafe extern "C" fn() -> *mut Overdrive;
+type FnFreeOverdrive = unsafe extern "C" fn(filter: *mut Overdrive);
+type FnSetGain = unsafe extern "C" fn(o: *mut Overdrive, gain: c_float);
+type FnSetLimit = unsafe extern "C" fn(o: *mut Overdrive, limit: c_float);
+type FnSetLimitModulator = unsafe extern "C" fn(o: *mut Overdrive, mod_: *mut PDSynthSignalValue);
+type FnGetLimitModulator = unsafe extern "C" fn(o: *mut Overdrive) -> *mut PDSynthSignalValue;
+type FnSetOffset = unsafe extern "C" fn(o: *mut Overdrive, offset: c_float);
+type FnSetOffsetModulator = unsafe extern "C" fn(o: *mut Overdrive, mod_: *mut PDSynthSignalValue);
+type FnGetOffsetModulator = unsafe extern "C" fn(o: *mut Overdrive) -> *mut PDSynthSignalValue;
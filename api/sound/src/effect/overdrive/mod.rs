@@ -0,0 +1,180 @@
+//! Playdate overdrive effect API
+
+use core::ffi::c_float;
+
+use sys::ffi::Overdrive as OpaqueOverdrive;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::SoundEffect as OpaqueSoundEffect;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+use super::SoundEffect;
+
+pub mod api;
+
+
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Overdrive<Api: api::Api = api::Default>(*mut OpaqueOverdrive, Api);
+
+impl<Api: api::Api> AsRaw for Overdrive<Api> {
+	type Type = OpaqueOverdrive;
+	unsafe fn as_raw(&self) -> *mut OpaqueOverdrive { self.0 }
+}
+
+impl<Api: api::Api> SoundEffect for Overdrive<Api> {
+	type Api = Api;
+
+	unsafe fn as_effect_raw(&self) -> *mut OpaqueSoundEffect { self.0 as _ }
+	fn effect_api(&self) -> &Api { &self.1 }
+}
+
+
+// ctor //
+
+impl<Api> Overdrive<Api> where Api: api::Api {
+	/// Creates a new overdrive effect.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_overdrive::newOverdrive`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::newOverdrive")]
+	pub fn new() -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new overdrive effect with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_overdrive::newOverdrive`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::newOverdrive")]
+	pub fn new_with(api: Api) -> Result<Self, Error> {
+		let f = api.new_overdrive();
+		let ptr = unsafe { f() };
+		if ptr.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(Overdrive(ptr, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api> Drop for Overdrive<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			let f = self.api().free_overdrive();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api> Overdrive<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api> Overdrive<Api> {
+	/// Sets the gain of the overdrive effect.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_overdrive::setGain`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::setGain")]
+	pub fn set_gain(&self, gain: c_float) {
+		let f = self.api().set_gain();
+		unsafe { f(self.0, gain) }
+	}
+
+	/// Sets the level where the amplified input clips.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_overdrive::setLimit`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::setLimit")]
+	pub fn set_limit(&self, limit: c_float) {
+		let f = self.api().set_limit();
+		unsafe { f(self.0, limit) }
+	}
+
+	/// Sets a `signal` to modulate the limit parameter.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_limit_modulator_raw`](Self::set_limit_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the effect is freed.
+	pub unsafe fn set_limit_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_limit_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the limit parameter.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_overdrive::setLimitModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::setLimitModulator")]
+	pub unsafe fn set_limit_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_limit_modulator();
+		f(self.0, signal)
+	}
+
+	/// Returns the currently set limit modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_overdrive::getLimitModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::getLimitModulator")]
+	pub fn limit_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_limit_modulator();
+		unsafe { f(self.0) }
+	}
+
+	/// Adds an offset to the upper and lower limits to create an asymmetric clipping.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_overdrive::setOffset`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::setOffset")]
+	pub fn set_offset(&self, offset: c_float) {
+		let f = self.api().set_offset();
+		unsafe { f(self.0, offset) }
+	}
+
+	/// Sets a `signal` to modulate the offset parameter.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_offset_modulator_raw`](Self::set_offset_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the effect is freed.
+	pub unsafe fn set_offset_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_offset_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the offset parameter.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_overdrive::setOffsetModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::setOffsetModulator")]
+	pub unsafe fn set_offset_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_offset_modulator();
+		f(self.0, signal)
+	}
+
+	/// Returns the currently set offset modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_overdrive::getOffsetModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_overdrive::getOffsetModulator")]
+	pub fn offset_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_offset_modulator();
+		unsafe { f(self.0) }
+	}
+}
@@ -0,0 +1,102 @@
+use core::ffi::c_float;
+use core::ptr::NonNull;
+
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::RingModulator;
+use sys::ffi::playdate_sound_effect_ringmodulator;
+
+
+/// Default ring modulator effect api end-point, ZST.
+///
+/// All calls approximately costs ~4 derefs.
+#[derive(Debug, Clone, Copy, core::default::Default)]
+pub struct Default;
+impl Api for Default {}
+impl crate::effect::api::Api for Default {}
+
+
+/// Cached ring modulator effect api end-point.
+///
+/// Stores one reference, so size on stack is eq `usize`.
+///
+/// All calls approximately costs ~1 deref.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Cache(&'static playdate_sound_effect_ringmodulator);
+
+impl core::default::Default for Cache {
+	fn default() -> Self { Self(sys::api!(sound.effect.ringmodulator)) }
+}
+
+impl From<*const playdate_sound_effect_ringmodulator> for Cache {
+	#[inline(always)]
+	fn from(ptr: *const playdate_sound_effect_ringmodulator) -> Self {
+		Self(unsafe { ptr.as_ref() }.expect("ringmodulator"))
+	}
+}
+
+impl From<&'static playdate_sound_effect_ringmodulator> for Cache {
+	#[inline(always)]
+	fn from(r: &'static playdate_sound_effect_ringmodulator) -> Self { Self(r) }
+}
+
+impl From<NonNull<playdate_sound_effect_ringmodulator>> for Cache {
+	#[inline(always)]
+	fn from(ptr: NonNull<playdate_sound_effect_ringmodulator>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+impl From<&'_ NonNull<playdate_sound_effect_ringmodulator>> for Cache {
+	#[inline(always)]
+	fn from(ptr: &NonNull<playdate_sound_effect_ringmodulator>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+
+/// Common effect functions are not cached and cost as [`crate::effect::api::Default`].
+impl crate::effect::api::Api for Cache {}
+
+impl Api for Cache {
+	fn new_ringmod(&self) -> FnNewRingmod { self.0.newRingmod.expect("newRingmod") }
+	fn free_ringmod(&self) -> FnFreeRingmod { self.0.freeRingmod.expect("freeRingmod") }
+	fn set_frequency(&self) -> FnSetFrequency { self.0.setFrequency.expect("setFrequency") }
+	fn set_frequency_modulator(&self) -> FnSetFrequencyModulator {
+		self.0.setFrequencyModulator.expect("setFrequencyModulator")
+	}
+	fn get_frequency_modulator(&self) -> FnGetFrequencyModulator {
+		self.0.getFrequencyModulator.expect("getFrequencyModulator")
+	}
+}
+
+
+/// Effect-specific api, extends the common [effect api](crate::effect::api::Api).
+pub trait Api: crate::effect::api::Api {
+	/// Returns [`sys::ffi::playdate_sound_effect_ringmodulator::newRingmod`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_ringmodulator::newRingmod")]
+	fn new_ringmod(&self) -> FnNewRingmod { *sys::api!(sound.effect.ringmodulator.newRingmod) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_ringmodulator::freeRingmod`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_ringmodulator::freeRingmod")]
+	fn free_ringmod(&self) -> FnFreeRingmod { *sys::api!(sound.effect.ringmodulator.freeRingmod) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_ringmodulator::setFrequency`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_ringmodulator::setFrequency")]
+	fn set_frequency(&self) -> FnSetFrequency { *sys::api!(sound.effect.ringmodulator.setFrequency) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_ringmodulator::setFrequencyModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_ringmodulator::setFrequencyModulator")]
+	fn set_frequency_modulator(&self) -> FnSetFrequencyModulator {
+		*sys::api!(sound.effect.ringmodulator.setFrequencyModulator)
+	}
+
+	/// Returns [`sys::ffi::playdate_sound_effect_ringmodulator::getFrequencyModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_ringmodulator::getFrequencyModulator")]
+	fn get_frequency_modulator(&self) -> FnGetFrequencyModulator {
+		*sys::api!(sound.effect.ringmodulator.getFrequencyModulator)
+	}
+}
+
+
+type FnNewRingmod = unsafe extern "C" fn() -> *mut RingModulator;
+type FnFreeRingmod = unsafe extern "C" fn(filter: *mut RingModulator);
+type FnSetFrequency = unsafe extern "C" fn(filter: *mut RingModulator, frequency: c_float);
+type FnSetFrequencyModulator = unsafe extern "C" fn(filter: *mut RingModulator, signal: *mut PDSynthSignalValue);
+type FnGetFrequencyModulator = unsafe extern "C" fn(filter: *mut RingModulator) -> *mut PDSynthSignalValue;
@@ -0,0 +1,127 @@
+//! Playdate ring modulator effect API
+
+use core::ffi::c_float;
+
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::RingModulator as OpaqueRingModulator;
+use sys::ffi::SoundEffect as OpaqueSoundEffect;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+use super::SoundEffect;
+
+pub mod api;
+
+
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct RingModulator<Api: api::Api = api::Default>(*mut OpaqueRingModulator, Api);
+
+impl<Api: api::Api> AsRaw for RingModulator<Api> {
+	type Type = OpaqueRingModulator;
+	unsafe fn as_raw(&self) -> *mut OpaqueRingModulator { self.0 }
+}
+
+impl<Api: api::Api> SoundEffect for RingModulator<Api> {
+	type Api = Api;
+
+	unsafe fn as_effect_raw(&self) -> *mut OpaqueSoundEffect { self.0 as _ }
+	fn effect_api(&self) -> &Api { &self.1 }
+}
+
+
+// ctor //
+
+impl<Api> RingModulator<Api> where Api: api::Api {
+	/// Creates a new ring modulator effect.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_ringmodulator::newRingmod`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_ringmodulator::newRingmod")]
+	pub fn new() -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new ring modulator effect with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_ringmodulator::newRingmod`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_ringmodulator::newRingmod")]
+	pub fn new_with(api: Api) -> Result<Self, Error> {
+		let f = api.new_ringmod();
+		let ptr = unsafe { f() };
+		if ptr.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(RingModulator(ptr, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api> Drop for RingModulator<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			let f = self.api().free_ringmod();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api> RingModulator<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api> RingModulator<Api> {
+	/// Sets the frequency of the modulation signal.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_ringmodulator::setFrequency`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_ringmodulator::setFrequency")]
+	pub fn set_frequency(&self, frequency: c_float) {
+		let f = self.api().set_frequency();
+		unsafe { f(self.0, frequency) }
+	}
+
+	/// Sets a `signal` to modulate the frequency of the ring modulator.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_frequency_modulator_raw`](Self::set_frequency_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the effect is freed.
+	pub unsafe fn set_frequency_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_frequency_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the frequency of the ring modulator.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_ringmodulator::setFrequencyModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_effect_ringmodulator::setFrequencyModulator")]
+	pub unsafe fn set_frequency_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_frequency_modulator();
+		f(self.0, signal)
+	}
+
+	/// Returns the currently set frequency modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_ringmodulator::getFrequencyModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_ringmodulator::getFrequencyModulator")]
+	pub fn frequency_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_frequency_modulator();
+		unsafe { f(self.0) }
+	}
+}
@@ -0,0 +1,141 @@
+use core::ffi::c_float;
+use core::ptr::NonNull;
+
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::TwoPoleFilter;
+use sys::ffi::TwoPoleFilterType;
+use sys::ffi::playdate_sound_effect_twopolefilter;
+
+
+/// Default two pole filter effect api end-point, ZST.
+///
+/// All calls approximately costs ~4 derefs.
+#[derive(Debug, Clone, Copy, core::default::Default)]
+pub struct Default;
+impl Api for Default {}
+impl crate::effect::api::Api for Default {}
+
+
+/// Cached two pole filter effect api end-point.
+///
+/// Stores one reference, so size on stack is eq `usize`.
+///
+/// All calls approximately costs ~1 deref.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Cache(&'static playdate_sound_effect_twopolefilter);
+
+impl core::default::Default for Cache {
+	fn default() -> Self { Self(sys::api!(sound.effect.twopolefilter)) }
+}
+
+impl From<*const playdate_sound_effect_twopolefilter> for Cache {
+	#[inline(always)]
+	fn from(ptr: *const playdate_sound_effect_twopolefilter) -> Self {
+		Self(unsafe { ptr.as_ref() }.expect("twopolefilter"))
+	}
+}
+
+impl From<&'static playdate_sound_effect_twopolefilter> for Cache {
+	#[inline(always)]
+	fn from(r: &'static playdate_sound_effect_twopolefilter) -> Self { Self(r) }
+}
+
+impl From<NonNull<playdate_sound_effect_twopolefilter>> for Cache {
+	#[inline(always)]
+	fn from(ptr: NonNull<playdate_sound_effect_twopolefilter>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+impl From<&'_ NonNull<playdate_sound_effect_twopolefilter>> for Cache {
+	#[inline(always)]
+	fn from(ptr: &NonNull<playdate_sound_effect_twopolefilter>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+
+/// Common effect functions are not cached and cost as [`crate::effect::api::Default`].
+impl crate::effect::api::Api for Cache {}
+
+impl Api for Cache {
+	fn new_filter(&self) -> FnNewFilter { self.0.newFilter.expect("newFilter") }
+	fn free_filter(&self) -> FnFreeFilter { self.0.freeFilter.expect("freeFilter") }
+	fn set_type(&self) -> FnSetType { self.0.setType.expect("setType") }
+	fn set_frequency(&self) -> FnSetFrequency { self.0.setFrequency.expect("setFrequency") }
+	fn set_frequency_modulator(&self) -> FnSetFrequencyModulator {
+		self.0.setFrequencyModulator.expect("setFrequencyModulator")
+	}
+	fn get_frequency_modulator(&self) -> FnGetFrequencyModulator {
+		self.0.getFrequencyModulator.expect("getFrequencyModulator")
+	}
+	fn set_gain(&self) -> FnSetGain { self.0.setGain.expect("setGain") }
+	fn set_resonance(&self) -> FnSetResonance { self.0.setResonance.expect("setResonance") }
+	fn set_resonance_modulator(&self) -> FnSetResonanceModulator {
+		self.0.setResonanceModulator.expect("setResonanceModulator")
+	}
+	fn get_resonance_modulator(&self) -> FnGetResonanceModulator {
+		self.0.getResonanceModulator.expect("getResonanceModulator")
+	}
+}
+
+
+/// Effect-specific api, extends the common [effect api](crate::effect::api::Api).
+pub trait Api: crate::effect::api::Api {
+	/// Returns [`sys::ffi::playdate_sound_effect_twopolefilter::newFilter`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::newFilter")]
+	fn new_filter(&self) -> FnNewFilter { *sys::api!(sound.effect.twopolefilter.newFilter) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_twopolefilter::freeFilter`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::freeFilter")]
+	fn free_filter(&self) -> FnFreeFilter { *sys::api!(sound.effect.twopolefilter.freeFilter) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_twopolefilter::setType`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setType")]
+	fn set_type(&self) -> FnSetType { *sys::api!(sound.effect.twopolefilter.setType) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_twopolefilter::setFrequency`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setFrequency")]
+	fn set_frequency(&self) -> FnSetFrequency { *sys::api!(sound.effect.twopolefilter.setFrequency) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_twopolefilter::setFrequencyModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setFrequencyModulator")]
+	fn set_frequency_modulator(&self) -> FnSetFrequencyModulator {
+		*sys::api!(sound.effect.twopolefilter.setFrequencyModulator)
+	}
+
+	/// Returns [`sys::ffi::playdate_sound_effect_twopolefilter::getFrequencyModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::getFrequencyModulator")]
+	fn get_frequency_modulator(&self) -> FnGetFrequencyModulator {
+		*sys::api!(sound.effect.twopolefilter.getFrequencyModulator)
+	}
+
+	/// Returns [`sys::ffi::playdate_sound_effect_twopolefilter::setGain`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setGain")]
+	fn set_gain(&self) -> FnSetGain { *sys::api!(sound.effect.twopolefilter.setGain) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_twopolefilter::setResonance`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setResonance")]
+	fn set_resonance(&self) -> FnSetResonance { *sys::api!(sound.effect.twopolefilter.setResonance) }
+
+	/// Returns [`sys::ffi::playdate_sound_effect_twopolefilter::setResonanceModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setResonanceModulator")]
+	fn set_resonance_modulator(&self) -> FnSetResonanceModulator {
+		*sys::api!(sound.effect.twopolefilter.setResonanceModulator)
+	}
+
+	/// Returns [`sys::ffi::playdate_sound_effect_twopolefilter::getResonanceModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::getResonanceModulator")]
+	fn get_resonance_modulator(&self) -> FnGetResonanceModulator {
+		*sys::api!(sound.effect.twopolefilter.getResonanceModulator)
+	}
+}
+
+
+type FnNewFilter = unsafe extern "C" fn() -> *mut TwoPoleFilter;
+type FnFreeFilter = unsafe extern "C" fn(filter: *mut TwoPoleFilter);
+type FnSetType = unsafe extern "C" fn(filter: *mut TwoPoleFilter, type_: TwoPoleFilterType);
+type FnSetFrequency = unsafe extern "C" fn(filter: *mut TwoPoleFilter, frequency: c_float);
+type FnSetFrequencyModulator = unsafe extern "C" fn(filter: *mut TwoPoleFilter, signal: *mut PDSynthSignalValue);
+type FnGetFrequencyModulator = unsafe extern "C" fn(filter: *mut TwoPoleFilter) -> *mut PDSynthSignalValue;
+type FnSetGain = unsafe extern "C" fn(filter: *mut TwoPoleFilter, gain: c_float);
+type FnSetResonance = unsafe extern "C" fn(filter: *mut TwoPoleFilter, resonance: c_float);
+type FnSetResonanceModulator = unsafe extern "C" fn(filter: *mut TwoPoleFilter, signal: *mut PDSynthSignalValue);
+type FnGetResonanceModulator = unsafe extern "C" fn(filter: *mut TwoPoleFilter) -> *mut PDSynthSignalValue;
@@ -0,0 +1,194 @@
+//! Playdate two pole filter effect API
+
+use core::ffi::c_float;
+
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::SoundEffect as OpaqueSoundEffect;
+use sys::ffi::TwoPoleFilter as OpaqueTwoPoleFilter;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+use super::SoundEffect;
+
+pub mod api;
+
+
+pub use sys::ffi::TwoPoleFilterType as FilterType;
+
+
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct TwoPoleFilter<Api: api::Api = api::Default>(*mut OpaqueTwoPoleFilter, Api);
+
+impl<Api: api::Api> AsRaw for TwoPoleFilter<Api> {
+	type Type = OpaqueTwoPoleFilter;
+	unsafe fn as_raw(&self) -> *mut OpaqueTwoPoleFilter { self.0 }
+}
+
+impl<Api: api::Api> SoundEffect for TwoPoleFilter<Api> {
+	type Api = Api;
+
+	unsafe fn as_effect_raw(&self) -> *mut OpaqueSoundEffect { self.0 as _ }
+	fn effect_api(&self) -> &Api { &self.1 }
+}
+
+
+// ctor //
+
+impl<Api> TwoPoleFilter<Api> where Api: api::Api {
+	/// Creates a new two pole filter effect.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_twopolefilter::newFilter`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::newFilter")]
+	pub fn new() -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new two pole filter effect with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_twopolefilter::newFilter`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::newFilter")]
+	pub fn new_with(api: Api) -> Result<Self, Error> {
+		let f = api.new_filter();
+		let ptr = unsafe { f() };
+		if ptr.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(TwoPoleFilter(ptr, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api> Drop for TwoPoleFilter<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			let f = self.api().free_filter();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api> TwoPoleFilter<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api> TwoPoleFilter<Api> {
+	/// Sets the type of the filter.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_twopolefilter::setType`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setType")]
+	pub fn set_type(&self, kind: FilterType) {
+		let f = self.api().set_type();
+		unsafe { f(self.0, kind) }
+	}
+
+	/// Sets the center/corner frequency of the filter, in Hz.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_twopolefilter::setFrequency`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setFrequency")]
+	pub fn set_frequency(&self, frequency: c_float) {
+		let f = self.api().set_frequency();
+		unsafe { f(self.0, frequency) }
+	}
+
+	/// Sets a `signal` to modulate the effect's frequency.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_frequency_modulator_raw`](Self::set_frequency_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the effect is freed.
+	pub unsafe fn set_frequency_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_frequency_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the effect's frequency.
+	///
+	/// The signal is scaled so that a value of `1.0` corresponds to half the sample rate.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_twopolefilter::setFrequencyModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setFrequencyModulator")]
+	pub unsafe fn set_frequency_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_frequency_modulator();
+		f(self.0, signal)
+	}
+
+	/// Returns the filter's current frequency modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_twopolefilter::getFrequencyModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::getFrequencyModulator")]
+	pub fn frequency_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_frequency_modulator();
+		unsafe { f(self.0) }
+	}
+
+	/// Sets the filter gain.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_twopolefilter::setGain`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setGain")]
+	pub fn set_gain(&self, gain: c_float) {
+		let f = self.api().set_gain();
+		unsafe { f(self.0, gain) }
+	}
+
+	/// Sets the filter resonance.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_twopolefilter::setResonance`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setResonance")]
+	pub fn set_resonance(&self, resonance: c_float) {
+		let f = self.api().set_resonance();
+		unsafe { f(self.0, resonance) }
+	}
+
+	/// Sets a `signal` to modulate the filter resonance.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_resonance_modulator_raw`](Self::set_resonance_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the effect is freed.
+	pub unsafe fn set_resonance_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_resonance_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the filter resonance.
+	/// Set to `null` to clear the modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_twopolefilter::setResonanceModulator`]
+	///
+	/// # Safety
+	/// `signal` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::setResonanceModulator")]
+	pub unsafe fn set_resonance_modulator_raw(&self, signal: *mut PDSynthSignalValue) {
+		let f = self.api().set_resonance_modulator();
+		f(self.0, signal)
+	}
+
+	/// Returns the filter's current resonance modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_effect_twopolefilter::getResonanceModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_effect_twopolefilter::getResonanceModulator")]
+	pub fn resonance_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_resonance_modulator();
+		unsafe { f(self.0) }
+	}
+}
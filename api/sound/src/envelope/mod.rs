@@ -0,0 +1,203 @@
+//! Playdate envelope API
+
+use core::ffi::c_float;
+
+use sys::ffi::MIDINote;
+use sys::ffi::PDSynthEnvelope;
+use sys::ffi::PDSynthSignalValue;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+
+pub mod api;
+
+
+/// ADSR envelope, can be used to modulate sounds.
+///
+/// Owned envelope (`FREE_ON_DROP = true`) frees the C-part on drop,
+/// envelopes borrowed from a [`Synth`](crate::synth::Synth) are not owned and will not be freed.
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Envelope<Api: api::Api = api::Default, const FREE_ON_DROP: bool = true>(*mut PDSynthEnvelope, Api);
+
+impl<Api: api::Api, const FOD: bool> AsRaw for Envelope<Api, FOD> {
+	type Type = PDSynthEnvelope;
+	unsafe fn as_raw(&self) -> *mut PDSynthEnvelope { self.0 }
+}
+
+impl<Api: api::Api, const FOD: bool> Signal for Envelope<Api, FOD> {
+	unsafe fn as_signal_raw(&self) -> *mut PDSynthSignalValue { self.0 as _ }
+}
+
+impl<Api: api::Api + Default, const FOD: bool> From<*mut PDSynthEnvelope> for Envelope<Api, FOD> {
+	fn from(ptr: *mut PDSynthEnvelope) -> Self { Self(ptr, Api::default()) }
+}
+
+impl<Api: api::Api, const FOD: bool> Envelope<Api, FOD> {
+	/// Wraps the raw `ptr` to the envelope with given `api`.
+	///
+	/// # Safety
+	/// `ptr` must point to a valid envelope.
+	/// If `FREE_ON_DROP` is `true`, the wrapper takes ownership of it and frees it on drop,
+	/// otherwise the envelope must outlive the wrapper.
+	pub unsafe fn from_with(api: Api, ptr: *mut PDSynthEnvelope) -> Self { Self(ptr, api) }
+}
+
+
+// ctor //
+
+impl<Api> Envelope<Api, true> where Api: api::Api {
+	/// Creates a new envelope with the given parameters.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::newEnvelope`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::newEnvelope")]
+	pub fn new(attack: c_float, decay: c_float, sustain: c_float, release: c_float) -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api, attack, decay, sustain, release)
+	}
+
+	/// Creates a new envelope with the given parameters and `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::newEnvelope`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::newEnvelope")]
+	pub fn new_with(api: Api,
+	                attack: c_float,
+	                decay: c_float,
+	                sustain: c_float,
+	                release: c_float)
+	                -> Result<Self, Error> {
+		let f = api.new_envelope();
+		let env = unsafe { f(attack, decay, sustain, release) };
+		if env.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(Envelope(env, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api, const FOD: bool> Drop for Envelope<Api, FOD> {
+	fn drop(&mut self) {
+		if FOD && !self.0.is_null() {
+			let f = self.api().free_envelope();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api, const FOD: bool> Envelope<Api, FOD> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api, const FOD: bool> Envelope<Api, FOD> {
+	/// Sets the attack time, in seconds.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::setAttack`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::setAttack")]
+	pub fn set_attack(&self, attack: c_float) {
+		let f = self.api().set_attack();
+		unsafe { f(self.0, attack) }
+	}
+
+	/// Sets the decay time, in seconds.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::setDecay`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::setDecay")]
+	pub fn set_decay(&self, decay: c_float) {
+		let f = self.api().set_decay();
+		unsafe { f(self.0, decay) }
+	}
+
+	/// Sets the sustain level, as a proportion of the total level (`0.0` to `1.0`).
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::setSustain`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::setSustain")]
+	pub fn set_sustain(&self, sustain: c_float) {
+		let f = self.api().set_sustain();
+		unsafe { f(self.0, sustain) }
+	}
+
+	/// Sets the release time, in seconds.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::setRelease`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::setRelease")]
+	pub fn set_release(&self, release: c_float) {
+		let f = self.api().set_release();
+		unsafe { f(self.0, release) }
+	}
+
+	/// Sets whether to use legato phrasing for the envelope.
+	///
+	/// If the legato flag is set, when the envelope is re-triggered before it's released,
+	/// it remains in the sustain phase instead of jumping back to the attack phase.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::setLegato`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::setLegato")]
+	pub fn set_legato(&self, value: bool) {
+		let f = self.api().set_legato();
+		unsafe { f(self.0, value.into()) }
+	}
+
+	/// If retrigger is on, the envelope always starts from `0` when a note starts playing,
+	/// instead of the current value if it's active.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::setRetrigger`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::setRetrigger")]
+	pub fn set_retrigger(&self, value: bool) {
+		let f = self.api().set_retrigger();
+		unsafe { f(self.0, value.into()) }
+	}
+
+	/// Smoothly changes the envelope's shape from linear (`0.0`) to exponential (`1.0`).
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::setCurvature`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::setCurvature")]
+	pub fn set_curvature(&self, amount: c_float) {
+		let f = self.api().set_curvature();
+		unsafe { f(self.0, amount) }
+	}
+
+	/// Changes the amount by which note velocity scales output level.
+	///
+	/// At the default value of `1.0`, output is proportional to velocity;
+	/// at `0.0` velocity has no effect on output level.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::setVelocitySensitivity`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::setVelocitySensitivity")]
+	pub fn set_velocity_sensitivity(&self, sensitivity: c_float) {
+		let f = self.api().set_velocity_sensitivity();
+		unsafe { f(self.0, sensitivity) }
+	}
+
+	/// Scales the envelope rate according to the played note.
+	///
+	/// For notes below `start`, the envelope's set rate is used;
+	/// for notes above `end` envelope rates are scaled by the `scaling` parameter.
+	/// Between the two notes the scaling factor is interpolated from `1.0` to `scaling`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::setRateScaling`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::setRateScaling")]
+	pub fn set_rate_scaling(&self, scaling: c_float, start: MIDINote, end: MIDINote) {
+		let f = self.api().set_rate_scaling();
+		unsafe { f(self.0, scaling, start, end) }
+	}
+
+	/// Returns the current output value of the envelope.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_envelope::getValue`]
+	#[doc(alias = "sys::ffi::playdate_sound_envelope::getValue")]
+	pub fn value(&self) -> c_float {
+		let f = self.api().get_value();
+		unsafe { f(self.0) }
+	}
+}
@@ -1,4 +1,6 @@
 use core::fmt;
+use sys::ffi::{CString, CStr};
+use alloc::borrow::ToOwned;
 
 
 pub type ApiError = sys::error::Error<self::Error>;
@@ -14,6 +16,17 @@ pub enum Error {
 
 	/// Error caused by the file system.
 	Fs(fs::error::Error),
+
+	/// Error reported by the sound engine.
+	///
+	/// See [`Sound::get_error`](crate::Sound::get_error).
+	Sound(CString),
+
+	/// Microphone recording could not be started.
+	Mic,
+
+	/// Audio data doesn't match its format, e.g. length is not a multiple of the frame size.
+	InvalidData,
 }
 
 impl fmt::Display for Error {
@@ -21,7 +34,15 @@ impl fmt::Display for Error {
 		match &self {
 			Error::Alloc => write!(f, "Snd: Allocation failed"),
 			Error::FileNotExist => write!(f, "Snd: File doesn't exist"),
+			Error::Mic => write!(f, "Snd: Unable to start mic recording"),
+			Error::InvalidData => write!(f, "Snd: Invalid audio data"),
 			Error::Fs(err) => err.fmt(f),
+			Error::Sound(cs) => {
+				match cs.to_str() {
+					Ok(err) => write!(f, "Snd: {err}"),
+					Err(_) => write!(f, "Snd: {cs:?}"),
+				}
+			},
 		}
 	}
 }
@@ -37,3 +58,18 @@ impl From<fs::error::Error> for Error {
 
 
 impl core::error::Error for Error {}
+
+
+impl Error {
+	pub(crate) fn sound_from(c: &CStr) -> Self { Self::Sound(c.to_owned()) }
+
+	/// Returns the most recent error, if any, using the `getError` function of the api end-point.
+	pub(crate) fn last_from(get_error: unsafe extern "C" fn() -> *const core::ffi::c_char) -> Option<Self> {
+		let ptr = unsafe { get_error() };
+		if ptr.is_null() {
+			None
+		} else {
+			Some(Self::sound_from(unsafe { CStr::from_ptr(ptr) }))
+		}
+	}
+}
@@ -0,0 +1,126 @@
+//! Playdate headphones API
+
+use alloc::boxed::Box;
+use core::ffi::c_int;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+
+/// State of the headphones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct HeadphoneState {
+	/// Headphones are currently plugged in.
+	pub headphone: bool,
+	/// Plugged in headphones include a microphone.
+	pub mic: bool,
+}
+
+impl HeadphoneState {
+	pub(crate) fn from_raw(headphone: c_int, mic: c_int) -> Self {
+		Self { headphone: headphone != 0,
+		       mic: mic != 0 }
+	}
+}
+
+
+static mut STORE: Option<Box<dyn FnMut(HeadphoneState)>> = None;
+
+/// Generation of the currently registered callback.
+///
+/// Used to prevent unregistering a newer callback when older [`HeadphoneStateCallback`] dropped.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+
+unsafe extern "C" fn proxy_headphone_state_change(headphone: c_int, mic: c_int) {
+	let store = &mut *core::ptr::addr_of_mut!(STORE);
+	if let Some(f) = store.as_mut() {
+		f(HeadphoneState::from_raw(headphone, mic))
+	}
+}
+
+
+/// Returns the proxy of the registered closure, if any.
+///
+/// Should be passed to the `getHeadphoneState` to keep the closure registered.
+pub(crate) fn registered_callback() -> Option<unsafe extern "C" fn(headphone: c_int, mic: c_int)> {
+	let store = unsafe { &*core::ptr::addr_of!(STORE) };
+	store.is_some().then_some(proxy_headphone_state_change)
+}
+
+
+/// Registered headphone state change callback.
+///
+/// While registered, audio output will __not__ automatically switch
+/// from speaker to headphones when headphones are plugged in (and vice versa),
+/// so the callback should use [`Sound::set_outputs_active`](crate::Sound::set_outputs_active)
+/// to change the output if needed.
+///
+/// Callback is unregistered on drop.
+#[must_use = "Callback is unregistered when dropped"]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct HeadphoneStateCallback<Api: crate::api::Api = crate::api::Default> {
+	generation: usize,
+	api: Api,
+}
+
+
+// ctor //
+
+impl<Api: crate::api::Api> HeadphoneStateCallback<Api> {
+	/// Registers the `callback`, it will be called when the headset or mic status changes.
+	///
+	/// Replaces previously registered callback.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound::getHeadphoneState`]
+	#[doc(alias = "sys::ffi::playdate_sound::getHeadphoneState")]
+	pub fn new<F>(callback: F) -> Self
+		where F: 'static + FnMut(HeadphoneState),
+		      Api: Default {
+		let api = Api::default();
+		Self::new_with(api, callback)
+	}
+
+	/// Registers the `callback` with given `api`,
+	/// it will be called when the headset or mic status changes.
+	///
+	/// Replaces previously registered callback.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound::getHeadphoneState`]
+	#[doc(alias = "sys::ffi::playdate_sound::getHeadphoneState")]
+	pub fn new_with<F>(api: Api, callback: F) -> Self
+		where F: 'static + FnMut(HeadphoneState) {
+		use core::ptr::null_mut;
+
+		let f = api.get_headphone_state();
+
+		// Unregister previous, then store new one and register it.
+		unsafe { f(null_mut(), null_mut(), None) };
+		unsafe { STORE = Some(Box::new(callback)) };
+		let generation = CURRENT.fetch_add(1, Ordering::SeqCst) + 1;
+		unsafe { f(null_mut(), null_mut(), Some(proxy_headphone_state_change)) };
+
+		Self { generation, api }
+	}
+}
+
+
+impl<Api: crate::api::Api> Drop for HeadphoneStateCallback<Api> {
+	fn drop(&mut self) {
+		if CURRENT.load(Ordering::SeqCst) == self.generation {
+			use core::ptr::null_mut;
+
+			// Unregister firstly, then clear the `STORE`.
+			let f = self.api.get_headphone_state();
+			unsafe { f(null_mut(), null_mut(), None) };
+			unsafe { STORE = None };
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: crate::api::Api> HeadphoneStateCallback<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.api }
+}
@@ -0,0 +1,258 @@
+//! Playdate synth instrument API
+
+use core::ffi::c_float;
+use core::ffi::c_int;
+
+use sys::ffi::MIDINote;
+use sys::ffi::PDSynthInstrument;
+use sys::ffi::SoundSource as OpaqueSoundSource;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::source::AsSoundSource;
+use crate::synth::Synth;
+
+pub mod api;
+
+
+/// Collection of [`Synth`] voices, that can be assigned to a [`Track`](crate::track::Track).
+///
+/// Owned instrument (`FREE_ON_DROP = true`) frees the C-part on drop,
+/// instruments returned by tracks are not owned and will not be freed.
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Instrument<Api: api::Api = api::Default, const FREE_ON_DROP: bool = true>(*mut PDSynthInstrument, Api);
+
+impl<Api: api::Api, const FOD: bool> AsRaw for Instrument<Api, FOD> {
+	type Type = PDSynthInstrument;
+	unsafe fn as_raw(&self) -> *mut PDSynthInstrument { self.0 }
+}
+
+impl<Api: api::Api, const FOD: bool> AsSoundSource for Instrument<Api, FOD> {
+	unsafe fn as_source_raw(&self) -> *mut OpaqueSoundSource { self.0 as _ }
+}
+
+impl<Api: api::Api + Default, const FOD: bool> From<*mut PDSynthInstrument> for Instrument<Api, FOD> {
+	fn from(ptr: *mut PDSynthInstrument) -> Self { Self(ptr, Api::default()) }
+}
+
+impl<Api: api::Api, const FOD: bool> Instrument<Api, FOD> {
+	/// Wraps the raw `ptr` to the instrument with given `api`.
+	///
+	/// # Safety
+	/// `ptr` must point to a valid instrument.
+	/// If `FREE_ON_DROP` is `true`, the wrapper takes ownership of it and frees it on drop,
+	/// otherwise the instrument must outlive the wrapper.
+	pub unsafe fn from_with(api: Api, ptr: *mut PDSynthInstrument) -> Self { Self(ptr, api) }
+}
+
+impl<Api: api::Api> Instrument<Api, true> {
+	/// Convert this instrument into the same instrument that will not be freed on drop.
+	/// That means that only C-part of the instrument will __not__ be freed.
+	///
+	/// __Safety is guaranteed by the caller.__
+	pub fn into_shared(mut self) -> Instrument<Api, false>
+		where Api: Copy {
+		let res = Instrument(self.0, self.1);
+		self.0 = core::ptr::null_mut();
+		res
+	}
+}
+
+
+// ctor //
+
+impl<Api> Instrument<Api, true> where Api: api::Api {
+	/// Creates a new instrument.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::newInstrument`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::newInstrument")]
+	pub fn new() -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new instrument with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::newInstrument`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::newInstrument")]
+	pub fn new_with(api: Api) -> Result<Self, Error> {
+		let f = api.new_instrument();
+		let instrument = unsafe { f() };
+		if instrument.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(Instrument(instrument, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api, const FOD: bool> Drop for Instrument<Api, FOD> {
+	fn drop(&mut self) {
+		if FOD && !self.0.is_null() {
+			let f = self.api().free_instrument();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api, const FOD: bool> Instrument<Api, FOD> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api, const FOD: bool> Instrument<Api, FOD> {
+	/// Adds the given `synth` to the instrument.
+	///
+	/// The synth will respond to play note events between `range_start` and `range_end`, inclusive.
+	/// The `transpose` argument is in half-step units,
+	/// and is added to the instrument's [transpose](Instrument::set_transpose) parameter.
+	///
+	/// The instrument takes ownership of the `synth`.
+	///
+	/// Returns error if the synth is already in another instrument or channel.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::addVoice`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::addVoice")]
+	pub fn add_voice<SApi: crate::synth::api::Api>(&self,
+	                                               synth: Synth<SApi>,
+	                                               range_start: MIDINote,
+	                                               range_end: MIDINote,
+	                                               transpose: c_float)
+	                                               -> Result<(), Error> {
+		let f = self.api().add_voice();
+		let ptr = unsafe { synth.as_raw() };
+		if unsafe { f(self.0, ptr, range_start, range_end, transpose) } == 1 {
+			core::mem::forget(synth);
+			Ok(())
+		} else {
+			Err(Error::last_from(self.api().get_error()).unwrap_or(Error::Alloc))
+		}
+	}
+
+	/// Allocates `count` copies of the `synth` and adds them to the instrument as voices,
+	/// so the instrument can play up to `count` notes simultaneously.
+	///
+	/// See also [`Instrument::add_voice`].
+	pub fn add_voices<SApi: crate::synth::api::Api + Clone>(&self,
+	                                                        synth: &Synth<SApi>,
+	                                                        count: usize,
+	                                                        range_start: MIDINote,
+	                                                        range_end: MIDINote,
+	                                                        transpose: c_float)
+	                                                        -> Result<(), Error> {
+		for _ in 0..count {
+			self.add_voice(synth.try_clone()?, range_start, range_end, transpose)?;
+		}
+		Ok(())
+	}
+
+
+	/// Plays a note at the given `frequency` on the voice
+	/// that has been off for the longest, or has been playing longest if all voices are currently playing.
+	///
+	/// If `len` is `None`, the note keeps playing until a subsequent [`Instrument::note_off`] call.
+	///
+	/// Returns `false` if there is no voice to play the note.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::playNote`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::playNote")]
+	pub fn play_note(&self, frequency: c_float, vel: c_float, len: Option<c_float>, when: u32) -> bool {
+		let f = self.api().play_note();
+		!unsafe { f(self.0, frequency, vel, len.unwrap_or(-1.0), when) }.is_null()
+	}
+
+	/// The same as [`Instrument::play_note`] but uses MIDI note (where `60` = C4) instead of frequency.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::playMIDINote`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::playMIDINote")]
+	pub fn play_midi_note(&self, note: MIDINote, vel: c_float, len: Option<c_float>, when: u32) -> bool {
+		let f = self.api().play_midi_note();
+		!unsafe { f(self.0, note, vel, len.unwrap_or(-1.0), when) }.is_null()
+	}
+
+	/// Forwards the note off event to the voice currently playing the given `note`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::noteOff`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::noteOff")]
+	pub fn note_off(&self, note: MIDINote, when: u32) {
+		let f = self.api().note_off();
+		unsafe { f(self.0, note, when) }
+	}
+
+	/// Sends a note off event to all voices in the instrument.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::allNotesOff`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::allNotesOff")]
+	pub fn all_notes_off(&self, when: u32) {
+		let f = self.api().all_notes_off();
+		unsafe { f(self.0, when) }
+	}
+
+	/// Sets the pitch bend to be applied to the voices in the instrument, as a fraction of the full range.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::setPitchBend`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::setPitchBend")]
+	pub fn set_pitch_bend(&self, bend: c_float) {
+		let f = self.api().set_pitch_bend();
+		unsafe { f(self.0, bend) }
+	}
+
+	/// Sets the pitch bend range for the voices in the instrument.
+	///
+	/// The default range is `12`, for a full octave.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::setPitchBendRange`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::setPitchBendRange")]
+	pub fn set_pitch_bend_range(&self, half_steps: c_float) {
+		let f = self.api().set_pitch_bend_range();
+		unsafe { f(self.0, half_steps) }
+	}
+
+	/// Sets the transpose parameter for all voices in the instrument.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::setTranspose`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::setTranspose")]
+	pub fn set_transpose(&self, half_steps: c_float) {
+		let f = self.api().set_transpose();
+		unsafe { f(self.0, half_steps) }
+	}
+
+	/// Returns the number of voices in the instrument currently playing.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::activeVoiceCount`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::activeVoiceCount")]
+	pub fn active_voice_count(&self) -> c_int {
+		let f = self.api().active_voice_count();
+		unsafe { f(self.0) }
+	}
+
+
+	/// Gets the playback volume for left and right channels of the instrument.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::getVolume`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::getVolume")]
+	pub fn volume(&self) -> (c_float, c_float) {
+		let (mut left, mut right) = (0.0, 0.0);
+		let f = self.api().get_volume();
+		unsafe { f(self.0, &mut left, &mut right) };
+		(left, right)
+	}
+
+	/// Sets the playback volume (`0.0` - `1.0`) for left and right channels of the instrument.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_instrument::setVolume`]
+	#[doc(alias = "sys::ffi::playdate_sound_instrument::setVolume")]
+	pub fn set_volume(&self, left: c_float, right: c_float) {
+		let f = self.api().set_volume();
+		unsafe { f(self.0, left, right) }
+	}
+}
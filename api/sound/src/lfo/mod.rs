@@ -0,0 +1,225 @@
+//! Playdate LFO API
+
+use core::ffi::c_float;
+use core::ffi::c_void;
+use alloc::boxed::Box;
+
+use sys::ffi::PDSynthLFO;
+use sys::ffi::PDSynthSignalValue;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+use crate::signal::Signal;
+
+pub mod api;
+
+
+pub use sys::ffi::LFOType as LfoType;
+
+
+/// Low-frequency oscillator, can be used to modulate sounds.
+pub struct Lfo<Api: api::Api = api::Default>(*mut PDSynthLFO, Api, Option<Box<Box<dyn FnMut() -> c_float>>>);
+
+#[cfg(feature = "bindings-derive-debug")]
+impl<Api: api::Api + core::fmt::Debug> core::fmt::Debug for Lfo<Api> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_tuple("Lfo")
+		 .field(&self.0)
+		 .field(&self.1)
+		 .field(&self.2.is_some())
+		 .finish()
+	}
+}
+
+impl<Api: api::Api> AsRaw for Lfo<Api> {
+	type Type = PDSynthLFO;
+	unsafe fn as_raw(&self) -> *mut PDSynthLFO { self.0 }
+}
+
+impl<Api: api::Api> Signal for Lfo<Api> {
+	unsafe fn as_signal_raw(&self) -> *mut PDSynthSignalValue { self.0 as _ }
+}
+
+
+// ctor //
+
+impl<Api> Lfo<Api> where Api: api::Api {
+	/// Creates a new LFO with given `kind` of shape.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::newLFO`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::newLFO")]
+	pub fn new(kind: LfoType) -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api, kind)
+	}
+
+	/// Creates a new LFO with given `kind` of shape and `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::newLFO`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::newLFO")]
+	pub fn new_with(api: Api, kind: LfoType) -> Result<Self, Error> {
+		let f = api.new_lfo();
+		let lfo = unsafe { f(kind) };
+		if lfo.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(Lfo(lfo, api, None))
+		}
+	}
+}
+
+
+impl<Api: api::Api> Drop for Lfo<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			let f = self.api().free_lfo();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api> Lfo<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api> Lfo<Api> {
+	/// Sets the LFO shape.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setType`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setType")]
+	pub fn set_type(&self, kind: LfoType) {
+		let f = self.api().set_type();
+		unsafe { f(self.0, kind) }
+	}
+
+	/// Sets the LFO's rate, in cycles per second.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setRate`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setRate")]
+	pub fn set_rate(&self, rate: c_float) {
+		let f = self.api().set_rate();
+		unsafe { f(self.0, rate) }
+	}
+
+	/// Sets the LFO's phase, from `0.0` to `1.0`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setPhase`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setPhase")]
+	pub fn set_phase(&self, phase: c_float) {
+		let f = self.api().set_phase();
+		unsafe { f(self.0, phase) }
+	}
+
+	/// Sets the LFO's initial phase, from `0.0` to `1.0`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setStartPhase`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setStartPhase")]
+	pub fn set_start_phase(&self, phase: c_float) {
+		let f = self.api().set_start_phase();
+		unsafe { f(self.0, phase) }
+	}
+
+	/// Sets the center value for the LFO.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setCenter`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setCenter")]
+	pub fn set_center(&self, center: c_float) {
+		let f = self.api().set_center();
+		unsafe { f(self.0, center) }
+	}
+
+	/// Sets the depth of the LFO.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setDepth`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setDepth")]
+	pub fn set_depth(&self, depth: c_float) {
+		let f = self.api().set_depth();
+		unsafe { f(self.0, depth) }
+	}
+
+	/// Sets the LFO type to arpeggio, where the given `steps` are in half-steps from the center note.
+	///
+	/// For example, the sequence `[0.0, 4.0, 7.0, 12.0]` plays the notes of a major chord.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setArpeggiation`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setArpeggiation")]
+	pub fn set_arpeggiation(&self, steps: &[c_float]) {
+		let f = self.api().set_arpeggiation();
+		unsafe { f(self.0, steps.len() as _, steps.as_ptr() as *mut _) }
+	}
+
+	/// Provides a custom function for LFO values.
+	///
+	/// If `interpolate` is set, values are interpolated between the function calls.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setFunction`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setFunction")]
+	pub fn set_function<F>(&mut self, interpolate: bool, func: F)
+		where F: 'static + FnMut() -> c_float {
+		let mut func: Box<Box<dyn FnMut() -> c_float>> = Box::new(Box::new(func));
+		let userdata = func.as_mut() as *mut Box<dyn FnMut() -> c_float> as *mut c_void;
+
+		let f = self.api().set_function();
+		unsafe { f(self.0, Some(proxy_function), userdata, interpolate.into()) };
+		// previous function is not used anymore, so it's safe to drop it:
+		self.2 = Some(func);
+	}
+
+	/// Sets an initial holdoff time for the LFO where the LFO remains at its center value,
+	/// and a ramp time where the value increases linearly to its maximum depth.
+	///
+	/// Values are in seconds.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setDelay`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setDelay")]
+	pub fn set_delay(&self, holdoff: c_float, ramp_time: c_float) {
+		let f = self.api().set_delay();
+		unsafe { f(self.0, holdoff, ramp_time) }
+	}
+
+	/// If retrigger is on, the LFO's phase is reset to its initial phase
+	/// when a synth using the LFO starts playing a note.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setRetrigger`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setRetrigger")]
+	pub fn set_retrigger(&self, value: bool) {
+		let f = self.api().set_retrigger();
+		unsafe { f(self.0, value.into()) }
+	}
+
+	/// If `global` is set, the LFO is continuously updated whether or not it's currently in use.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::setGlobal`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::setGlobal")]
+	pub fn set_global(&self, global: bool) {
+		let f = self.api().set_global();
+		unsafe { f(self.0, global.into()) }
+	}
+
+	/// Returns the current output value of the LFO.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_lfo::getValue`]
+	#[doc(alias = "sys::ffi::playdate_sound_lfo::getValue")]
+	pub fn value(&self) -> c_float {
+		let f = self.api().get_value();
+		unsafe { f(self.0) }
+	}
+}
+
+
+unsafe extern "C" fn proxy_function(_: *mut PDSynthLFO, userdata: *mut c_void) -> c_float {
+	if let Some(func) = (userdata as *mut Box<dyn FnMut() -> c_float>).as_mut() {
+		func()
+	} else {
+		0.0
+	}
+}
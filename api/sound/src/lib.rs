@@ -10,20 +10,45 @@ use sys::traits::AsRaw;
 extern crate sys;
 extern crate alloc;
 
+pub mod borrow;
+pub mod channel;
+pub mod control;
+pub mod effect;
+pub mod envelope;
 pub mod error;
+pub mod headphone;
+pub mod instrument;
+pub mod lfo;
+pub mod mic;
 pub mod player;
 pub mod sample;
+pub mod sequence;
+pub mod signal;
 pub mod source;
+pub mod synth;
+pub mod track;
 
-// TODO: Sound api: channel, synth, sequence, effect, lfo, envelope, callbacks, etc..
+// TODO: Sound api: callbacks, etc..
 
 
 pub mod prelude {
 	pub use crate::error::ApiError as SndApiError;
 	pub use crate::error::Error as SndError;
 
+	pub use crate::channel;
+	pub use crate::control;
+	pub use crate::effect;
+	pub use crate::envelope;
+	pub use crate::headphone;
+	pub use crate::instrument;
+	pub use crate::lfo;
+	pub use crate::mic;
 	pub use crate::player;
 	pub use crate::sample;
+	pub use crate::sequence;
+	pub use crate::signal;
+	pub use crate::synth;
+	pub use crate::track;
 }
 
 
@@ -87,6 +112,40 @@ impl<Api: api::Api> Sound<Api> {
 		self.set_headphone_state_change_callback(headphone, mic, None)
 	}
 
+	/// Returns current state of the headphones.
+	///
+	/// Callback registered with [`Sound::on_headphone_state_change`] stays registered,
+	/// but a raw callback set with [`Sound::set_headphone_state_change_callback`] is cleared,
+	/// as with [`Sound::headphone_state`].
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound::getHeadphoneState`]
+	#[doc(alias = "sys::ffi::playdate_sound::getHeadphoneState")]
+	pub fn headphones(&self) -> headphone::HeadphoneState {
+		let mut headphone = 0;
+		let mut mic = 0;
+		// Pass the registered proxy back, because `null` unregisters the current callback.
+		let callback = headphone::registered_callback();
+		self.set_headphone_state_change_callback(Some(&mut headphone), Some(&mut mic), callback);
+		headphone::HeadphoneState::from_raw(headphone, mic)
+	}
+
+	/// Registers the `callback`, it will be called with the new [`HeadphoneState`](headphone::HeadphoneState)
+	/// when the headset or mic status changes.
+	///
+	/// While registered, audio output will __not__ automatically switch from speaker to headphones
+	/// when headphones are plugged in (and vice versa),
+	/// so the callback should use [`Sound::set_outputs_active`] to change the output if needed.
+	///
+	/// Callback is unregistered when returned [`HeadphoneStateCallback`](headphone::HeadphoneStateCallback) is dropped.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound::getHeadphoneState`] with the `changeCallback`
+	#[doc(alias = "sys::ffi::playdate_sound::getHeadphoneState")]
+	pub fn on_headphone_state_change<F>(&self, callback: F) -> headphone::HeadphoneStateCallback<Api>
+		where F: 'static + FnMut(headphone::HeadphoneState),
+		      Api: Clone {
+		headphone::HeadphoneStateCallback::new_with(self.0.clone(), callback)
+	}
+
 	/// If `headphone` is `Some`, the value is set to 1 if headphones are currently plugged in.
 	///
 	/// Likewise, `mic` is set if the headphones include a microphone.
@@ -96,6 +155,8 @@ impl<Api: api::Api> Sound<Api> {
 	///
 	/// In this case, the callback should use [`Sound::set_outputs_active`] to change the output if needed.
 	///
+	/// See also [`Sound::on_headphone_state_change`] to register a closure.
+	///
 	/// Equivalent to [`sys::ffi::playdate_sound::getHeadphoneState`]
 	#[doc(alias = "sys::ffi::playdate_sound::getHeadphoneState")]
 	pub fn set_headphone_state_change_callback(&self,
@@ -115,6 +176,32 @@ impl<Api: api::Api> Sound<Api> {
 		}
 	}
 
+	/// Returns [`Error`](error::Error) with text describing the most recent error.
+	///
+	/// See also [`Sound::get_error_cstr`].
+	///
+	/// Calls [`sys::ffi::playdate_sound::getError`].
+	#[must_use = "Error message is borrowed from C part, must be used immediately or converted to owned string."]
+	#[inline(always)]
+	pub fn get_error(&self) -> Option<error::Error> { self.get_error_cstr().map(error::Error::sound_from) }
+
+	/// Returns [`CStr`](core::ffi::CStr) describing the most recent error.
+	///
+	/// String-slice is borrowed by C, so it should be used immediately or converted to something owned.
+	///
+	/// Calls [`sys::ffi::playdate_sound::getError`].
+	#[doc(alias = "sys::ffi::playdate_sound::getError")]
+	#[must_use = "Error message is borrowed from C part, must be used immediately or converted to owned string."]
+	pub fn get_error_cstr(&self) -> Option<&core::ffi::CStr> {
+		let f = self.0.get_error();
+		let ptr = unsafe { f() };
+		if ptr.is_null() {
+			None
+		} else {
+			unsafe { core::ffi::CStr::from_ptr(ptr as _) }.into()
+		}
+	}
+
 	/// Force audio output to the given outputs, regardless of headphone status.
 	///
 	/// Equivalent to [`sys::ffi::playdate_sound::setOutputsActive`]
@@ -158,50 +245,75 @@ impl<Api: api::Api> Sound<Api> {
 	}
 
 
-	// /// Returns the default channel, where sound sources play
-	// /// if they haven’t been explicitly assigned to a different channel.
-	// ///
-	// /// Equivalent to [`sys::ffi::playdate_sound::getDefaultChannel`]
-	// #[doc(alias = "sys::ffi::playdate_sound::getDefaultChannel")]
-	// pub fn default_channel(&self) -> *mut SoundChannel {
-	// 	let f = self.0.get_default_channel();
-	// 	unsafe { f() }
-	// }
-
-	// /// Adds the given channel to the sound engine.
-	// ///
-	// /// Returns 1 if the channel was added, 0 if it was already in the engine.
-	// ///
-	// /// Equivalent to [`sys::ffi::playdate_sound::addChannel`]
-	// #[doc(alias = "sys::ffi::playdate_sound::addChannel")]
-	// pub fn add_channel(&self, channel: *mut SoundChannel) -> c_int {
-	// 	let f = self.0.add_channel();
-	// 	unsafe { f(channel) }
-	// }
-
-	// /// Removes the given channel from the sound engine.
-	// ///
-	// /// Returns 1 if the channel was successfully removed, 0 if the channel is the default channel or hadn’t been previously added.
-	// ///
-	// /// Equivalent to [`sys::ffi::playdate_sound::removeChannel`]
-	// #[doc(alias = "sys::ffi::playdate_sound::removeChannel")]
-	// pub fn remove_channel(&self, channel: *mut SoundChannel) -> c_int {
-	// 	let f = self.0.remove_channel();
-	// 	unsafe { f(channel) }
-	// }
-
-	// /// The callback you pass in will be called every audio cycle.
-	// ///
-	// /// Equivalent to [`sys::ffi::playdate_sound::setMicCallback`]
-	// #[doc(alias = "sys::ffi::playdate_sound::setMicCallback")]
-	// pub fn set_mic_callback(&self, callback: RecordCallback, context: *mut c_void, force_internal: c_int) {
-	// 	let f = self.0.set_mic_callback();
-	// 	unsafe { f(callback, context, force_internal) }
-	// }
+	/// Returns the default channel, where sound sources play
+	/// if they haven’t been explicitly assigned to a different channel.
+	///
+	/// Returned channel is owned by the sound engine, so it will __not__ be freed on drop.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound::getDefaultChannel`]
+	#[doc(alias = "sys::ffi::playdate_sound::getDefaultChannel")]
+	pub fn default_channel(&self) -> channel::Channel<channel::api::Default, false> {
+		let f = self.0.get_default_channel();
+		unsafe { f() }.into()
+	}
+
+	/// Adds the given [`channel`](channel::Channel) to the sound engine.
+	///
+	/// Returns `true` if the channel was added, `false` if it was already in the engine.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound::addChannel`]
+	#[doc(alias = "sys::ffi::playdate_sound::addChannel")]
+	pub fn add_channel<CApi: channel::api::Api, const FOD: bool>(&self,
+	                                                             channel: &channel::Channel<CApi, FOD>)
+	                                                             -> bool {
+		let f = self.0.add_channel();
+		unsafe { f(channel.as_raw()) == 1 }
+	}
+
+	/// Removes the given [`channel`](channel::Channel) from the sound engine.
+	///
+	/// Returns `true` if the channel was successfully removed,
+	/// `false` if the channel is the default channel or hadn’t been previously added.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound::removeChannel`]
+	#[doc(alias = "sys::ffi::playdate_sound::removeChannel")]
+	pub fn remove_channel<CApi: channel::api::Api, const FOD: bool>(&self,
+	                                                                channel: &channel::Channel<CApi, FOD>)
+	                                                                -> bool {
+		let f = self.0.remove_channel();
+		unsafe { f(channel.as_raw()) == 1 }
+	}
+
+	/// Starts recording from the microphone,
+	/// the `callback` you pass in will be called every audio cycle with the recorded data.
+	///
+	/// If `force_internal` is set, the device mic is used even when a headset with a mic is plugged in,
+	/// otherwise the headset mic is used if detected.
+	///
+	/// Recording stops when returned [`Mic`](mic::Mic) is dropped or the `callback` returns `false`.
+	///
+	/// See also [`Mic::start`](mic::Mic::start) for other sources.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound::setMicCallback`]
+	#[doc(alias = "sys::ffi::playdate_sound::setMicCallback")]
+	pub fn set_mic_callback<F: mic::MicInput>(&self,
+	                                          callback: F,
+	                                          force_internal: bool)
+	                                          -> Result<mic::Mic<F, Api>, error::Error>
+		where Api: Clone
+	{
+		let source = if force_internal {
+			mic::MicSource::kMicInputInternal
+		} else {
+			mic::MicSource::kMicInputAutodetect
+		};
+		mic::Mic::start_with(self.0.clone(), source, callback)
+	}
 }
 
 
 pub mod api {
+	use core::ffi::c_char;
 	use core::ffi::c_int;
 	use core::ffi::c_void;
 	use core::ptr::NonNull;
@@ -340,6 +452,8 @@ pub mod api {
 		fn remove_source(&self) -> unsafe extern "C" fn(source: *mut SoundSource) -> c_int {
 			self.0.removeSource.expect("removeSource")
 		}
+
+		fn get_error(&self) -> unsafe extern "C" fn() -> *const c_char { self.0.getError.expect("getError") }
 	}
 
 
@@ -420,5 +534,9 @@ pub mod api {
 		fn remove_source(&self) -> unsafe extern "C" fn(source: *mut SoundSource) -> c_int {
 			*sys::api!(sound.removeSource)
 		}
+
+		/// Returns [`sys::ffi::playdate_sound::getError`]
+		#[doc(alias = "sys::ffi::playdate_sound::getError")]
+		fn get_error(&self) -> unsafe extern "C" fn() -> *const c_char { *sys::api!(sound.getError) }
 	}
 }
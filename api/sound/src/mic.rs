@@ -0,0 +1,237 @@
+//! Playdate microphone API
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ffi::c_float;
+use core::ffi::c_int;
+use core::ffi::c_void;
+use core::sync::atomic::AtomicPtr;
+use core::sync::atomic::Ordering;
+
+use crate::error::Error;
+use crate::sample::Channels;
+use crate::sample::Sample;
+use crate::sample::SampleWithData;
+
+pub use sys::ffi::MicSource;
+
+
+/// Sample rate of the recorded audio data.
+pub const SAMPLE_RATE: u32 = 44100;
+
+
+/// Context of the currently registered mic callback.
+///
+/// Used to prevent unregistering a newer callback when older [`Mic`] dropped.
+static CURRENT: AtomicPtr<c_void> = AtomicPtr::new(core::ptr::null_mut());
+
+
+/// Receiver of the recorded audio data, a monophonic stream of samples.
+///
+/// Implemented for `FnMut(&[i16]) -> bool` closures and [`Recording`].
+pub trait MicInput: 'static {
+	/// Called every audio cycle with the recorded `data`.
+	///
+	/// Should return `true` to continue recording, `false` to stop.
+	fn input(&mut self, data: &[i16]) -> bool;
+}
+
+impl<F: FnMut(&[i16]) -> bool + 'static> MicInput for F {
+	fn input(&mut self, data: &[i16]) -> bool { self(data) }
+}
+
+
+/// Active microphone recording.
+///
+/// Recording is stopped and the callback is unregistered on drop.
+///
+/// Only one recording can be active at a time,
+/// so starting a new one replaces the previous.
+/// In that case dropping the replaced `Mic` only frees its callback.
+#[must_use = "Recording stops when `Mic` is dropped"]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Mic<F: MicInput, Api: crate::api::Api = crate::api::Default> {
+	callback: *mut F,
+	source: MicSource,
+	api: Api,
+}
+
+
+// ctor //
+
+impl<F: MicInput, Api: crate::api::Api> Mic<F, Api> {
+	/// Starts recording from the given `source`,
+	/// passing recorded data to the `callback` every audio cycle.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound::setMicCallback`]
+	#[doc(alias = "sys::ffi::playdate_sound::setMicCallback")]
+	pub fn start(source: MicSource, callback: F) -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::start_with(api, source, callback)
+	}
+
+	/// Starts recording from the given `source` with given `api`,
+	/// passing recorded data to the `callback` every audio cycle.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound::setMicCallback`]
+	#[doc(alias = "sys::ffi::playdate_sound::setMicCallback")]
+	pub fn start_with(api: Api, source: MicSource, callback: F) -> Result<Self, Error> {
+		let callback = Box::into_raw(Box::new(callback));
+
+		let f = api.set_mic_callback();
+		let result = unsafe { f(Some(proxy::<F>), callback as _, source) };
+
+		let source = match result {
+			1 => MicSource::kMicInputInternal,
+			2 => MicSource::kMicInputHeadset,
+			_ => {
+				// Not published, so the current registration, if any, is untouched.
+				drop(unsafe { Box::from_raw(callback) });
+				return Err(Error::last_from(api.get_error()).unwrap_or(Error::Mic));
+			},
+		};
+
+		// Publish only after successful registration, replacing the previous one:
+		CURRENT.store(callback as _, Ordering::SeqCst);
+
+		Ok(Self { callback,
+		          source,
+		          api })
+	}
+}
+
+
+impl<F: MicInput, Api: crate::api::Api> Drop for Mic<F, Api> {
+	fn drop(&mut self) {
+		if !self.callback.is_null() {
+			self.unregister();
+			drop(unsafe { Box::from_raw(self.callback) });
+			self.callback = core::ptr::null_mut();
+		}
+	}
+}
+
+
+unsafe extern "C" fn proxy<F: MicInput>(context: *mut c_void, buffer: *mut i16, length: c_int) -> c_int {
+	if context.is_null() || buffer.is_null() || length <= 0 {
+		return 1;
+	}
+	let callback = &mut *(context as *mut F);
+	let data = core::slice::from_raw_parts(buffer as *const i16, length as _);
+	callback.input(data).into()
+}
+
+
+// utils //
+
+impl<F: MicInput, Api: crate::api::Api> Mic<F, Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.api }
+
+	/// Returns the source actually used for recording, internal or headset mic.
+	pub fn source(&self) -> MicSource { self.source.clone() }
+
+	/// Unregisters the callback if it is still the current one.
+	fn unregister(&self) {
+		let current = self.callback as *mut c_void;
+		let unset = CURRENT.compare_exchange(current, core::ptr::null_mut(), Ordering::SeqCst, Ordering::SeqCst);
+		if unset.is_ok() {
+			let f = self.api.set_mic_callback();
+			unsafe { f(None, core::ptr::null_mut(), MicSource::kMicInputAutodetect) };
+		}
+	}
+}
+
+
+// impl //
+
+impl<F: MicInput, Api: crate::api::Api> Mic<F, Api> {
+	/// Stops recording and returns the callback,
+	/// e.g. to get the recorded data out of the [`Recording`].
+	pub fn stop(mut self) -> F {
+		self.unregister();
+		let callback = unsafe { Box::from_raw(self.callback) };
+		self.callback = core::ptr::null_mut();
+		*callback
+	}
+}
+
+
+/// Buffer accumulating recorded audio data up to the configured length.
+///
+/// Recording stops automatically when the buffer is full.
+///
+/// Note that the buffer grows during recording, in the audio callback.
+/// To avoid allocations there, use [`Recording::with_capacity`].
+#[derive(Debug, Clone)]
+pub struct Recording {
+	data: Vec<i16>,
+	limit: usize,
+}
+
+impl Recording {
+	/// Creates an empty recording limited by `limit` samples.
+	pub const fn new(limit: usize) -> Self {
+		Self { data: Vec::new(),
+		       limit }
+	}
+
+	/// Creates an empty recording limited by `limit` samples,
+	/// with pre-allocated buffer for all of them.
+	pub fn with_capacity(limit: usize) -> Self {
+		Self { data: Vec::with_capacity(limit),
+		       limit }
+	}
+
+	/// Creates an empty recording limited by `seconds` of audio,
+	/// with pre-allocated buffer for all of them.
+	pub fn with_duration(seconds: c_float) -> Self {
+		Self::with_capacity((seconds * SAMPLE_RATE as c_float) as usize)
+	}
+
+	/// Maximum number of samples in this recording.
+	pub fn limit(&self) -> usize { self.limit }
+
+	/// Returns `true` if the recording reached its limit.
+	pub fn is_full(&self) -> bool { self.data.len() >= self.limit }
+
+	/// Returns recorded samples.
+	pub fn data(&self) -> &[i16] { &self.data }
+
+	/// Returns length of the recorded audio, in seconds.
+	pub fn length(&self) -> c_float { self.data.len() as c_float / SAMPLE_RATE as c_float }
+
+	/// Drops recorded samples, keeping the limit.
+	pub fn clear(&mut self) { self.data.clear() }
+
+	/// Returns recorded samples.
+	pub fn into_inner(self) -> Vec<i16> { self.data }
+
+	/// Returns a new [`Sample`] referencing the recorded data,
+	/// 16 bit mono at [`SAMPLE_RATE`].
+	///
+	/// See also [`Sample::from_pcm_i16`].
+	pub fn sample(&mut self) -> Result<SampleWithData<'_, crate::sample::api::Default>, Error> {
+		self.sample_with(Default::default())
+	}
+
+	/// Returns a new [`Sample`] referencing the recorded data with given `api`,
+	/// 16 bit mono at [`SAMPLE_RATE`].
+	///
+	/// See also [`Sample::from_pcm_i16_with`].
+	pub fn sample_with<Api: crate::sample::api::Api>(&mut self,
+	                                                 api: Api)
+	                                                 -> Result<SampleWithData<'_, Api>, Error> {
+		Sample::from_pcm_i16_with(api, &mut self.data, Channels::Mono, SAMPLE_RATE)
+	}
+}
+
+impl MicInput for Recording {
+	fn input(&mut self, data: &[i16]) -> bool {
+		let remaining = self.limit.saturating_sub(self.data.len());
+		let len = data.len().min(remaining);
+		self.data.extend_from_slice(&data[..len]);
+		!self.is_full()
+	}
+}
@@ -2,22 +2,33 @@ use core::ffi::c_char;
 use core::ffi::c_float;
 use core::ffi::c_int;
 use core::ffi::c_void;
+use core::mem::ManuallyDrop;
+use alloc::boxed::Box;
 
 use sys::ffi::CString;
 use sys::ffi::FilePlayer;
+use sys::ffi::SoundSource as OpaqueSoundSource;
 use sys::ffi::sndCallbackProc;
 
 use fs::Path;
 
+use super::Callback;
+use super::Callbacks;
+use super::FromRaw;
 use super::Repeat;
 use crate::error::ApiError;
 use crate::error::Error;
+use crate::source::AsSoundSource;
 
 pub mod api;
+pub mod stream;
 
 
 #[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
-pub struct Player<Api: api::Api = api::Default>(*mut FilePlayer, Api);
+pub struct Player<Api: api::Api = api::Default>(*mut FilePlayer,
+                                                Api,
+                                                Callbacks<Self, Api>,
+                                                Option<stream::Stream>);
 
 
 // ctor //
@@ -35,12 +46,24 @@ impl<Api> Player<Api> where Api: api::Api {
 		if player.is_null() {
 			Err(Error::Alloc)
 		} else {
-			Ok(Player(player, api))
+			Ok(Player(player, api, Default::default(), None))
 		}
 	}
 }
 
 
+impl<Api: api::Api> AsSoundSource for Player<Api> {
+	unsafe fn as_source_raw(&self) -> *mut OpaqueSoundSource { self.0 as _ }
+}
+
+
+impl<Api: api::Api> FromRaw<Api> for Player<Api> {
+	unsafe fn from_raw(ptr: *mut OpaqueSoundSource, api: Api) -> ManuallyDrop<Self> {
+		ManuallyDrop::new(Self(ptr as _, api, Default::default(), None))
+	}
+}
+
+
 impl<Api: api::Api> Drop for Player<Api> {
 	fn drop(&mut self) {
 		if !self.0.is_null() {
@@ -222,7 +245,24 @@ impl<Api> Player<Api> where Api: api::Api {
 
 	// callbacks //
 
-	// TODO: rustify this functions
+	/// Sets a `callback` to be called when playback has completed.
+	///
+	/// The `callback` is owned by the player and freed on drop, or when replaced.
+	/// If `callback` is `None`, the current one is cleared.
+	///
+	/// Equivalent to [setFinishCallback](sys::ffi::playdate_sound_fileplayer::setFinishCallback)
+	#[doc(alias = "sys::ffi::playdate_sound_fileplayer::setFinishCallback")]
+	pub fn on_finish<F>(&mut self, callback: Option<F>)
+		where F: 'static + FnMut(&Self),
+		      Api: Clone {
+		let f = self.api().set_finish_callback();
+		let mut callback = callback.map(|f| Callback::new(self.api().clone(), f));
+		let (proxy, userdata) = callback.as_mut()
+		                                .map_or((None, core::ptr::null_mut()), Callback::as_raw);
+		unsafe { f(self.0, proxy, userdata) };
+		// Register firstly, then drop previous one.
+		self.2.finish = callback;
+	}
 
 	/// Sets a function to be called when playback has completed.
 	///
@@ -232,30 +272,85 @@ impl<Api> Player<Api> where Api: api::Api {
 	#[doc(alias = "sys::ffi::playdate_sound_fileplayer::setFinishCallback")]
 	pub fn set_finish_callback(&self, callback: sndCallbackProc) {
 		let f = self.api().set_finish_callback();
-		// TODO: use userdata
 		unsafe { f(self.0, callback, core::ptr::null_mut()) }
 	}
 
+	/// Sets a `callback` to be called every time the file loops.
+	///
+	/// The `callback` is owned by the player and freed on drop, or when replaced.
+	/// If `callback` is `None`, the current one is cleared.
+	///
+	/// Equivalent to [setLoopCallback](sys::ffi::playdate_sound_fileplayer::setLoopCallback)
+	#[doc(alias = "sys::ffi::playdate_sound_fileplayer::setLoopCallback")]
+	pub fn on_loop<F>(&mut self, callback: Option<F>)
+		where F: 'static + FnMut(&Self),
+		      Api: Clone {
+		let f = self.api().set_loop_callback();
+		let mut callback = callback.map(|f| Callback::new(self.api().clone(), f));
+		let (proxy, userdata) = callback.as_mut()
+		                                .map_or((None, core::ptr::null_mut()), Callback::as_raw);
+		unsafe { f(self.0, proxy, userdata) };
+		// Register firstly, then drop previous one.
+		self.2.looped = callback;
+	}
+
 	/// Equivalent to [setLoopCallback](sys::ffi::playdate_sound_fileplayer::setLoopCallback)
 	#[doc(alias = "sys::ffi::playdate_sound_fileplayer::setLoopCallback")]
 	pub fn set_loop_callback(&self, callback: sndCallbackProc) {
 		let f = self.api().set_loop_callback();
-		// TODO: use userdata
 		unsafe { f(self.0, callback, core::ptr::null_mut()) }
 	}
 
 	/// Changes the volume of the [`Player`] to `left` and `right` over a length of `len` sample frames,
-	/// then calls the provided `callback` (if set).
+	/// then calls the provided `finish_callback` (if set).
+	///
+	/// The `finish_callback` is owned by the player and freed on drop, or with the next fade.
+	///
+	/// Equivalent to [fadeVolume](sys::ffi::playdate_sound_fileplayer::fadeVolume)
+	#[doc(alias = "sys::ffi::playdate_sound_fileplayer::fadeVolume")]
+	pub fn fade_volume_with<F>(&mut self, left: c_float, right: c_float, len: i32, finish_callback: Option<F>)
+		where F: 'static + FnMut(&Self),
+		      Api: Clone {
+		let f = self.api().fade_volume();
+		let mut callback = finish_callback.map(|f| Callback::new(self.api().clone(), f));
+		let (proxy, userdata) = callback.as_mut()
+		                                .map_or((None, core::ptr::null_mut()), Callback::as_raw);
+		unsafe { f(self.0, left, right, len, proxy, userdata) };
+		// Register firstly, then drop previous one.
+		self.2.fade = callback;
+	}
+
+	/// Changes the volume of the [`Player`] to `left` and `right` over a length of `len` sample frames,
+	/// then calls the provided `finish_callback` (if set).
 	///
 	/// Equivalent to [fadeVolume](sys::ffi::playdate_sound_fileplayer::fadeVolume)
 	#[doc(alias = "sys::ffi::playdate_sound_fileplayer::fadeVolume")]
-	// Probably here we can use just FnOnce, because it will dropped after call by proxy.
 	pub fn fade_volume(&self, left: c_float, right: c_float, len: i32, finish_callback: sndCallbackProc) {
 		let f = self.api().fade_volume();
-		// TODO: use userdata
 		unsafe { f(self.0, left, right, len, finish_callback, core::ptr::null_mut()) }
 	}
 
+	/// Sets the `source` of MP3 data to stream, instead of a file loaded with [`Player::load_into_player`].
+	///
+	/// The `source` is owned by the player and freed on drop, or when replaced.
+	/// `buffer_len` is the length of the player's buffer, in seconds.
+	///
+	/// To stream data produced at runtime, pass the reader of the [`stream::buffer`]
+	/// and push the data with its writer.
+	///
+	/// If the `source` can't provide enough data in time, the player underruns,
+	/// that can be checked with [`Player::did_underrun`].
+	///
+	/// Equivalent to [setMP3StreamSource](sys::ffi::playdate_sound_fileplayer::setMP3StreamSource)
+	#[doc(alias = "sys::ffi::playdate_sound_fileplayer::setMP3StreamSource")]
+	pub fn set_mp3_stream<S: stream::StreamSource>(&mut self, source: S, buffer_len: c_float) {
+		let mut source: Box<Box<dyn stream::StreamSource>> = Box::new(Box::new(source));
+		let userdata = source.as_mut() as *mut Box<dyn stream::StreamSource>;
+		self.set_mp3_stream_source(Some(stream::proxy), userdata as _, buffer_len);
+		// Register firstly, then drop previous one.
+		self.3 = Some(stream::Stream(source));
+	}
+
 	/// Equivalent to [setMP3StreamSource](sys::ffi::playdate_sound_fileplayer::setMP3StreamSource)
 	#[doc(alias = "sys::ffi::playdate_sound_fileplayer::setMP3StreamSource")]
 	pub fn set_mp3_stream_source(&self,
@@ -0,0 +1,192 @@
+//! MP3 stream sources for the [`Player`](super::Player).
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::cell::UnsafeCell;
+use core::ffi::c_int;
+use core::ffi::c_void;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+use sys::traits::AsRaw;
+
+
+/// Read-like source of the MP3 stream data.
+///
+/// Called from the audio callback, so implementation should be as fast as possible.
+///
+/// To feed the player with data produced at runtime, use the [`buffer`]
+/// or share the data with the audio callback through another lock-free single-producer single-consumer queue.
+/// `Rc<RefCell<_>>` is not suitable, its borrow flag is not safe against the audio interrupt.
+pub trait StreamSource: 'static {
+	/// Fills the `buf` with up to `buf.len()` bytes of MP3 data.
+	///
+	/// Returns the number of bytes written.
+	/// Returning less than requested (e.g. `0` when no data available yet) causes the player to underrun,
+	/// see [`Player::did_underrun`](super::Player::did_underrun).
+	fn read(&mut self, buf: &mut [u8]) -> usize;
+}
+
+impl<F: FnMut(&mut [u8]) -> usize + 'static> StreamSource for F {
+	fn read(&mut self, buf: &mut [u8]) -> usize { self(buf) }
+}
+
+/// Reads the file from the current position.
+///
+/// Read errors are reported as end of data.
+impl<Api: fs::api::Api + 'static> StreamSource for fs::file::File<Api> {
+	fn read(&mut self, buf: &mut [u8]) -> usize {
+		let f = self.api().read();
+		let result = unsafe { f(self.as_raw() as _, buf.as_mut_ptr() as _, buf.len() as _) };
+		result.max(0) as _
+	}
+}
+
+
+/// Creates a lock-free in-memory ring buffer of `capacity` bytes,
+/// returns the writer to push data produced at runtime
+/// and the reader to pass to the [`Player::set_mp3_stream`](super::Player::set_mp3_stream).
+///
+/// Writer and reader can be used concurrently from the main loop and the audio callback.
+pub fn buffer(capacity: usize) -> (StreamWriter, StreamReader) {
+	let data = (0..capacity).map(|_| UnsafeCell::new(0)).collect();
+	let ring = Arc::new(Ring { data,
+	                           head: AtomicUsize::new(0),
+	                           tail: AtomicUsize::new(0) });
+	(StreamWriter(ring.clone()), StreamReader(ring))
+}
+
+
+/// Single-producer single-consumer ring of bytes.
+///
+/// `head` and `tail` are the total number of written and read bytes,
+/// each is modified only by its own side.
+struct Ring {
+	data: Box<[UnsafeCell<u8>]>,
+	head: AtomicUsize,
+	tail: AtomicUsize,
+}
+
+// Each byte is accessed by one side at a time, guarded by `head` and `tail`.
+unsafe impl Sync for Ring {}
+unsafe impl Send for Ring {}
+
+impl core::fmt::Debug for Ring {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Ring")
+		 .field("len", &self.len())
+		 .field("capacity", &self.data.len())
+		 .finish()
+	}
+}
+
+impl Ring {
+	fn len(&self) -> usize {
+		let head = self.head.load(Ordering::Acquire);
+		let tail = self.tail.load(Ordering::Acquire);
+		head.wrapping_sub(tail)
+	}
+
+	#[inline(always)]
+	fn slot(&self, pos: usize) -> *mut u8 { self.data[pos % self.data.len()].get() }
+}
+
+
+/// Writing half of the [`buffer`].
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct StreamWriter(Arc<Ring>);
+
+impl StreamWriter {
+	/// Pushes as many bytes of `data` as fit into the buffer.
+	///
+	/// Returns the number of bytes written.
+	pub fn write(&mut self, data: &[u8]) -> usize {
+		let ring = &self.0;
+		let head = ring.head.load(Ordering::Relaxed);
+		let tail = ring.tail.load(Ordering::Acquire);
+		let free = ring.data.len() - head.wrapping_sub(tail);
+		let len = data.len().min(free);
+		for (i, byte) in data[..len].iter().enumerate() {
+			unsafe { *ring.slot(head.wrapping_add(i)) = *byte };
+		}
+		ring.head.store(head.wrapping_add(len), Ordering::Release);
+		len
+	}
+
+	/// Returns the number of bytes that are not read yet.
+	pub fn len(&self) -> usize { self.0.len() }
+
+	/// Returns `true` if all written bytes are read.
+	pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+	/// Returns the number of bytes that can be written now.
+	pub fn free(&self) -> usize { self.capacity() - self.len() }
+
+	/// Returns the capacity of the buffer.
+	pub fn capacity(&self) -> usize { self.0.data.len() }
+}
+
+
+/// Reading half of the [`buffer`], the source of the stream.
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct StreamReader(Arc<Ring>);
+
+impl StreamSource for StreamReader {
+	fn read(&mut self, buf: &mut [u8]) -> usize {
+		let ring = &self.0;
+		let tail = ring.tail.load(Ordering::Relaxed);
+		let head = ring.head.load(Ordering::Acquire);
+		let len = buf.len().min(head.wrapping_sub(tail));
+		for (i, byte) in buf[..len].iter_mut().enumerate() {
+			*byte = unsafe { *ring.slot(tail.wrapping_add(i)) };
+		}
+		ring.tail.store(tail.wrapping_add(len), Ordering::Release);
+		len
+	}
+}
+
+
+/// Source of the stream owned by the player.
+pub(super) struct Stream(#[allow(dead_code)] pub Box<Box<dyn StreamSource>>);
+
+impl core::fmt::Debug for Stream {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { f.debug_struct("Stream").finish() }
+}
+
+pub(super) unsafe extern "C" fn proxy(data: *mut u8, bytes: c_int, userdata: *mut c_void) -> c_int {
+	if data.is_null() || bytes <= 0 {
+		return 0;
+	}
+	if let Some(source) = (userdata as *mut Box<dyn StreamSource>).as_mut() {
+		let buf = core::slice::from_raw_parts_mut(data, bytes as _);
+		source.read(buf).min(bytes as _) as _
+	} else {
+		0
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	fn buffer_wraps() {
+		let (mut writer, mut reader) = buffer(4);
+		let mut buf = [0; 3];
+
+		assert_eq!(3, writer.write(&[1, 2, 3]));
+		assert_eq!(2, reader.read(&mut buf[..2]));
+		assert_eq!([1, 2], buf[..2]);
+
+		assert_eq!(3, writer.write(&[4, 5, 6, 7]));
+		assert_eq!(0, writer.free());
+		assert_eq!(3, reader.read(&mut buf));
+		assert_eq!([3, 4, 5], buf);
+		assert_eq!(1, reader.read(&mut buf));
+		assert_eq!(6, buf[0]);
+		assert_eq!(0, reader.read(&mut buf));
+		assert!(writer.is_empty());
+	}
+}
@@ -1,4 +1,9 @@
+use alloc::boxed::Box;
 use core::ffi::c_int;
+use core::ffi::c_void;
+use core::mem::ManuallyDrop;
+
+use sys::ffi::SoundSource as OpaqueSoundSource;
 
 pub mod fp;
 pub mod sp;
@@ -27,3 +32,66 @@ impl Into<c_int> for Repeat {
 		}
 	}
 }
+
+
+/// Closure-callback of the player `P`, passed to the C-part as userdata.
+///
+/// Sound sources have no `setUserdata`/`getUserdata` in the C API,
+/// so the closure is passed as the userdata argument of the callback setter
+/// and every callback of the player has its own closure.
+pub(crate) struct Callback<P, Api> {
+	api: Api,
+	f: Box<dyn FnMut(&P)>,
+}
+
+impl<P: FromRaw<Api>, Api: Clone> Callback<P, Api> {
+	pub(crate) fn new<F: FnMut(&P) + 'static>(api: Api, f: F) -> Box<Self> {
+		Box::new(Self { api, f: Box::new(f) })
+	}
+
+	/// Returns the proxy and the userdata for the C-part.
+	pub(crate) fn as_raw(this: &mut Box<Self>) -> (sys::ffi::sndCallbackProc, *mut c_void) {
+		(Some(proxy::<P, Api>), this.as_mut() as *mut Self as _)
+	}
+}
+
+
+/// Closures-callbacks owned by the player, so they are freed with the player.
+pub(crate) struct Callbacks<P, Api> {
+	pub finish: Option<Box<Callback<P, Api>>>,
+	pub looped: Option<Box<Callback<P, Api>>>,
+	pub fade: Option<Box<Callback<P, Api>>>,
+}
+
+impl<P, Api> Default for Callbacks<P, Api> {
+	fn default() -> Self {
+		Self { finish: None,
+		       looped: None,
+		       fade: None }
+	}
+}
+
+impl<P, Api> core::fmt::Debug for Callbacks<P, Api> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Callbacks")
+		 .field("finish", &self.finish.is_some())
+		 .field("looped", &self.looped.is_some())
+		 .field("fade", &self.fade.is_some())
+		 .finish()
+	}
+}
+
+
+/// Player that can be borrowed from the raw pointer passed to a callback.
+pub(crate) trait FromRaw<Api>: Sized {
+	/// Creates __not owned__ player, that must not be dropped.
+	unsafe fn from_raw(ptr: *mut OpaqueSoundSource, api: Api) -> ManuallyDrop<Self>;
+}
+
+
+unsafe extern "C" fn proxy<P: FromRaw<Api>, Api: Clone>(c: *mut OpaqueSoundSource, userdata: *mut c_void) {
+	if let Some(callback) = (userdata as *mut Callback<P, Api>).as_mut() {
+		let player = P::from_raw(c, callback.api.clone());
+		(callback.f)(&player)
+	}
+}
@@ -1,10 +1,16 @@
 use core::ffi::c_float;
 use core::ffi::c_int;
+use core::mem::ManuallyDrop;
 
 use sys::ffi::SamplePlayer;
+use sys::ffi::SoundSource as OpaqueSoundSource;
 use sys::ffi::sndCallbackProc;
 
 use crate::error::Error;
+use crate::source::AsSoundSource;
+use super::Callback;
+use super::Callbacks;
+use super::FromRaw;
 use super::Repeat;
 
 
@@ -12,7 +18,7 @@ pub mod api;
 
 
 #[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
-pub struct Player<Api: api::Api = api::Default>(*mut SamplePlayer, Api);
+pub struct Player<Api: api::Api = api::Default>(*mut SamplePlayer, Api, Callbacks<Self, Api>);
 
 
 // ctor //
@@ -38,12 +44,24 @@ impl<Api> Player<Api> where Api: api::Api {
 		if player.is_null() {
 			Err(Error::Alloc)
 		} else {
-			Ok(Player(player, api))
+			Ok(Player(player, api, Default::default()))
 		}
 	}
 }
 
 
+impl<Api: api::Api> AsSoundSource for Player<Api> {
+	unsafe fn as_source_raw(&self) -> *mut OpaqueSoundSource { self.0 as _ }
+}
+
+
+impl<Api: api::Api> FromRaw<Api> for Player<Api> {
+	unsafe fn from_raw(ptr: *mut OpaqueSoundSource, api: Api) -> ManuallyDrop<Self> {
+		ManuallyDrop::new(Self(ptr as _, api, Default::default()))
+	}
+}
+
+
 impl<Api: api::Api> Drop for Player<Api> {
 	fn drop(&mut self) {
 		if !self.0.is_null() {
@@ -193,28 +211,57 @@ impl<Api> Player<Api> where Api: api::Api {
 
 	// callbacks //
 
-	/// Sets a function to be called when playback has completed.
+	/// Sets a `callback` to be called when playback has completed.
+	///
+	/// The `callback` is owned by the player and freed on drop, or when replaced.
+	/// If `callback` is `None`, the current one is cleared.
+	///
+	/// Equivalent to [setFinishCallback](sys::ffi::playdate_sound_sampleplayer::setFinishCallback)
+	#[doc(alias = "sys::ffi::playdate_sound_sampleplayer::setFinishCallback")]
+	pub fn on_finish<F>(&mut self, callback: Option<F>)
+		where F: 'static + FnMut(&Self),
+		      Api: Clone {
+		let f = self.api().set_finish_callback();
+		let mut callback = callback.map(|f| Callback::new(self.api().clone(), f));
+		let (proxy, userdata) = callback.as_mut()
+		                                .map_or((None, core::ptr::null_mut()), Callback::as_raw);
+		unsafe { f(self.0, proxy, userdata) };
+		// Register firstly, then drop previous one.
+		self.2.finish = callback;
+	}
+
+	/// Sets a `callback` to be called when playback has completed.
 	///
 	/// Equivalent to [setFinishCallback](sys::ffi::playdate_sound_sampleplayer::setFinishCallback)
 	#[doc(alias = "sys::ffi::playdate_sound_sampleplayer::setFinishCallback")]
-	// TODO: Rustify this function, maybe impl like it done for sprites.
-	// My idea is to store user-callback into self,
-	// and here use proxy "extern C" function.
-	// Maybe move self into the wrapper with user-callback?
-	// We're need so store user-callback somewhere like StackedMap(stacked_type_map) or ErasedSet by type.
-	// Type of `F: FnMut(*mut SoundSource)` is unique, so we can do it.
-	// But with cost of memory - one static for each `F`*`Self`, so so much.
 	pub fn set_finish_callback_raw(&self, callback: sndCallbackProc) -> Result<(), Error> {
 		let f = self.api().set_finish_callback();
-		// TODO: use userdata
 		Ok(unsafe { f(self.0, callback, core::ptr::null_mut()) })
 	}
 
+	/// Sets a `callback` to be called every time the sample loops.
+	///
+	/// The `callback` is owned by the player and freed on drop, or when replaced.
+	/// If `callback` is `None`, the current one is cleared.
+	///
+	/// Equivalent to [setLoopCallback](sys::ffi::playdate_sound_sampleplayer::setLoopCallback)
+	#[doc(alias = "sys::ffi::playdate_sound_sampleplayer::setLoopCallback")]
+	pub fn on_loop<F>(&mut self, callback: Option<F>)
+		where F: 'static + FnMut(&Self),
+		      Api: Clone {
+		let f = self.api().set_loop_callback();
+		let mut callback = callback.map(|f| Callback::new(self.api().clone(), f));
+		let (proxy, userdata) = callback.as_mut()
+		                                .map_or((None, core::ptr::null_mut()), Callback::as_raw);
+		unsafe { f(self.0, proxy, userdata) };
+		// Register firstly, then drop previous one.
+		self.2.looped = callback;
+	}
+
 	/// Equivalent to [setLoopCallback](sys::ffi::playdate_sound_sampleplayer::setLoopCallback)
 	#[doc(alias = "sys::ffi::playdate_sound_sampleplayer::setLoopCallback")]
 	pub fn set_loop_callback_raw(&self, callback: sndCallbackProc) -> Result<(), Error> {
 		let f = self.api().set_loop_callback();
-		// TODO: use userdata
 		Ok(unsafe { f(self.0, callback, core::ptr::null_mut()) })
 	}
 }
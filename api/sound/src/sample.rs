@@ -76,6 +76,50 @@ impl<Api: Default + api::Api> Sample<Api> {
 		let api: Api = Default::default();
 		Self::new_from_data_with(api, data, format, sample_rate)
 	}
+
+
+	/// Returns a new [`Sample`] referencing the given 16 bit PCM `data`.
+	///
+	/// Stereo `data` is interleaved, so its length must be even.
+	///
+	/// The `data` is borrowed mutably, like in [`Sample::new_from_data`],
+	/// because the sample gives mutable access to it with [`Sample::get_data`].
+	///
+	/// Uses [`sys::ffi::playdate_sound_sample::newSampleFromData`]
+	pub fn from_pcm_i16<'t>(data: &'t mut [i16],
+	                        channels: Channels,
+	                        sample_rate: u32)
+	                        -> Result<SampleWithData<'t, Api>, Error> {
+		let api: Api = Default::default();
+		Self::from_pcm_i16_with(api, data, channels, sample_rate)
+	}
+
+	/// Returns a new [`Sample`] referencing the given 8 bit PCM `data`.
+	///
+	/// Stereo `data` is interleaved, so its length must be even.
+	/// The `data` is borrowed mutably for the same reason as in [`Sample::from_pcm_i16`].
+	///
+	/// Uses [`sys::ffi::playdate_sound_sample::newSampleFromData`]
+	pub fn from_pcm_u8<'t>(data: &'t mut [u8],
+	                       channels: Channels,
+	                       sample_rate: u32)
+	                       -> Result<SampleWithData<'t, Api>, Error> {
+		let api: Api = Default::default();
+		Self::from_pcm_u8_with(api, data, channels, sample_rate)
+	}
+
+	/// Returns a new [`Sample`] referencing the given IMA ADPCM encoded `data`.
+	///
+	/// See [`Sample::from_adpcm_with`] for the requirements to the `data`.
+	///
+	/// Uses [`sys::ffi::playdate_sound_sample::newSampleFromData`]
+	pub fn from_adpcm<'t>(data: &'t mut [u8],
+	                      channels: Channels,
+	                      sample_rate: u32)
+	                      -> Result<SampleWithData<'t, Api>, Error> {
+		let api: Api = Default::default();
+		Self::from_adpcm_with(api, data, channels, sample_rate)
+	}
 }
 
 
@@ -148,6 +192,70 @@ impl<Api: api::Api> Sample<Api> {
 			Ok(SampleWithData(Self(ptr, api), data))
 		}
 	}
+
+
+	/// Returns a new [`Sample`] referencing the given 16 bit PCM `data` with given `api`.
+	///
+	/// Stereo `data` is interleaved, so its length must be even.
+	///
+	/// Uses [`sys::ffi::playdate_sound_sample::newSampleFromData`]
+	pub fn from_pcm_i16_with<'t>(api: Api,
+	                             data: &'t mut [i16],
+	                             channels: Channels,
+	                             sample_rate: u32)
+	                             -> Result<SampleWithData<'t, Api>, Error> {
+		let format = channels.format(SoundFormat::kSound16bitMono);
+		let len = core::mem::size_of_val(data);
+		let bytes = unsafe { core::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u8, len) };
+		Self::from_bytes_with(api, bytes, format, sample_rate)
+	}
+
+	/// Returns a new [`Sample`] referencing the given 8 bit PCM `data` with given `api`.
+	///
+	/// Stereo `data` is interleaved, so its length must be even.
+	///
+	/// Uses [`sys::ffi::playdate_sound_sample::newSampleFromData`]
+	pub fn from_pcm_u8_with<'t>(api: Api,
+	                            data: &'t mut [u8],
+	                            channels: Channels,
+	                            sample_rate: u32)
+	                            -> Result<SampleWithData<'t, Api>, Error> {
+		let format = channels.format(SoundFormat::kSound8bitMono);
+		Self::from_bytes_with(api, data, format, sample_rate)
+	}
+
+	/// Returns a new [`Sample`] referencing the given IMA ADPCM encoded `data` with given `api`.
+	///
+	/// Encoded blocks start with a header of [`ADPCM_HEADER_LEN`] bytes per channel
+	/// and consist of 32 bit words per channel,
+	/// so the `data` must contain at least one header and a sample word and be aligned to words of all channels.
+	///
+	/// Uses [`sys::ffi::playdate_sound_sample::newSampleFromData`]
+	pub fn from_adpcm_with<'t>(api: Api,
+	                           data: &'t mut [u8],
+	                           channels: Channels,
+	                           sample_rate: u32)
+	                           -> Result<SampleWithData<'t, Api>, Error> {
+		let align = ADPCM_HEADER_LEN * channels.count();
+		if data.len() <= align || data.len() % align != 0 {
+			return Err(Error::InvalidData);
+		}
+		let format = channels.format(SoundFormat::kSoundADPCMMono);
+		Self::from_bytes_with(api, data, format, sample_rate)
+	}
+
+	/// Validates the `data` against the `format` and creates a new sample referencing it.
+	fn from_bytes_with<'t>(api: Api,
+	                       data: &'t mut [u8],
+	                       format: SoundFormat,
+	                       sample_rate: u32)
+	                       -> Result<SampleWithData<'t, Api>, Error> {
+		let len = data.len();
+		if len == 0 || sample_rate == 0 || len % format.bytes_per_frame() != 0 || len > c_int::MAX as usize {
+			return Err(Error::InvalidData);
+		}
+		Self::new_from_data_with(api, data, format, sample_rate)
+	}
 }
 
 
@@ -181,7 +289,9 @@ impl<Api: api::Api> Sample<Api> {
 		boxed_data = unsafe { Box::from_raw(data) };
 		let data = unsafe { core::slice::from_raw_parts_mut::<u8>(*boxed_data, byte_length as usize) };
 
-		SampleData { data, sample_rate }
+		SampleData { data,
+		             format,
+		             sample_rate }
 	}
 }
 
@@ -202,9 +312,114 @@ impl<Api: api::Api> AsRef<Sample<Api>> for SampleWithData<'_, Api> {
 
 pub struct SampleData<'t> {
 	pub sample_rate: u32,
+	pub format: SoundFormat,
 	pub data: &'t mut [u8],
 }
 
+impl SampleData<'_> {
+	/// Returns number of channels of the data.
+	pub fn channels(&self) -> Channels {
+		if self.format.is_stereo() {
+			Channels::Stereo
+		} else {
+			Channels::Mono
+		}
+	}
+
+	/// Returns number of frames in the data.
+	///
+	/// For ADPCM formats the data is encoded, so this is a number of bytes instead.
+	pub fn frames(&self) -> usize { self.data.len() / self.format.bytes_per_frame() }
+
+	/// Returns samples of the 16 bit PCM data, interleaved if stereo.
+	///
+	/// Returns `None` if the data is in other format.
+	pub fn samples_i16(&self) -> Option<impl Iterator<Item = i16> + '_> {
+		match self.format {
+			SoundFormat::kSound16bitMono | SoundFormat::kSound16bitStereo => {
+				let samples = self.data
+				                  .chunks_exact(2)
+				                  .map(|b| i16::from_le_bytes([b[0], b[1]]));
+				Some(samples)
+			},
+			_ => None,
+		}
+	}
+
+	/// Returns samples of the 8 bit PCM data, interleaved if stereo.
+	///
+	/// Returns `None` if the data is in other format.
+	pub fn samples_u8(&self) -> Option<impl Iterator<Item = u8> + '_> {
+		match self.format {
+			SoundFormat::kSound8bitMono | SoundFormat::kSound8bitStereo => Some(self.data.iter().copied()),
+			_ => None,
+		}
+	}
+}
+
+
+/// Number of channels of the audio data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channels {
+	Mono,
+	Stereo,
+}
+
+impl Channels {
+	/// Returns the number of channels.
+	pub const fn count(self) -> usize {
+		match self {
+			Channels::Mono => 1,
+			Channels::Stereo => 2,
+		}
+	}
+
+	/// Returns the given mono `format` with this number of channels.
+	fn format(self, format: SoundFormat) -> SoundFormat {
+		match (self, format) {
+			(Channels::Mono, format) => format,
+			(Channels::Stereo, SoundFormat::kSound8bitMono) => SoundFormat::kSound8bitStereo,
+			(Channels::Stereo, SoundFormat::kSound16bitMono) => SoundFormat::kSound16bitStereo,
+			(Channels::Stereo, SoundFormat::kSoundADPCMMono) => SoundFormat::kSoundADPCMStereo,
+			(Channels::Stereo, format) => format,
+		}
+	}
+}
+
+
+/// Length of the IMA ADPCM block header of one channel, in bytes:
+/// initial predictor (`i16`), step index (`u8`) and reserved byte.
+pub const ADPCM_HEADER_LEN: usize = 4;
+
+
+/// Helpers for [`SoundFormat`], same as provided by `pd_api_sound.h`.
+pub trait SoundFormatExt {
+	/// Returns `true` if the format is stereo.
+	fn is_stereo(&self) -> bool;
+	/// Returns `true` if the format is 16 bit, including ADPCM.
+	fn is_16bit(&self) -> bool;
+	/// Returns number of bytes per frame.
+	///
+	/// For ADPCM formats it is `1`, because frames are encoded in blocks.
+	fn bytes_per_frame(&self) -> usize;
+}
+
+impl SoundFormatExt for SoundFormat {
+	fn is_stereo(&self) -> bool { (*self as u32) & 1 == 1 }
+
+	fn is_16bit(&self) -> bool { *self as u32 >= SoundFormat::kSound16bitMono as u32 }
+
+	fn bytes_per_frame(&self) -> usize {
+		match self {
+			SoundFormat::kSound8bitMono => 1,
+			SoundFormat::kSound8bitStereo => 2,
+			SoundFormat::kSound16bitMono => 2,
+			SoundFormat::kSound16bitStereo => 4,
+			SoundFormat::kSoundADPCMMono | SoundFormat::kSoundADPCMStereo => 1,
+		}
+	}
+}
+
 
 pub mod api {
 	use core::ffi::c_int;
@@ -360,3 +575,74 @@ pub mod api {
 		}
 	}
 }
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	fn invalid(result: Result<SampleWithData<'_, api::Default>, Error>) -> bool {
+		matches!(result, Err(Error::InvalidData))
+	}
+
+
+	#[test]
+	fn channels_format() {
+		let stereo = Channels::Stereo.format(SoundFormat::kSound16bitMono);
+		assert!(matches!(stereo, SoundFormat::kSound16bitStereo));
+		let stereo = Channels::Stereo.format(SoundFormat::kSoundADPCMMono);
+		assert!(matches!(stereo, SoundFormat::kSoundADPCMStereo));
+		let mono = Channels::Mono.format(SoundFormat::kSound8bitMono);
+		assert!(matches!(mono, SoundFormat::kSound8bitMono));
+	}
+
+	#[test]
+	fn format_ext() {
+		assert!(SoundFormat::kSound8bitStereo.is_stereo());
+		assert!(!SoundFormat::kSoundADPCMMono.is_stereo());
+		assert!(SoundFormat::kSoundADPCMMono.is_16bit());
+		assert!(!SoundFormat::kSound8bitStereo.is_16bit());
+		assert_eq!(4, SoundFormat::kSound16bitStereo.bytes_per_frame());
+		assert_eq!(1, SoundFormat::kSoundADPCMStereo.bytes_per_frame());
+	}
+
+	#[test]
+	fn invalid_data() {
+		let api = api::Default;
+		assert!(invalid(Sample::from_pcm_i16_with(api, &mut [], Channels::Mono, 44100)));
+		assert!(invalid(Sample::from_pcm_i16_with(api, &mut [0; 3], Channels::Stereo, 44100)));
+		assert!(invalid(Sample::from_pcm_i16_with(api, &mut [0; 4], Channels::Mono, 0)));
+		assert!(invalid(Sample::from_pcm_u8_with(api, &mut [0; 5], Channels::Stereo, 22050)));
+		assert!(invalid(Sample::from_adpcm_with(api, &mut [], Channels::Stereo, 22050)));
+		assert!(invalid(Sample::from_adpcm_with(api, &mut [0; 4], Channels::Mono, 22050)));
+		assert!(invalid(Sample::from_adpcm_with(api, &mut [0; 6], Channels::Mono, 22050)));
+		assert!(invalid(Sample::from_adpcm_with(api, &mut [0; 12], Channels::Stereo, 22050)));
+	}
+
+	#[test]
+	fn data_i16() {
+		let mut bytes = [0x01, 0x00, 0xFF, 0xFF, 0x00, 0x80, 0xFF, 0x7F];
+		let data = SampleData { sample_rate: 44100,
+		                        format: SoundFormat::kSound16bitStereo,
+		                        data: &mut bytes };
+		assert_eq!(Channels::Stereo, data.channels());
+		assert_eq!(2, data.frames());
+		assert!(data.samples_u8().is_none());
+		let samples: Vec<_> = data.samples_i16().unwrap().collect();
+		assert_eq!(&[1, -1, i16::MIN, i16::MAX], samples.as_slice());
+	}
+
+	#[test]
+	fn data_u8() {
+		let mut bytes = [0, 127, 255];
+		let data = SampleData { sample_rate: 22050,
+		                        format: SoundFormat::kSound8bitMono,
+		                        data: &mut bytes };
+		assert_eq!(Channels::Mono, data.channels());
+		assert_eq!(3, data.frames());
+		assert!(data.samples_i16().is_none());
+		let samples: Vec<_> = data.samples_u8().unwrap().collect();
+		assert_eq!(&[0, 127, 255], samples.as_slice());
+	}
+}
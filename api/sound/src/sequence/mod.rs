@@ -0,0 +1,319 @@
+//! Playdate sound sequence API
+
+use core::ffi::c_char;
+use core::ffi::c_float;
+use core::ffi::c_int;
+use core::ffi::c_void;
+
+use sys::ffi::CString;
+use sys::ffi::SequenceFinishedCallback;
+use sys::ffi::SoundSequence;
+use sys::traits::AsRaw;
+
+use fs::Path;
+
+use crate::borrow::Ref;
+use crate::error::ApiError;
+use crate::error::Error;
+use crate::track::Track;
+use crate::track::Step;
+use crate::track::Length;
+
+pub mod api;
+
+
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Sequence<Api: api::Api = api::Default>(*mut SoundSequence, Api);
+
+impl<Api: api::Api> AsRaw for Sequence<Api> {
+	type Type = SoundSequence;
+	unsafe fn as_raw(&self) -> *mut SoundSequence { self.0 }
+}
+
+
+// ctor //
+
+impl<Api> Sequence<Api> where Api: api::Api {
+	/// Creates a new empty sequence.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::newSequence`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::newSequence")]
+	pub fn new() -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new empty sequence with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::newSequence`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::newSequence")]
+	pub fn new_with(api: Api) -> Result<Self, Error> {
+		let f = api.new_sequence();
+		let seq = unsafe { f() };
+		if seq.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(Sequence(seq, api))
+		}
+	}
+
+	/// Creates a new sequence and loads the MIDI file at `path` into it.
+	///
+	/// See also [`Sequence::load_midi_file`].
+	pub fn load_midi<P: AsRef<Path>>(path: P) -> Result<Self, ApiError>
+		where Api: Default {
+		let api = Api::default();
+		Self::load_midi_with(api, path)
+	}
+
+	/// Creates a new sequence with given `api` and loads the MIDI file at `path` into it.
+	///
+	/// See also [`Sequence::load_midi_file`].
+	pub fn load_midi_with<P: AsRef<Path>>(api: Api, path: P) -> Result<Self, ApiError> {
+		let seq = Self::new_with(api).map_err(ApiError::Api)?;
+		seq.load_midi_file(path)?;
+		Ok(seq)
+	}
+}
+
+
+impl<Api: api::Api> Drop for Sequence<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			let f = self.api().free_sequence();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api> Sequence<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api> Sequence<Api> where Api: api::Api {
+	/// If the sequence is empty, attempts to load data from the MIDI file at `path` into the sequence.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::loadMIDIFile`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::loadMIDIFile")]
+	pub fn load_midi_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ApiError> {
+		let path_cs = CString::new(path.as_ref())?;
+		let path_ptr = path_cs.as_ptr() as *mut c_char;
+
+		let f = self.api().load_midi_file();
+		if unsafe { f(self.0, path_ptr) } == 1 {
+			Ok(())
+		} else {
+			Err(Error::FileNotExist.into())
+		}
+	}
+
+
+	/// Returns the current time in the sequence, in samples since the start of the file.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::getTime`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::getTime")]
+	pub fn time(&self) -> u32 {
+		let f = self.api().get_time();
+		unsafe { f(self.0) }
+	}
+
+	/// Sets the current time in the sequence, in samples since the start of the file.
+	///
+	/// Note that which step this moves the sequence to depends on the current tempo.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::setTime`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::setTime")]
+	pub fn set_time(&self, time: u32) {
+		let f = self.api().set_time();
+		unsafe { f(self.0, time) }
+	}
+
+	/// Sets the looping range of the sequence.
+	///
+	/// If `loops` is `0`, the loop repeats endlessly.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::setLoops`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::setLoops")]
+	pub fn set_loops(&self, start: Step, end: Step, loops: c_int) {
+		let f = self.api().set_loops();
+		unsafe { f(self.0, start.0 as _, end.0 as _, loops) }
+	}
+
+	/// Returns the tempo of the sequence, in steps per second.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::getTempo`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::getTempo")]
+	pub fn tempo(&self) -> c_float {
+		let f = self.api().get_tempo();
+		unsafe { f(self.0) }
+	}
+
+	/// Sets the tempo of the sequence, in steps per second.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::setTempo`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::setTempo")]
+	pub fn set_tempo(&self, steps_per_second: c_float) {
+		let f = self.api().set_tempo();
+		unsafe { f(self.0, steps_per_second) }
+	}
+
+	/// Returns the length of the longest track in the sequence, in steps.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::getLength`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::getLength")]
+	pub fn length(&self) -> Length {
+		let f = self.api().get_length();
+		Length(unsafe { f(self.0) })
+	}
+
+	/// Returns the step the sequence is currently at
+	/// and the current sample offset within the step.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::getCurrentStep`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::getCurrentStep")]
+	pub fn current_step(&self) -> (Step, c_int) {
+		let mut offset = 0;
+		let f = self.api().get_current_step();
+		let step = unsafe { f(self.0, &mut offset) };
+		(Step(step.max(0) as _), offset)
+	}
+
+	/// Sets the current step for the sequence.
+	///
+	/// `time_offset` is a sample offset within the step.
+	/// If `play_notes` is set, notes at the given step (ignoring `time_offset`) are played.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::setCurrentStep`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::setCurrentStep")]
+	pub fn set_current_step(&self, step: Step, time_offset: c_int, play_notes: bool) {
+		let f = self.api().set_current_step();
+		unsafe { f(self.0, step.0 as _, time_offset, play_notes.into()) }
+	}
+
+
+	/// Returns the number of tracks in the sequence.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::getTrackCount`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::getTrackCount")]
+	pub fn track_count(&self) -> c_int {
+		let f = self.api().get_track_count();
+		unsafe { f(self.0) }
+	}
+
+	/// Creates a new track and adds it to the sequence.
+	///
+	/// Returned track is owned by the sequence, so it will __not__ be freed on drop
+	/// and can't outlive the sequence.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::addTrack`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::addTrack")]
+	pub fn add_track(&self) -> Result<Ref<'_, Track<crate::track::api::Default, false>>, Error> {
+		let f = self.api().add_track();
+		let ptr = unsafe { f(self.0) };
+		if ptr.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(unsafe { Ref::new(Track::from(ptr)) })
+		}
+	}
+
+	/// Returns the track at `index`, if `index` is out of bounds, the function returns `None`.
+	///
+	/// Returned track is owned by the sequence, so it will __not__ be freed on drop
+	/// and can't outlive the sequence.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::getTrackAtIndex`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::getTrackAtIndex")]
+	pub fn track(&self, index: c_int) -> Option<Ref<'_, Track<crate::track::api::Default, false>>> {
+		if index < 0 {
+			return None;
+		}
+		let f = self.api().get_track_at_index();
+		let ptr = unsafe { f(self.0, index as _) };
+		if ptr.is_null() {
+			None
+		} else {
+			Some(unsafe { Ref::new(Track::from(ptr)) })
+		}
+	}
+
+	/// Returns iterator over all tracks of the sequence.
+	///
+	/// See also [`Sequence::track`].
+	pub fn tracks(&self) -> impl Iterator<Item = Ref<'_, Track<crate::track::api::Default, false>>> + '_ {
+		(0..self.track_count()).filter_map(|i| self.track(i))
+	}
+
+	/// Sets the given `track` at position `index` in the sequence.
+	///
+	/// The sequence takes ownership of the `track`.
+	/// Replaced track may be freed, so this requires exclusive access to the sequence.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::setTrackAtIndex`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::setTrackAtIndex")]
+	pub fn set_track<TApi: crate::track::api::Api + Copy>(&mut self, track: Track<TApi, true>, index: u32) {
+		let track = track.into_shared();
+		let f = self.api().set_track_at_index();
+		unsafe { f(self.0, track.as_raw(), index) }
+	}
+
+
+	/// Returns `true` if the sequence is currently playing.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::isPlaying`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::isPlaying")]
+	pub fn is_playing(&self) -> bool {
+		let f = self.api().is_playing();
+		unsafe { f(self.0) == 1 }
+	}
+
+	/// Starts playing the sequence.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::play`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::play")]
+	#[inline(always)]
+	pub fn play(&self) { unsafe { self.play_raw(None, core::ptr::null_mut()) } }
+
+	/// Starts playing the sequence.
+	///
+	/// `finish_callback` is an optional function to be called
+	/// when the sequence finishes playing or is stopped.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::play`]
+	///
+	/// # Safety
+	/// `userdata` is passed to the `finish_callback` as is,
+	/// so it must stay valid until the callback is called or the sequence is freed.
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::play")]
+	pub unsafe fn play_raw(&self, finish_callback: SequenceFinishedCallback, userdata: *mut c_void) {
+		let f = self.api().play();
+		f(self.0, finish_callback, userdata)
+	}
+
+	/// Stops playing the sequence.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::stop`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::stop")]
+	pub fn stop(&self) {
+		let f = self.api().stop();
+		unsafe { f(self.0) }
+	}
+
+	/// Sends a stop signal to all playing notes on all tracks.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_sequence::allNotesOff`]
+	#[doc(alias = "sys::ffi::playdate_sound_sequence::allNotesOff")]
+	pub fn all_notes_off(&self) {
+		let f = self.api().all_notes_off();
+		unsafe { f(self.0) }
+	}
+}
@@ -0,0 +1,103 @@
+use core::ffi::c_float;
+use core::ffi::c_void;
+use core::ptr::NonNull;
+
+use sys::ffi::PDSynthSignal;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::signalDeallocFunc;
+use sys::ffi::signalNoteOffFunc;
+use sys::ffi::signalNoteOnFunc;
+use sys::ffi::signalStepFunc;
+use sys::ffi::playdate_sound_signal;
+
+
+/// Default synth signal api end-point, ZST.
+///
+/// All calls approximately costs ~4 derefs.
+#[derive(Debug, Clone, Copy, core::default::Default)]
+pub struct Default;
+impl Api for Default {}
+
+
+/// Cached synth signal api end-point.
+///
+/// Stores one reference, so size on stack is eq `usize`.
+///
+/// All calls approximately costs ~1 deref.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Cache(&'static playdate_sound_signal);
+
+impl core::default::Default for Cache {
+	fn default() -> Self { Self(sys::api!(sound.signal)) }
+}
+
+impl From<*const playdate_sound_signal> for Cache {
+	#[inline(always)]
+	fn from(ptr: *const playdate_sound_signal) -> Self { Self(unsafe { ptr.as_ref() }.expect("signal")) }
+}
+
+impl From<&'static playdate_sound_signal> for Cache {
+	#[inline(always)]
+	fn from(r: &'static playdate_sound_signal) -> Self { Self(r) }
+}
+
+impl From<NonNull<playdate_sound_signal>> for Cache {
+	#[inline(always)]
+	fn from(ptr: NonNull<playdate_sound_signal>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+impl From<&'_ NonNull<playdate_sound_signal>> for Cache {
+	#[inline(always)]
+	fn from(ptr: &NonNull<playdate_sound_signal>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+
+impl Api for Cache {
+	fn new_signal(&self) -> FnNewSignal { self.0.newSignal.expect("newSignal") }
+	fn free_signal(&self) -> FnFreeSignal { self.0.freeSignal.expect("freeSignal") }
+	fn get_value(&self) -> FnGetValue { self.0.getValue.expect("getValue") }
+	fn set_value_scale(&self) -> FnSetValueScale { self.0.setValueScale.expect("setValueScale") }
+	fn set_value_offset(&self) -> FnSetValueOffset { self.0.setValueOffset.expect("setValueOffset") }
+	fn new_signal_for_value(&self) -> FnNewSignalForValue { self.0.newSignalForValue.expect("newSignalForValue") }
+}
+
+
+pub trait Api {
+	/// Returns [`sys::ffi::playdate_sound_signal::newSignal`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::newSignal")]
+	fn new_signal(&self) -> FnNewSignal { *sys::api!(sound.signal.newSignal) }
+
+	/// Returns [`sys::ffi::playdate_sound_signal::freeSignal`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::freeSignal")]
+	fn free_signal(&self) -> FnFreeSignal { *sys::api!(sound.signal.freeSignal) }
+
+	/// Returns [`sys::ffi::playdate_sound_signal::getValue`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::getValue")]
+	fn get_value(&self) -> FnGetValue { *sys::api!(sound.signal.getValue) }
+
+	/// Returns [`sys::ffi::playdate_sound_signal::setValueScale`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::setValueScale")]
+	fn set_value_scale(&self) -> FnSetValueScale { *sys::api!(sound.signal.setValueScale) }
+
+	/// Returns [`sys::ffi::playdate_sound_signal::setValueOffset`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::setValueOffset")]
+	fn set_value_offset(&self) -> FnSetValueOffset { *sys::api!(sound.signal.setValueOffset) }
+
+	/// Returns [`sys::ffi::playdate_sound_signal::newSignalForValue`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::newSignalForValue")]
+	fn new_signal_for_value(&self) -> FnNewSignalForValue { *sys::api!(sound.signal.newSignalForValue) }
+}
+
+
+type FnNewSignal = unsafe extern "C" fn(step: signalStepFunc,
+                                        note_on: signalNoteOnFunc,
+                                        note_off: signalNoteOffFunc,
+                                        dealloc: signalDeallocFunc,
+                                        userdata: *mut c_void)
+                                        -> *mut PDSynthSignal;
+type FnFreeSignal = unsafe extern "C" fn(signal: *mut PDSynthSignal);
+type FnGetValue = unsafe extern "C" fn(signal: *mut PDSynthSignal) -> c_float;
+type FnSetValueScale = unsafe extern "C" fn(signal: *mut PDSynthSignal, scale: c_float);
+type FnSetValueOffset = unsafe extern "C" fn(signal: *mut PDSynthSignal, offset: c_float);
+type FnNewSignalForValue = unsafe extern "C" fn(value: *mut PDSynthSignalValue) -> *mut PDSynthSignal;
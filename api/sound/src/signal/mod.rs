@@ -0,0 +1,221 @@
+//! Playdate synth signal API
+
+use core::ffi::c_float;
+use core::ffi::c_int;
+use core::ffi::c_void;
+use alloc::boxed::Box;
+
+use sys::ffi::MIDINote;
+use sys::ffi::PDSynthSignal;
+use sys::ffi::PDSynthSignalValue;
+use sys::traits::AsRaw;
+
+use crate::error::Error;
+
+pub mod api;
+
+
+/// Common interface of all modulation signals -
+/// [`Lfo`](crate::lfo::Lfo), [`Envelope`](crate::envelope::Envelope),
+/// [`ControlSignal`](crate::control::ControlSignal) and [`CustomSignal`].
+///
+/// Any signal can be passed to any `set_*_modulator` method.
+/// These methods are `unsafe`, because the signal must outlive its use as a modulator.
+pub trait Signal {
+	/// Returns raw pointer to the underlying signal value.
+	///
+	/// All signals are "subclasses" of [`sys::ffi::PDSynthSignalValue`],
+	/// so their pointers are casted.
+	///
+	/// # Safety
+	/// Returned pointer is valid while the signal is alive.
+	unsafe fn as_signal_raw(&self) -> *mut PDSynthSignalValue;
+
+
+	/// Returns the current output value of the signal.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_signal::getValue`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::getValue")]
+	fn value(&self) -> c_float {
+		use api::Api;
+		let f = api::Default.get_value();
+		unsafe { f(self.as_signal_raw() as _) }
+	}
+
+	/// Scales the signal's output by the given factor.
+	///
+	/// The scale is applied before the offset.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_signal::setValueScale`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::setValueScale")]
+	fn set_value_scale(&self, scale: c_float) {
+		use api::Api;
+		let f = api::Default.set_value_scale();
+		unsafe { f(self.as_signal_raw() as _, scale) }
+	}
+
+	/// Offsets the signal's output by the given amount.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_signal::setValueOffset`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::setValueOffset")]
+	fn set_value_offset(&self, offset: c_float) {
+		use api::Api;
+		let f = api::Default.set_value_offset();
+		unsafe { f(self.as_signal_raw() as _, offset) }
+	}
+}
+
+
+/// Returns raw pointer to the `signal` or `null` if it is `None`.
+#[inline(always)]
+pub(crate) fn as_raw_or_null(signal: Option<&dyn Signal>) -> *mut PDSynthSignalValue {
+	signal.map_or(core::ptr::null_mut(), |signal| unsafe { signal.as_signal_raw() })
+}
+
+
+/// Implementation of the [`CustomSignal`].
+///
+/// Implemented for all `FnMut(frames, interframe) -> value` closures.
+///
+/// All functions are called on the audio render thread, so they should return as quickly as possible.
+pub trait SignalFn: 'static {
+	/// Returns the value at the end of the current frame.
+	///
+	/// `frames` contains the number of samples until the end of the frame.
+	/// If the signal needs to provide a value in the middle of the frame
+	/// (e.g. an LFO that needs to be sample-accurate)
+	/// it should set the "interframe" value to `interframe` and set `frames` to the sample offset of the value.
+	fn step(&mut self, frames: &mut c_int, interframe: &mut c_float) -> c_float;
+
+	/// Called on note on event.
+	///
+	/// `len` is `None` for indefinite note length.
+	fn note_on(&mut self, note: MIDINote, velocity: c_float, len: Option<c_float>) {
+		let _ = (note, velocity, len);
+	}
+
+	/// Called on note off event.
+	///
+	/// `stopped` is `false` on note release, `true` when the note actually stops playing;
+	/// `offset` is number of frames into the current cycle.
+	fn note_off(&mut self, stopped: bool, offset: c_int) { let _ = (stopped, offset); }
+}
+
+impl<F> SignalFn for F where F: 'static + FnMut(&mut c_int, &mut c_float) -> c_float {
+	#[inline(always)]
+	fn step(&mut self, frames: &mut c_int, interframe: &mut c_float) -> c_float { self(frames, interframe) }
+}
+
+
+/// Custom signal, driven by the Rust [`SignalFn`] implementation.
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct CustomSignal<Api: api::Api = api::Default>(*mut PDSynthSignal, Api);
+
+impl<Api: api::Api> AsRaw for CustomSignal<Api> {
+	type Type = PDSynthSignal;
+	unsafe fn as_raw(&self) -> *mut PDSynthSignal { self.0 }
+}
+
+impl<Api: api::Api> Signal for CustomSignal<Api> {
+	unsafe fn as_signal_raw(&self) -> *mut PDSynthSignalValue { self.0 as _ }
+}
+
+
+// ctor //
+
+impl<Api> CustomSignal<Api> where Api: api::Api {
+	/// Creates a new signal with given implementation.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_signal::newSignal`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::newSignal")]
+	pub fn new<S: SignalFn>(signal: S) -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api, signal)
+	}
+
+	/// Creates a new signal with given implementation and `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_signal::newSignal`]
+	#[doc(alias = "sys::ffi::playdate_sound_signal::newSignal")]
+	pub fn new_with<S: SignalFn>(api: Api, signal: S) -> Result<Self, Error> {
+		let signal: Box<Box<dyn SignalFn>> = Box::new(Box::new(signal));
+		let userdata = Box::into_raw(signal) as *mut c_void;
+
+		let f = api.new_signal();
+		let ptr = unsafe {
+			f(
+			  Some(proxy::step),
+			  Some(proxy::note_on),
+			  Some(proxy::note_off),
+			  Some(proxy::dealloc),
+			  userdata,
+			)
+		};
+		if ptr.is_null() {
+			unsafe { proxy::dealloc(userdata) };
+			Err(Error::Alloc)
+		} else {
+			Ok(CustomSignal(ptr, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api> Drop for CustomSignal<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			// userdata is freed by the `dealloc` callback
+			let f = self.api().free_signal();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api> CustomSignal<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+mod proxy {
+	use core::ffi::c_float;
+	use core::ffi::c_int;
+	use core::ffi::c_void;
+	use alloc::boxed::Box;
+	use sys::ffi::MIDINote;
+	use super::SignalFn;
+
+
+	pub unsafe extern "C" fn step(userdata: *mut c_void, frames: *mut c_int, interframe: *mut c_float) -> c_float {
+		let Some(signal) = (userdata as *mut Box<dyn SignalFn>).as_mut() else {
+			return 0.0;
+		};
+		let (Some(frames), Some(interframe)) = (frames.as_mut(), interframe.as_mut()) else {
+			return 0.0;
+		};
+		signal.step(frames, interframe)
+	}
+
+	pub unsafe extern "C" fn note_on(userdata: *mut c_void, note: MIDINote, velocity: c_float, len: c_float) {
+		if let Some(signal) = (userdata as *mut Box<dyn SignalFn>).as_mut() {
+			signal.note_on(note, velocity, (len >= 0.0).then_some(len))
+		}
+	}
+
+	pub unsafe extern "C" fn note_off(userdata: *mut c_void, stopped: c_int, offset: c_int) {
+		if let Some(signal) = (userdata as *mut Box<dyn SignalFn>).as_mut() {
+			signal.note_off(stopped == 1, offset)
+		}
+	}
+
+	pub unsafe extern "C" fn dealloc(userdata: *mut c_void) {
+		if !userdata.is_null() {
+			drop(Box::from_raw(userdata as *mut Box<dyn SignalFn>))
+		}
+	}
+}
@@ -7,6 +7,17 @@ use sys::ffi::SoundSource as OpaqueSoundSource;
 use sys::traits::AsRaw;
 
 
+/// Anything that is a sound source - players, synths, delay line taps, etc.,
+/// so it can be added to or removed from a [`Channel`](crate::channel::Channel).
+pub trait AsSoundSource {
+	/// Returns raw pointer to the underlying sound source.
+	///
+	/// # Safety
+	/// Returned pointer is valid while the source is alive.
+	unsafe fn as_source_raw(&self) -> *mut OpaqueSoundSource;
+}
+
+
 #[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
 pub struct SoundSource<Api = api::Default>(*mut OpaqueSoundSource, Api);
 
@@ -15,6 +26,10 @@ impl<Api> AsRaw for SoundSource<Api> {
 	unsafe fn as_raw(&self) -> *mut Self::Type { self.0 }
 }
 
+impl<Api> AsSoundSource for SoundSource<Api> {
+	unsafe fn as_source_raw(&self) -> *mut OpaqueSoundSource { self.0 }
+}
+
 impl<Api: Default> From<*mut OpaqueSoundSource> for SoundSource<Api> {
 	fn from(ptr: *mut OpaqueSoundSource) -> Self { Self(ptr, Default::default()) }
 }
@@ -0,0 +1,594 @@
+//! Playdate synth API
+
+use core::ffi::c_float;
+use core::ffi::c_int;
+use core::ffi::c_void;
+use alloc::boxed::Box;
+
+use sys::ffi::MIDINote;
+use sys::ffi::PDSynth;
+use sys::ffi::PDSynthEnvelope;
+use sys::ffi::PDSynthSignalValue;
+use sys::ffi::SoundSource as OpaqueSoundSource;
+use sys::traits::AsRaw;
+
+use crate::borrow::Ref;
+use crate::error::Error;
+use crate::signal::Signal;
+use crate::sample::Sample;
+use crate::source::AsSoundSource;
+
+pub use sys::ffi::SoundWaveform as Waveform;
+
+pub mod api;
+
+
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Synth<Api: api::Api = api::Default>(*mut PDSynth, Api);
+
+impl<Api: api::Api> AsRaw for Synth<Api> {
+	type Type = PDSynth;
+	unsafe fn as_raw(&self) -> *mut PDSynth { self.0 }
+}
+
+
+// ctor //
+
+impl<Api> Synth<Api> where Api: api::Api {
+	/// Creates a new synth object.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::newSynth`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::newSynth")]
+	pub fn new() -> Result<Synth<Api>, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new synth object with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::newSynth`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::newSynth")]
+	pub fn new_with(api: Api) -> Result<Synth<Api>, Error> {
+		let f = api.new_synth();
+		let synth = unsafe { f() };
+		if synth.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(Synth(synth, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api> AsSoundSource for Synth<Api> {
+	unsafe fn as_source_raw(&self) -> *mut OpaqueSoundSource { self.0 as _ }
+}
+
+
+impl<Api: api::Api> Drop for Synth<Api> {
+	fn drop(&mut self) {
+		if !self.0.is_null() {
+			// Generator's userdata will be freed by the synth using `dealloc` proxy.
+			let f = self.api().free_synth();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+// utils //
+
+impl<Api: api::Api> Synth<Api> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+
+	/// Returns a copy of the synth.
+	///
+	/// If the synth has a [`Generator`], it will be cloned too.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::copy`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::copy")]
+	pub fn try_clone(&self) -> Result<Self, Error>
+		where Api: Clone {
+		let f = self.api().copy();
+		let ptr = unsafe { f(self.0) };
+		if ptr.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(Self(ptr, self.1.clone()))
+		}
+	}
+}
+
+
+// impl //
+
+impl<Api> Synth<Api> where Api: api::Api {
+	/// Sets the waveform of the synth.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setWaveform`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setWaveform")]
+	pub fn set_waveform(&self, waveform: Waveform) {
+		let f = self.api().set_waveform();
+		unsafe { f(self.0, waveform) }
+	}
+
+	/// Provides a `sample` for the synth to play.
+	///
+	/// Sample data must be uncompressed PCM, not ADPCM.
+	///
+	/// If a sustain range is set, it is looped while the synth is playing a note.
+	/// As a convenience, if `sustain_end` is zero and `sustain_start` is greater than zero,
+	/// `sustain_end` will be set to the length of the sample.
+	///
+	/// The synth keeps a reference to the `sample`, so it must remain valid while the synth uses it.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setSample`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setSample")]
+	pub fn set_sample<SApi: crate::sample::api::Api>(&self,
+	                                                 sample: &Sample<SApi>,
+	                                                 sustain_start: u32,
+	                                                 sustain_end: u32) {
+		let f = self.api().set_sample();
+		unsafe { f(self.0, sample.0, sustain_start, sustain_end) }
+	}
+
+	/// Sets a wavetable for the synth to play.
+	///
+	/// Sample data must be 16-bit mono uncompressed.
+	/// `log2size` is the base 2 logarithm of the number of samples in each waveform "cell" in the table,
+	/// and `columns` and `rows` gives the number of cells in each direction.
+	///
+	/// The synth's position in the wavetable is set with [`Synth::set_parameter`].
+	///
+	/// The synth keeps a reference to the `sample`, so it must remain valid while the synth uses it.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setWavetable`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setWavetable")]
+	pub fn set_wavetable<SApi: crate::sample::api::Api>(&self,
+	                                                    sample: &Sample<SApi>,
+	                                                    log2size: c_int,
+	                                                    columns: c_int,
+	                                                    rows: c_int)
+	                                                    -> Result<(), Error> {
+		let f = self.api().set_wavetable();
+		if unsafe { f(self.0, sample.0, log2size, columns, rows) } == 1 {
+			Ok(())
+		} else {
+			Err(Error::last_from(self.api().get_error()).unwrap_or(Error::Alloc))
+		}
+	}
+
+	/// Provides custom waveform `generator` for the synth.
+	///
+	/// Generator's functions are called on the audio render thread,
+	/// so they should return as quickly as possible.
+	///
+	/// If `stereo` is `true`, the generator will be asked to render both channels.
+	///
+	/// The `generator` is owned by the synth and will be dropped when the synth is freed
+	/// or another generator is set.
+	/// It is cloned when the synth is copied with [`Synth::try_clone`],
+	/// so every copy renders with its own generator state.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setGenerator`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setGenerator")]
+	pub fn set_generator<G: Generator + Clone>(&self, stereo: bool, generator: G) {
+		let userdata = Box::into_raw(Box::new(generator)) as *mut c_void;
+		let f = self.api().set_generator();
+		unsafe {
+			f(
+			  self.0,
+			  stereo.into(),
+			  Some(proxy::render::<G>),
+			  Some(proxy::note_on::<G>),
+			  Some(proxy::release::<G>),
+			  Some(proxy::set_parameter::<G>),
+			  Some(proxy::dealloc::<G>),
+			  Some(proxy::copy::<G>),
+			  userdata,
+			)
+		}
+	}
+
+
+	/// Sets the attack time, in seconds.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setAttackTime`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setAttackTime")]
+	pub fn set_attack_time(&self, attack: c_float) {
+		let f = self.api().set_attack_time();
+		unsafe { f(self.0, attack) }
+	}
+
+	/// Sets the decay time, in seconds.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setDecayTime`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setDecayTime")]
+	pub fn set_decay_time(&self, decay: c_float) {
+		let f = self.api().set_decay_time();
+		unsafe { f(self.0, decay) }
+	}
+
+	/// Sets the sustain level, as a proportion of the total level (`0.0` to `1.0`).
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setSustainLevel`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setSustainLevel")]
+	pub fn set_sustain_level(&self, sustain: c_float) {
+		let f = self.api().set_sustain_level();
+		unsafe { f(self.0, sustain) }
+	}
+
+	/// Sets the release time, in seconds.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setReleaseTime`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setReleaseTime")]
+	pub fn set_release_time(&self, release: c_float) {
+		let f = self.api().set_release_time();
+		unsafe { f(self.0, release) }
+	}
+
+	/// Sets attack, decay, sustain and release of the synth's envelope at once.
+	///
+	/// See also [`Synth::set_attack_time`], [`Synth::set_decay_time`],
+	/// [`Synth::set_sustain_level`], [`Synth::set_release_time`].
+	pub fn set_adsr(&self, attack: c_float, decay: c_float, sustain: c_float, release: c_float) {
+		self.set_attack_time(attack);
+		self.set_decay_time(decay);
+		self.set_sustain_level(sustain);
+		self.set_release_time(release);
+	}
+
+	/// Clears the synth's envelope settings.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::clearEnvelope`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::clearEnvelope")]
+	pub fn clear_envelope(&self) {
+		let f = self.api().clear_envelope();
+		unsafe { f(self.0) }
+	}
+
+	/// Returns the synth's [`Envelope`](crate::envelope::Envelope).
+	///
+	/// The synth owns this envelope, so it will __not__ be freed on drop
+	/// and can't outlive the synth.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::getEnvelope`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::getEnvelope")]
+	pub fn envelope(&self) -> Ref<'_, crate::envelope::Envelope<crate::envelope::api::Default, false>> {
+		unsafe { Ref::new(self.envelope_raw().into()) }
+	}
+
+	/// Returns the synth's envelope.
+	///
+	/// The synth owns this envelope, so it must not be freed.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::getEnvelope`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::getEnvelope")]
+	pub fn envelope_raw(&self) -> *mut PDSynthEnvelope {
+		let f = self.api().get_envelope();
+		unsafe { f(self.0) }
+	}
+
+	/// Transposes the synth's output by the given number of half steps.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setTranspose`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setTranspose")]
+	pub fn set_transpose(&self, half_steps: c_float) {
+		let f = self.api().set_transpose();
+		unsafe { f(self.0, half_steps) }
+	}
+
+
+	/// Sets a `signal` to modulate the synth's frequency.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_frequency_modulator_raw`](Self::set_frequency_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the synth is freed.
+	pub unsafe fn set_frequency_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_frequency_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the synth's frequency.
+	///
+	/// The signal is scaled so that a value of `1` doubles the synth pitch (i.e. an octave up)
+	/// and `-1` halves it (an octave down).
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setFrequencyModulator`]
+	///
+	/// # Safety
+	/// `modulator` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setFrequencyModulator")]
+	pub unsafe fn set_frequency_modulator_raw(&self, modulator: *mut PDSynthSignalValue) {
+		let f = self.api().set_frequency_modulator();
+		f(self.0, modulator)
+	}
+
+	/// Returns the currently set frequency modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::getFrequencyModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::getFrequencyModulator")]
+	pub fn frequency_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_frequency_modulator();
+		unsafe { f(self.0) }
+	}
+
+	/// Sets a `signal` to modulate the synth's output amplitude.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_amplitude_modulator_raw`](Self::set_amplitude_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the synth is freed.
+	pub unsafe fn set_amplitude_modulator(&self, signal: Option<&dyn Signal>) {
+		self.set_amplitude_modulator_raw(crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the synth's output amplitude.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setAmplitudeModulator`]
+	///
+	/// # Safety
+	/// `modulator` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setAmplitudeModulator")]
+	pub unsafe fn set_amplitude_modulator_raw(&self, modulator: *mut PDSynthSignalValue) {
+		let f = self.api().set_amplitude_modulator();
+		f(self.0, modulator)
+	}
+
+	/// Returns the currently set amplitude modulator.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::getAmplitudeModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::getAmplitudeModulator")]
+	pub fn amplitude_modulator_raw(&self) -> *mut PDSynthSignalValue {
+		let f = self.api().get_amplitude_modulator();
+		unsafe { f(self.0) }
+	}
+
+
+	/// Returns the number of parameters advertised by the synth.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::getParameterCount`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::getParameterCount")]
+	pub fn parameter_count(&self) -> c_int {
+		let f = self.api().get_parameter_count();
+		unsafe { f(self.0) }
+	}
+
+	/// Sets the (1-based) `parameter` to the given `value`.
+	///
+	/// Returns `false` if `parameter` is not a valid parameter index.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setParameter`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setParameter")]
+	pub fn set_parameter(&self, parameter: c_int, value: c_float) -> bool {
+		let f = self.api().set_parameter();
+		unsafe { f(self.0, parameter, value) != 0 }
+	}
+
+	/// Sets a `signal` to modulate the (1-based) `parameter`.
+	///
+	/// If `signal` is `None`, the modulator is cleared.
+	///
+	/// See also [`set_parameter_modulator_raw`](Self::set_parameter_modulator_raw).
+	///
+	/// # Safety
+	/// `signal` must outlive its use as a modulator,
+	/// i.e. until the modulator is cleared or replaced, or the synth is freed.
+	pub unsafe fn set_parameter_modulator(&self, parameter: c_int, signal: Option<&dyn Signal>) {
+		self.set_parameter_modulator_raw(parameter, crate::signal::as_raw_or_null(signal))
+	}
+
+	/// Sets a signal to modulate the (1-based) `parameter`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setParameterModulator`]
+	///
+	/// # Safety
+	/// `modulator` must be `null` or point to a valid signal, that outlives its use as a modulator.
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setParameterModulator")]
+	pub unsafe fn set_parameter_modulator_raw(&self, parameter: c_int, modulator: *mut PDSynthSignalValue) {
+		let f = self.api().set_parameter_modulator();
+		f(self.0, parameter, modulator)
+	}
+
+	/// Returns the currently set modulator of the (1-based) `parameter`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::getParameterModulator`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::getParameterModulator")]
+	pub fn parameter_modulator_raw(&self, parameter: c_int) -> *mut PDSynthSignalValue {
+		let f = self.api().get_parameter_modulator();
+		unsafe { f(self.0, parameter) }
+	}
+
+
+	/// Plays a note on the synth, at the given frequency.
+	///
+	/// If `len` is `None`, the note keeps playing until a subsequent [`Synth::note_off`] call.
+	///
+	/// If `when` is `0`, the note is played immediately,
+	/// otherwise the note is scheduled for the given time
+	/// (see [`Sound::current_time`](crate::Sound::current_time)).
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::playNote`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::playNote")]
+	pub fn play_note(&self, freq: c_float, vel: c_float, len: Option<c_float>, when: u32) {
+		let f = self.api().play_note();
+		unsafe { f(self.0, freq, vel, len.unwrap_or(-1.0), when) }
+	}
+
+	/// The same as [`Synth::play_note`] but uses MIDI note (where `60` = C4) instead of frequency.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::playMIDINote`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::playMIDINote")]
+	pub fn play_midi_note(&self, note: MIDINote, vel: c_float, len: Option<c_float>, when: u32) {
+		let f = self.api().play_midi_note();
+		unsafe { f(self.0, note, vel, len.unwrap_or(-1.0), when) }
+	}
+
+	/// Sends a note off event to the synth,
+	/// either immediately (`when` = `0`) or at the scheduled time.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::noteOff`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::noteOff")]
+	pub fn note_off(&self, when: u32) {
+		let f = self.api().note_off();
+		unsafe { f(self.0, when) }
+	}
+
+	/// Stops the synth immediately, without playing the release part of the envelope.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::stop`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::stop")]
+	pub fn stop(&self) {
+		let f = self.api().stop();
+		unsafe { f(self.0) }
+	}
+
+	/// Returns `true` if the synth is playing.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::isPlaying`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::isPlaying")]
+	pub fn is_playing(&self) -> bool {
+		let f = self.api().is_playing();
+		unsafe { f(self.0) == 1 }
+	}
+
+
+	/// Gets the playback volume for left and right channels of the synth.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::getVolume`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::getVolume")]
+	pub fn volume(&self) -> (c_float, c_float) {
+		let (mut left, mut right) = (0.0, 0.0);
+		let f = self.api().get_volume();
+		unsafe { f(self.0, &mut left, &mut right) };
+		(left, right)
+	}
+
+	/// Sets the playback volume (`0.0` - `1.0`) for left and right channels of the synth.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_synth::setVolume`]
+	#[doc(alias = "sys::ffi::playdate_sound_synth::setVolume")]
+	pub fn set_volume(&self, left: c_float, right: c_float) {
+		let f = self.api().set_volume();
+		unsafe { f(self.0, left, right) }
+	}
+}
+
+
+/// Custom waveform generator for the [`Synth`].
+///
+/// All functions are called on the audio render thread.
+///
+/// Implemented for closures `FnMut(left, right, rate, drate) -> usize`,
+/// that are only able to render.
+pub trait Generator: 'static {
+	/// Called every audio cycle to get the samples for playback.
+	///
+	/// `left` (and `right` if the generator is stereo) are sample buffers in Q8.24 format.
+	/// `rate` is the amount to change a (Q32) phase accumulator each sample,
+	/// and `drate` is the amount to change `rate` each sample.
+	///
+	/// Returns the number of rendered samples, `0` means silence.
+	fn render(&mut self, left: &mut [i32], right: Option<&mut [i32]>, rate: u32, drate: i32) -> usize;
+
+	/// Called when the synth receives a note on event.
+	///
+	/// `len` is the length of the note in seconds, or `None` if it's not known yet when the note will end.
+	fn note_on(&mut self, note: MIDINote, velocity: c_float, len: Option<c_float>) {
+		let _ = (note, velocity, len);
+	}
+
+	/// Called when the synth receives a note off event.
+	///
+	/// `end_offset` is how many samples into the current render cycle the note ends.
+	fn release(&mut self, end_offset: c_int) { let _ = end_offset; }
+
+	/// Called when a parameter change is received from [`Synth::set_parameter`] or a modulator.
+	///
+	/// Returns `true` if the parameter is known and was applied.
+	fn set_parameter(&mut self, parameter: c_int, value: c_float) -> bool {
+		let _ = (parameter, value);
+		false
+	}
+}
+
+impl<F> Generator for F where F: 'static + FnMut(&mut [i32], Option<&mut [i32]>, u32, i32) -> usize {
+	fn render(&mut self, left: &mut [i32], right: Option<&mut [i32]>, rate: u32, drate: i32) -> usize {
+		self(left, right, rate, drate)
+	}
+}
+
+
+mod proxy {
+	use core::ffi::c_float;
+	use core::ffi::c_int;
+	use core::ffi::c_void;
+	use alloc::boxed::Box;
+	use sys::ffi::MIDINote;
+	use super::Generator;
+
+
+	pub unsafe extern "C" fn render<G: Generator>(userdata: *mut c_void,
+	                                              left: *mut i32,
+	                                              right: *mut i32,
+	                                              nsamples: c_int,
+	                                              rate: u32,
+	                                              drate: i32)
+	                                              -> c_int {
+		let Some(generator) = (userdata as *mut G).as_mut() else {
+			return 0;
+		};
+		let len = nsamples.max(0) as usize;
+		let left = core::slice::from_raw_parts_mut(left, len);
+		let right = (!right.is_null()).then(|| core::slice::from_raw_parts_mut(right, len));
+		generator.render(left, right, rate, drate).min(len) as _
+	}
+
+	pub unsafe extern "C" fn note_on<G: Generator>(userdata: *mut c_void,
+	                                               note: MIDINote,
+	                                               velocity: c_float,
+	                                               len: c_float) {
+		if let Some(generator) = (userdata as *mut G).as_mut() {
+			generator.note_on(note, velocity, (len >= 0.0).then_some(len))
+		}
+	}
+
+	pub unsafe extern "C" fn release<G: Generator>(userdata: *mut c_void, end_offset: c_int) {
+		if let Some(generator) = (userdata as *mut G).as_mut() {
+			generator.release(end_offset)
+		}
+	}
+
+	pub unsafe extern "C" fn set_parameter<G: Generator>(userdata: *mut c_void,
+	                                                     parameter: c_int,
+	                                                     value: c_float)
+	                                                     -> c_int {
+		if let Some(generator) = (userdata as *mut G).as_mut() {
+			generator.set_parameter(parameter, value).into()
+		} else {
+			0
+		}
+	}
+
+	pub unsafe extern "C" fn dealloc<G: Generator>(userdata: *mut c_void) {
+		if !userdata.is_null() {
+			drop(Box::from_raw(userdata as *mut G))
+		}
+	}
+
+	pub unsafe extern "C" fn copy<G: Generator + Clone>(userdata: *mut c_void) -> *mut c_void {
+		if let Some(generator) = (userdata as *mut G).as_ref() {
+			Box::into_raw(Box::new(generator.clone())) as *mut c_void
+		} else {
+			core::ptr::null_mut()
+		}
+	}
+}
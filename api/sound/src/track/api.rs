@@ -0,0 +1,178 @@
+use core::ffi::c_float;
+use core::ffi::c_int;
+use core::ptr::NonNull;
+
+use sys::ffi::ControlSignal;
+use sys::ffi::MIDINote;
+use sys::ffi::PDSynthInstrument;
+use sys::ffi::SequenceTrack;
+use sys::ffi::playdate_sound_track;
+
+
+/// Default sequence track api end-point, ZST.
+///
+/// All calls approximately costs ~4 derefs.
+#[derive(Debug, Clone, Copy, core::default::Default)]
+pub struct Default;
+impl Api for Default {}
+
+
+/// Cached sequence track api end-point.
+///
+/// Stores one reference, so size on stack is eq `usize`.
+///
+/// All calls approximately costs ~1 deref.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Cache(&'static playdate_sound_track);
+
+impl core::default::Default for Cache {
+	fn default() -> Self { Self(sys::api!(sound.track)) }
+}
+
+impl From<*const playdate_sound_track> for Cache {
+	#[inline(always)]
+	fn from(ptr: *const playdate_sound_track) -> Self { Self(unsafe { ptr.as_ref() }.expect("track")) }
+}
+
+impl From<&'static playdate_sound_track> for Cache {
+	#[inline(always)]
+	fn from(r: &'static playdate_sound_track) -> Self { Self(r) }
+}
+
+impl From<NonNull<playdate_sound_track>> for Cache {
+	#[inline(always)]
+	fn from(ptr: NonNull<playdate_sound_track>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+impl From<&'_ NonNull<playdate_sound_track>> for Cache {
+	#[inline(always)]
+	fn from(ptr: &NonNull<playdate_sound_track>) -> Self { Self(unsafe { ptr.as_ref() }) }
+}
+
+
+impl Api for Cache {
+	fn new_track(&self) -> FnNewTrack { self.0.newTrack.expect("newTrack") }
+	fn free_track(&self) -> FnFreeTrack { self.0.freeTrack.expect("freeTrack") }
+	fn set_instrument(&self) -> FnSetInstrument { self.0.setInstrument.expect("setInstrument") }
+	fn get_instrument(&self) -> FnGetInstrument { self.0.getInstrument.expect("getInstrument") }
+	fn add_note_event(&self) -> FnAddNoteEvent { self.0.addNoteEvent.expect("addNoteEvent") }
+	fn remove_note_event(&self) -> FnRemoveNoteEvent { self.0.removeNoteEvent.expect("removeNoteEvent") }
+	fn clear_notes(&self) -> FnClearNotes { self.0.clearNotes.expect("clearNotes") }
+	fn get_control_signal_count(&self) -> FnGetControlSignalCount {
+		self.0.getControlSignalCount.expect("getControlSignalCount")
+	}
+	fn get_control_signal(&self) -> FnGetControlSignal { self.0.getControlSignal.expect("getControlSignal") }
+	fn clear_control_events(&self) -> FnClearControlEvents {
+		self.0.clearControlEvents.expect("clearControlEvents")
+	}
+	fn get_polyphony(&self) -> FnGetPolyphony { self.0.getPolyphony.expect("getPolyphony") }
+	fn active_voice_count(&self) -> FnActiveVoiceCount { self.0.activeVoiceCount.expect("activeVoiceCount") }
+	fn set_muted(&self) -> FnSetMuted { self.0.setMuted.expect("setMuted") }
+	fn get_length(&self) -> FnGetLength { self.0.getLength.expect("getLength") }
+	fn get_index_for_step(&self) -> FnGetIndexForStep { self.0.getIndexForStep.expect("getIndexForStep") }
+	fn get_note_at_index(&self) -> FnGetNoteAtIndex { self.0.getNoteAtIndex.expect("getNoteAtIndex") }
+	fn get_signal_for_controller(&self) -> FnGetSignalForController {
+		self.0.getSignalForController.expect("getSignalForController")
+	}
+}
+
+
+pub trait Api {
+	/// Returns [`sys::ffi::playdate_sound_track::newTrack`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::newTrack")]
+	fn new_track(&self) -> FnNewTrack { *sys::api!(sound.track.newTrack) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::freeTrack`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::freeTrack")]
+	fn free_track(&self) -> FnFreeTrack { *sys::api!(sound.track.freeTrack) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::setInstrument`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::setInstrument")]
+	fn set_instrument(&self) -> FnSetInstrument { *sys::api!(sound.track.setInstrument) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::getInstrument`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getInstrument")]
+	fn get_instrument(&self) -> FnGetInstrument { *sys::api!(sound.track.getInstrument) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::addNoteEvent`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::addNoteEvent")]
+	fn add_note_event(&self) -> FnAddNoteEvent { *sys::api!(sound.track.addNoteEvent) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::removeNoteEvent`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::removeNoteEvent")]
+	fn remove_note_event(&self) -> FnRemoveNoteEvent { *sys::api!(sound.track.removeNoteEvent) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::clearNotes`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::clearNotes")]
+	fn clear_notes(&self) -> FnClearNotes { *sys::api!(sound.track.clearNotes) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::getControlSignalCount`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getControlSignalCount")]
+	fn get_control_signal_count(&self) -> FnGetControlSignalCount { *sys::api!(sound.track.getControlSignalCount) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::getControlSignal`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getControlSignal")]
+	fn get_control_signal(&self) -> FnGetControlSignal { *sys::api!(sound.track.getControlSignal) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::clearControlEvents`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::clearControlEvents")]
+	fn clear_control_events(&self) -> FnClearControlEvents { *sys::api!(sound.track.clearControlEvents) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::getPolyphony`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getPolyphony")]
+	fn get_polyphony(&self) -> FnGetPolyphony { *sys::api!(sound.track.getPolyphony) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::activeVoiceCount`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::activeVoiceCount")]
+	fn active_voice_count(&self) -> FnActiveVoiceCount { *sys::api!(sound.track.activeVoiceCount) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::setMuted`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::setMuted")]
+	fn set_muted(&self) -> FnSetMuted { *sys::api!(sound.track.setMuted) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::getLength`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getLength")]
+	fn get_length(&self) -> FnGetLength { *sys::api!(sound.track.getLength) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::getIndexForStep`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getIndexForStep")]
+	fn get_index_for_step(&self) -> FnGetIndexForStep { *sys::api!(sound.track.getIndexForStep) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::getNoteAtIndex`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getNoteAtIndex")]
+	fn get_note_at_index(&self) -> FnGetNoteAtIndex { *sys::api!(sound.track.getNoteAtIndex) }
+
+	/// Returns [`sys::ffi::playdate_sound_track::getSignalForController`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getSignalForController")]
+	fn get_signal_for_controller(&self) -> FnGetSignalForController {
+		*sys::api!(sound.track.getSignalForController)
+	}
+}
+
+
+type FnNewTrack = unsafe extern "C" fn() -> *mut SequenceTrack;
+type FnFreeTrack = unsafe extern "C" fn(track: *mut SequenceTrack);
+type FnSetInstrument = unsafe extern "C" fn(track: *mut SequenceTrack, instrument: *mut PDSynthInstrument);
+type FnGetInstrument = unsafe extern "C" fn(track: *mut SequenceTrack) -> *mut PDSynthInstrument;
+type FnAddNoteEvent =
+	unsafe extern "C" fn(track: *mut SequenceTrack, step: u32, len: u32, note: MIDINote, velocity: c_float);
+type FnRemoveNoteEvent = unsafe extern "C" fn(track: *mut SequenceTrack, step: u32, note: MIDINote);
+type FnClearNotes = unsafe extern "C" fn(track: *mut SequenceTrack);
+type FnGetControlSignalCount = unsafe extern "C" fn(track: *mut SequenceTrack) -> c_int;
+type FnGetControlSignal = unsafe extern "C" fn(track: *mut SequenceTrack, index: c_int) -> *mut ControlSignal;
+type FnClearControlEvents = unsafe extern "C" fn(track: *mut SequenceTrack);
+type FnGetPolyphony = unsafe extern "C" fn(track: *mut SequenceTrack) -> c_int;
+type FnActiveVoiceCount = unsafe extern "C" fn(track: *mut SequenceTrack) -> c_int;
+type FnSetMuted = unsafe extern "C" fn(track: *mut SequenceTrack, mute: c_int);
+type FnGetLength = unsafe extern "C" fn(track: *mut SequenceTrack) -> u32;
+type FnGetIndexForStep = unsafe extern "C" fn(track: *mut SequenceTrack, step: u32) -> c_int;
+type FnGetNoteAtIndex = unsafe extern "C" fn(track: *mut SequenceTrack,
+                                             index: c_int,
+                                             out_step: *mut u32,
+                                             out_len: *mut u32,
+                                             out_note: *mut MIDINote,
+                                             out_velocity: *mut c_float)
+                                             -> c_int;
+type FnGetSignalForController =
+	unsafe extern "C" fn(track: *mut SequenceTrack, controller: c_int, create: c_int) -> *mut ControlSignal;
@@ -0,0 +1,395 @@
+//! Playdate sequence track API
+
+use core::ffi::c_float;
+use core::ffi::c_int;
+
+use sys::ffi::ControlSignal as OpaqueControlSignal;
+use sys::ffi::MIDINote;
+use sys::ffi::SequenceTrack;
+use sys::traits::AsRaw;
+
+use crate::borrow::Ref;
+use crate::control::ControlSignal;
+use crate::error::Error;
+use crate::instrument::Instrument;
+
+pub mod api;
+
+
+/// Position in a sequence, in steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Step(pub u32);
+
+/// Duration of a note or a track, in steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Length(pub u32);
+
+/// Note velocity, from `0.0` to `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Velocity(c_float);
+
+impl Velocity {
+	/// Maximum velocity, `1.0`.
+	pub const MAX: Self = Self(1.0);
+
+	/// Creates new velocity, clamping `value` into `0.0..=1.0`.
+	pub fn new(value: c_float) -> Self { Self(value.clamp(0.0, 1.0)) }
+
+	#[inline(always)]
+	pub const fn get(&self) -> c_float { self.0 }
+}
+
+impl From<c_float> for Velocity {
+	fn from(value: c_float) -> Self { Self::new(value) }
+}
+
+impl From<Velocity> for c_float {
+	fn from(value: Velocity) -> Self { value.0 }
+}
+
+
+/// Single note event of the [`Track`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Note {
+	pub step: Step,
+	pub len: Length,
+	pub note: MIDINote,
+	pub velocity: Velocity,
+}
+
+
+#[cfg_attr(feature = "bindings-derive-debug", derive(Debug))]
+pub struct Track<Api: api::Api = api::Default, const FREE_ON_DROP: bool = true>(*mut SequenceTrack, Api);
+
+impl<Api: api::Api, const FOD: bool> AsRaw for Track<Api, FOD> {
+	type Type = SequenceTrack;
+	unsafe fn as_raw(&self) -> *mut SequenceTrack { self.0 }
+}
+
+impl<Api: api::Api + Default, const FOD: bool> From<*mut SequenceTrack> for Track<Api, FOD> {
+	fn from(ptr: *mut SequenceTrack) -> Self { Self(ptr, Api::default()) }
+}
+
+impl<Api: api::Api, const FOD: bool> Track<Api, FOD> {
+	/// Wraps the raw `ptr` to the track with given `api`.
+	///
+	/// # Safety
+	/// `ptr` must point to a valid track.
+	/// If `FREE_ON_DROP` is `true`, the wrapper takes ownership of it and frees it on drop,
+	/// otherwise the track must outlive the wrapper.
+	pub unsafe fn from_with(api: Api, ptr: *mut SequenceTrack) -> Self { Self(ptr, api) }
+}
+
+impl<Api: api::Api> Track<Api, true> {
+	/// Convert this track into the same track that will not be freed on drop.
+	/// That means that only C-part of the track will __not__ be freed.
+	///
+	/// __Safety is guaranteed by the caller.__
+	pub fn into_shared(mut self) -> Track<Api, false>
+		where Api: Copy {
+		let res = Track(self.0, self.1);
+		self.0 = core::ptr::null_mut();
+		res
+	}
+}
+
+
+// ctor //
+
+impl<Api> Track<Api, true> where Api: api::Api {
+	/// Creates a new track.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::newTrack`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::newTrack")]
+	pub fn new() -> Result<Self, Error>
+		where Api: Default {
+		let api = Api::default();
+		Self::new_with(api)
+	}
+
+	/// Creates a new track with given `api`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::newTrack`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::newTrack")]
+	pub fn new_with(api: Api) -> Result<Self, Error> {
+		let f = api.new_track();
+		let track = unsafe { f() };
+		if track.is_null() {
+			Err(Error::Alloc)
+		} else {
+			Ok(Track(track, api))
+		}
+	}
+}
+
+
+impl<Api: api::Api, const FOD: bool> Drop for Track<Api, FOD> {
+	fn drop(&mut self) {
+		if FOD && !self.0.is_null() {
+			let f = self.api().free_track();
+			unsafe { f(self.0) }
+			self.0 = core::ptr::null_mut();
+		}
+	}
+}
+
+
+// utils //
+
+impl<Api: api::Api, const FOD: bool> Track<Api, FOD> {
+	#[inline(always)]
+	pub fn api(&self) -> &Api { &self.1 }
+}
+
+
+// impl //
+
+impl<Api: api::Api, const FOD: bool> Track<Api, FOD> {
+	/// Sets the [`Instrument`] assigned to the track.
+	///
+	/// The track does not own the instrument,
+	/// so it can be shared between tracks and must remain valid while the track uses it.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::setInstrument`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::setInstrument")]
+	pub fn set_instrument<IApi: crate::instrument::api::Api, const IFOD: bool>(&self,
+	                                                                           instrument: &Instrument<
+	                                                                                       IApi,
+	                                                                                       IFOD,
+	>) {
+		let f = self.api().set_instrument();
+		unsafe { f(self.0, instrument.as_raw()) }
+	}
+
+	/// Returns the [`Instrument`] assigned to the track.
+	///
+	/// Returned instrument is not owned, so it will __not__ be freed on drop.
+	/// It is borrowed along with the track, so the assigned instrument must outlive the track.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getInstrument`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getInstrument")]
+	pub fn instrument(&self) -> Option<Ref<'_, Instrument<crate::instrument::api::Default, false>>> {
+		self.instrument_with(Default::default())
+	}
+
+	/// Returns the [`Instrument`] assigned to the track, using given `api`.
+	///
+	/// Returned instrument is not owned, so it will __not__ be freed on drop.
+	/// It is borrowed along with the track, so the assigned instrument must outlive the track.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getInstrument`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getInstrument")]
+	pub fn instrument_with<IApi: crate::instrument::api::Api>(&self,
+	                                                          api: IApi)
+	                                                          -> Option<Ref<'_, Instrument<IApi, false>>> {
+		let f = self.api().get_instrument();
+		let ptr = unsafe { f(self.0) };
+		if ptr.is_null() {
+			None
+		} else {
+			Some(unsafe { Ref::new(Instrument::from_with(api, ptr)) })
+		}
+	}
+
+
+	/// Adds a single note event to the track.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::addNoteEvent`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::addNoteEvent")]
+	pub fn add_note(&self, step: Step, len: Length, note: MIDINote, velocity: Velocity) {
+		let f = self.api().add_note_event();
+		unsafe { f(self.0, step.0, len.0, note, velocity.0) }
+	}
+
+	/// Adds a single [`Note`] event to the track.
+	///
+	/// See also [`Track::add_note`].
+	#[inline(always)]
+	pub fn add(&self, note: Note) { self.add_note(note.step, note.len, note.note, note.velocity) }
+
+	/// Removes the event at `step` playing `note`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::removeNoteEvent`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::removeNoteEvent")]
+	pub fn remove_note(&self, step: Step, note: MIDINote) {
+		let f = self.api().remove_note_event();
+		unsafe { f(self.0, step.0, note) }
+	}
+
+	/// Clears all notes from the track.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::clearNotes`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::clearNotes")]
+	pub fn clear_notes(&self) {
+		let f = self.api().clear_notes();
+		unsafe { f(self.0) }
+	}
+
+	/// Returns the internal array index for the first note at the given `step`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getIndexForStep`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getIndexForStep")]
+	pub fn index_for_step(&self, step: Step) -> c_int {
+		let f = self.api().get_index_for_step();
+		unsafe { f(self.0, step.0) }
+	}
+
+	/// Returns the [`Note`] at the given internal `index`, if the `index` is in range.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getNoteAtIndex`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getNoteAtIndex")]
+	pub fn note_at(&self, index: c_int) -> Option<Note> {
+		let mut step = 0;
+		let mut len = 0;
+		let mut note = 0.0;
+		let mut velocity = 0.0;
+		let f = self.api().get_note_at_index();
+		if unsafe { f(self.0, index, &mut step, &mut len, &mut note, &mut velocity) } == 1 {
+			Some(Note { step: Step(step),
+			            len: Length(len),
+			            note,
+			            velocity: Velocity(velocity) })
+		} else {
+			None
+		}
+	}
+
+	/// Returns iterator over all [`Note`]s of the track, starting from `step`.
+	///
+	/// See also [`Track::index_for_step`], [`Track::note_at`].
+	pub fn notes_from(&self, step: Step) -> impl Iterator<Item = Note> + '_ {
+		let start = self.index_for_step(step).max(0);
+		(start..).map_while(|i| self.note_at(i))
+	}
+
+	/// Returns iterator over all [`Note`]s of the track.
+	#[inline(always)]
+	pub fn notes(&self) -> impl Iterator<Item = Note> + '_ { self.notes_from(Step(0)) }
+
+
+	/// Returns the length, in steps, of the track - that is, the step where the last note in the track ends.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getLength`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getLength")]
+	pub fn length(&self) -> Length {
+		let f = self.api().get_length();
+		Length(unsafe { f(self.0) })
+	}
+
+	/// Returns the maximum number of simultaneously playing notes in the track.
+	///
+	/// Currently, this value is only set when the track was loaded from a MIDI file.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getPolyphony`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getPolyphony")]
+	pub fn polyphony(&self) -> c_int {
+		let f = self.api().get_polyphony();
+		unsafe { f(self.0) }
+	}
+
+	/// Returns the number of voices currently playing in the track's instrument.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::activeVoiceCount`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::activeVoiceCount")]
+	pub fn active_voice_count(&self) -> c_int {
+		let f = self.api().active_voice_count();
+		unsafe { f(self.0) }
+	}
+
+	/// Mutes or unmutes the track.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::setMuted`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::setMuted")]
+	pub fn set_muted(&self, value: bool) {
+		let f = self.api().set_muted();
+		unsafe { f(self.0, value.into()) }
+	}
+
+
+	/// Returns the number of control signals in the track.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getControlSignalCount`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getControlSignalCount")]
+	pub fn control_signal_count(&self) -> c_int {
+		let f = self.api().get_control_signal_count();
+		unsafe { f(self.0) }
+	}
+
+	/// Returns the [`ControlSignal`] at `index`.
+	///
+	/// Returned signal is owned by the track, so it will __not__ be freed on drop
+	/// and can't outlive the track.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getControlSignal`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getControlSignal")]
+	pub fn control_signal(&self,
+	                      index: c_int)
+	                      -> Option<Ref<'_, ControlSignal<crate::control::api::Default, false>>> {
+		let ptr = self.control_signal_raw(index);
+		if ptr.is_null() {
+			None
+		} else {
+			Some(unsafe { Ref::new(ptr.into()) })
+		}
+	}
+
+	/// Returns iterator over all [`ControlSignal`]s of the track.
+	///
+	/// See also [`Track::control_signal`].
+	pub fn control_signals(
+		&self)
+		-> impl Iterator<Item = Ref<'_, ControlSignal<crate::control::api::Default, false>>> + '_ {
+		(0..self.control_signal_count()).filter_map(|i| self.control_signal(i))
+	}
+
+	/// Returns the control signal at `index`.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getControlSignal`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getControlSignal")]
+	pub fn control_signal_raw(&self, index: c_int) -> *mut OpaqueControlSignal {
+		let f = self.api().get_control_signal();
+		unsafe { f(self.0, index) }
+	}
+
+	/// Returns the [`ControlSignal`] for MIDI `controller` number,
+	/// creating it if the `create` is set and it doesn't yet exist.
+	///
+	/// Returned signal is owned by the track, so it will __not__ be freed on drop
+	/// and can't outlive the track.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getSignalForController`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getSignalForController")]
+	pub fn signal_for_controller(&self,
+	                             controller: c_int,
+	                             create: bool)
+	                             -> Option<Ref<'_, ControlSignal<crate::control::api::Default, false>>> {
+		let ptr = self.signal_for_controller_raw(controller, create);
+		if ptr.is_null() {
+			None
+		} else {
+			Some(unsafe { Ref::new(ptr.into()) })
+		}
+	}
+
+	/// Returns the control signal for MIDI `controller` number,
+	/// creating it if the `create` is set and it doesn't yet exist.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::getSignalForController`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::getSignalForController")]
+	pub fn signal_for_controller_raw(&self, controller: c_int, create: bool) -> *mut OpaqueControlSignal {
+		let f = self.api().get_signal_for_controller();
+		unsafe { f(self.0, controller, create.into()) }
+	}
+
+	/// Clears all control signals from the track.
+	///
+	/// Borrowed [`ControlSignal`]s of the track are freed,
+	/// so this requires exclusive access to the track.
+	///
+	/// Equivalent to [`sys::ffi::playdate_sound_track::clearControlEvents`]
+	#[doc(alias = "sys::ffi::playdate_sound_track::clearControlEvents")]
+	pub fn clear_control_events(&mut self) {
+		let f = self.api().clear_control_events();
+		unsafe { f(self.0) }
+	}
+}
@@ -0,0 +1,92 @@
+//! Sprites with animated images.
+
+use core::marker::PhantomData;
+use core::time::Duration;
+
+use gfx::bitmap::animation::Animation;
+use gfx::bitmap::animation::FrameTable;
+
+use crate::Sprite;
+use crate::SpriteType;
+use crate::SharedSprite;
+use crate::api;
+use super::update::Handle;
+use super::update::SpriteUpdate;
+
+
+/// User-data of the sprite, that carries animation of its image.
+pub trait SpriteAnimation {
+	type Table: FrameTable;
+
+	fn animation(&mut self) -> &mut Animation<Self::Table>;
+}
+
+impl<T: FrameTable> SpriteAnimation for Animation<T> {
+	type Table = T;
+
+	fn animation(&mut self) -> &mut Animation<T> { self }
+}
+
+
+/// Update handler advancing the animation carried by the sprite's user-data
+/// and setting the current frame as the sprite's image when it changes.
+///
+/// Animation is updated with the [current time](sys::ffi::playdate_sys::getCurrentTimeMilliseconds),
+/// so it isn't affected by resetting of the elapsed time.
+/// Flip of the sprite's image is preserved.
+///
+/// ```ignore
+/// let sprite = Sprite::<Animation>::new();
+/// sprite.set_animation(Animation::new(table, Duration::from_millis(100)));
+/// let sprite = sprite.into_update_handler::<AnimatedUpdate>();
+/// ```
+pub struct AnimatedUpdate<UD = Animation, Api = api::Default>(PhantomData<(UD, Api)>);
+
+impl<UD, Api: api::Api> SpriteType for AnimatedUpdate<UD, Api> {
+	type Api = Api;
+	type Userdata = UD;
+}
+
+impl<UD: SpriteAnimation, Api: api::Api> SpriteUpdate for AnimatedUpdate<UD, Api> {
+	fn on_update(sprite: &Handle<false, SharedSprite<UD, Api>, Self>) {
+		let Some(ud) = sprite.userdata() else { return };
+		let animation = ud.animation();
+		if animation.update(current_time()) {
+			if let Some(frame) = animation.frame() {
+				sprite.set_image(frame, sprite.image_flip());
+			}
+		}
+	}
+}
+
+fn current_time() -> Duration {
+	let f = *sys::api!(system.getCurrentTimeMilliseconds);
+	Duration::from_millis(unsafe { f() }.into())
+}
+
+
+impl<T: FrameTable, Api: api::Api, const FOD: bool> Sprite<Animation<T>, Api, FOD> {
+	/// Sets the animation of the sprite's image and its current frame as the image.
+	///
+	/// The animation is stored as user-data of the sprite
+	/// and advanced by the [`AnimatedUpdate`] handler.
+	pub fn set_animation(&self, animation: Animation<T>) {
+		match self.userdata() {
+			Some(current) => *current = animation,
+			None => self.set_userdata(animation),
+		}
+		self.update_animation_frame();
+	}
+
+	/// Returns the animation of the sprite's image.
+	pub fn animation(&self) -> Option<&mut Animation<T>> { self.userdata() }
+
+	/// Sets the current frame of the sprite's animation as the image.
+	///
+	/// Useful after the animation is changed manually, e.g. with [`Animation::advance`] or [`Animation::seek`].
+	pub fn update_animation_frame(&self) {
+		if let Some(frame) = self.userdata().and_then(|animation| animation.frame()) {
+			self.set_image(frame, self.image_flip());
+		}
+	}
+}
@@ -0,0 +1,78 @@
+//! Sprites with transformed images.
+
+use core::marker::PhantomData;
+
+use sys::ffi::PDRect;
+use gfx::geometry::Affine2D;
+
+use crate::Sprite;
+use crate::SpriteType;
+use crate::SharedSprite;
+use crate::api;
+use super::draw::Handle;
+use super::draw::SpriteDraw;
+
+
+/// User-data of the sprite, that carries transform of its image.
+pub trait SpriteTransform {
+	/// Transform of the image relative to the top-left corner of the sprite's bounds.
+	fn transform(&self) -> Affine2D;
+}
+
+impl SpriteTransform for Affine2D {
+	fn transform(&self) -> Affine2D { *self }
+}
+
+
+/// Draw handler drawing the sprite's image with the transform carried by its user-data.
+///
+/// Image is drawn with [`Bitmap::draw_transformed`](gfx::bitmap::Bitmap::draw_transformed),
+/// sprites without user-data are drawn without transform.
+///
+/// Bounds of the sprite are not changed by the transform,
+/// set them to contain the transformed image, e.g. with [`Affine2D::apply_rect`].
+///
+/// ```ignore
+/// let sprite = Sprite::<Affine2D>::new();
+/// sprite.set_image(image, BitmapFlip::kBitmapUnflipped);
+/// sprite.set_bounds(bounds);
+/// sprite.set_transform(Affine2D::rotation_about(30.0, 16.0, 16.0));
+/// let sprite = sprite.into_draw_handler::<TransformedDraw>();
+/// ```
+pub struct TransformedDraw<UD = Affine2D, Api = api::Default>(PhantomData<(UD, Api)>);
+
+impl<UD, Api: api::Api> SpriteType for TransformedDraw<UD, Api> {
+	type Api = Api;
+	type Userdata = UD;
+}
+
+impl<UD: SpriteTransform, Api: api::Api> SpriteDraw for TransformedDraw<UD, Api> {
+	fn on_draw(sprite: &Handle<false, SharedSprite<UD, Api>, Self>, bounds: PDRect, _: PDRect) {
+		let Some(Ok(mut image)) = sprite.image().map(|image| image.into_bitmap()) else {
+			return;
+		};
+		let transform = sprite.userdata()
+		                      .map(|ud| ud.transform())
+		                      .unwrap_or_default()
+		                      .translate(bounds.x, bounds.y);
+		image.draw_transformed(&transform).ok();
+	}
+}
+
+
+impl<Api: api::Api, const FOD: bool> Sprite<Affine2D, Api, FOD> {
+	/// Sets the transform of the sprite's image and marks the sprite dirty.
+	///
+	/// The transform is stored as user-data of the sprite
+	/// and applied by the [`TransformedDraw`] handler.
+	pub fn set_transform(&self, transform: Affine2D) {
+		match self.userdata() {
+			Some(current) => *current = transform,
+			None => self.set_userdata(transform),
+		}
+		self.mark_dirty();
+	}
+
+	/// Returns the transform of the sprite's image, identity by default.
+	pub fn transform(&self) -> Affine2D { self.userdata().map(|t| *t).unwrap_or_default() }
+}
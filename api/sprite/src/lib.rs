@@ -19,6 +19,9 @@ pub mod callback {
 	pub mod draw;
 	pub mod update;
 	pub mod collision;
+	#[cfg(feature = "transform")]
+	pub mod transform;
+	pub mod animation;
 }
 
 pub mod prelude {
@@ -12,6 +12,8 @@ use crate::metadata::format::AssetsOptions;
 
 pub mod plan;
 pub mod resolver;
+#[cfg(feature = "tiled")]
+pub mod tiled;
 use self::plan::*;
 
 
@@ -118,6 +120,31 @@ pub fn apply_build_plan<'l, 'r, P: AsRef<Path>>(plan: BuildPlan<'l, 'r>,
 		AssetsBuildMethod::Link => &link_method,
 	};
 
+	// converts Tiled maps instead of copying or linking them:
+	#[cfg(feature = "tiled")]
+	let convert_method = |source: &Path, target: &Path| -> Result<OpRes, FsExtraError> {
+		let into = target_root.join(target);
+		let into_parent = parent_of(&into)?;
+		ensure_dir_exists(into_parent, target_root)?;
+		ensure_out_of_root(into_parent)?;
+
+		let converted = if !into.try_exists()? || overwrite {
+			tiled::convert(source, &into, overwrite).map(OpRes::Write)?
+		} else {
+			OpRes::Skip
+		};
+		info!("  {converted:?} convert: {} <- {}", target.display(), source.display());
+		Ok(converted)
+	};
+
+	let method = |source: &Path, target: &Path, to_inside: bool| -> Result<OpRes, FsExtraError> {
+		#[cfg(feature = "tiled")]
+		if !to_inside && tiled::is_conversion(source, target) {
+			return convert_method(source, target);
+		}
+		method(source, target, to_inside)
+	};
+
 	let (mut plan, crate_root) = plan.into_parts();
 	let mut results = HashMap::with_capacity(plan.len());
 	for entry in plan.drain(..) {
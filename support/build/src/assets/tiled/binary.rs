@@ -0,0 +1,226 @@
+//! Encoder of the binary map, `PDTM`.
+//!
+//! All numbers are little-endian, strings are `u16` length followed by UTF-8 bytes.
+//!
+//! ```text
+//! map:       "PDTM" u8:version u32:width u32:height u16:tile_width u16:tile_height props
+//!            u16:count tileset* u16:count layer*
+//! tileset:   str:table u32:first_gid u32:tile_count u32:columns u16:tile_width u16:tile_height
+//!            u32:count (u32:id u16:count (i16:x i16:y i16:w i16:h)*)*
+//! layer:     u8:kind str:name u8:visible i32:offset_x i32:offset_y props
+//!            kind 0, tiles:   u32:width u32:height u32:gid*
+//!            kind 1, objects: u32:count object*
+//! object:    u32:id str:name str:class i32:x i32:y i32:w i32:h u32:gid u8:shape
+//!            u32:count (i32:x i32:y)* props
+//! props:     u16:count (str:name u8:type value)*
+//! ```
+//!
+//! Property types: 0 string, 1 int, 2 float, 3 bool, 4 color, 5 file, 6 object.
+
+use std::io::Result;
+
+use super::*;
+
+
+/// Magic bytes of the binary map.
+pub const MAGIC: &[u8; 4] = b"PDTM";
+/// Version of the binary map format.
+pub const VERSION: u8 = 1;
+
+
+impl Map {
+	/// Encodes the map into the binary format.
+	pub fn encode(&self) -> Result<Vec<u8>> {
+		let mut w = Writer(Vec::new());
+		w.0.extend_from_slice(MAGIC);
+		w.u8(VERSION);
+		w.u32(self.width);
+		w.u32(self.height);
+		w.u16(self.tile_width)?;
+		w.u16(self.tile_height)?;
+		w.props(&self.properties)?;
+
+		w.len16(self.tilesets.len())?;
+		for tileset in &self.tilesets {
+			w.str(tileset.table_name()?)?;
+			w.u32(tileset.first_gid);
+			w.u32(tileset.tile_count);
+			w.u32(tileset.columns);
+			w.u16(tileset.tile_width)?;
+			w.u16(tileset.tile_height)?;
+			w.len32(tileset.collision.len())?;
+			for (id, rects) in &tileset.collision {
+				w.u32(*id);
+				w.len16(rects.len())?;
+				for rect in rects {
+					for v in [rect.x, rect.y, rect.width, rect.height] {
+						w.i16(v)?;
+					}
+				}
+			}
+		}
+
+		w.len16(self.layers.len())?;
+		for layer in &self.layers {
+			w.u8(match layer.kind {
+				 LayerKind::Tiles { .. } => 0,
+			    LayerKind::Objects(_) => 1,
+			 });
+			w.str(&layer.name)?;
+			w.u8(layer.visible as _);
+			w.i32(layer.offset.0);
+			w.i32(layer.offset.1);
+			w.props(&layer.properties)?;
+
+			match &layer.kind {
+				LayerKind::Tiles { width, height, data } => {
+					if data.len() != (*width as usize) * (*height as usize) {
+						return Err(invalid(format!("Invalid size of layer {} data", layer.name)));
+					}
+					w.u32(*width);
+					w.u32(*height);
+					data.iter().for_each(|gid| w.u32(*gid));
+				},
+				LayerKind::Objects(objects) => {
+					w.len32(objects.len())?;
+					for object in objects {
+						w.object(object)?;
+					}
+				},
+			}
+		}
+
+		Ok(w.0)
+	}
+}
+
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+	fn u8(&mut self, v: u8) { self.0.push(v) }
+	fn u32(&mut self, v: u32) { self.0.extend_from_slice(&v.to_le_bytes()) }
+	fn i32(&mut self, v: i32) { self.0.extend_from_slice(&v.to_le_bytes()) }
+	fn f32(&mut self, v: f32) { self.0.extend_from_slice(&v.to_le_bytes()) }
+
+	fn u16<T: TryInto<u16> + Copy + std::fmt::Display>(&mut self, v: T) -> Result<()> {
+		let v = v.try_into()
+		         .map_err(|_| invalid(format!("Value {v} is out of u16 range")))?;
+		self.0.extend_from_slice(&v.to_le_bytes());
+		Ok(())
+	}
+
+	fn i16(&mut self, v: i32) -> Result<()> {
+		let v = i16::try_from(v).map_err(|_| invalid(format!("Value {v} is out of i16 range")))?;
+		self.0.extend_from_slice(&v.to_le_bytes());
+		Ok(())
+	}
+
+	fn len16(&mut self, len: usize) -> Result<()> { self.u16(len) }
+
+	fn len32(&mut self, len: usize) -> Result<()> {
+		let len = u32::try_from(len).map_err(|_| invalid(format!("Length {len} is out of u32 range")))?;
+		self.u32(len);
+		Ok(())
+	}
+
+	fn str(&mut self, s: &str) -> Result<()> {
+		self.len16(s.len())?;
+		self.0.extend_from_slice(s.as_bytes());
+		Ok(())
+	}
+
+	fn props(&mut self, props: &[Property]) -> Result<()> {
+		self.len16(props.len())?;
+		for prop in props {
+			self.str(&prop.name)?;
+			match &prop.value {
+				PropertyValue::String(s) => {
+					self.u8(0);
+					self.str(s)?;
+				},
+				PropertyValue::Int(v) => {
+					self.u8(1);
+					self.i32(*v);
+				},
+				PropertyValue::Float(v) => {
+					self.u8(2);
+					self.f32(*v);
+				},
+				PropertyValue::Bool(v) => {
+					self.u8(3);
+					self.u8(*v as _);
+				},
+				PropertyValue::Color(v) => {
+					self.u8(4);
+					self.u32(*v);
+				},
+				PropertyValue::File(s) => {
+					self.u8(5);
+					self.str(s)?;
+				},
+				PropertyValue::Object(v) => {
+					self.u8(6);
+					self.u32(*v);
+				},
+			}
+		}
+		Ok(())
+	}
+
+	fn object(&mut self, object: &Object) -> Result<()> {
+		self.u32(object.id);
+		self.str(&object.name)?;
+		self.str(&object.class)?;
+		for v in [object.x, object.y, object.width, object.height] {
+			self.i32(v);
+		}
+		self.u32(object.gid);
+		self.u8(object.shape as _);
+		self.len32(object.points.len())?;
+		for (x, y) in &object.points {
+			self.i32(*x);
+			self.i32(*y);
+		}
+		self.props(&object.properties)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	fn encode() {
+		let map = Map { width: 2,
+		                height: 1,
+		                tile_width: 8,
+		                tile_height: 8,
+		                properties: vec![Property { name: "n".into(),
+		                                            value: PropertyValue::Int(-1) }],
+		                tilesets: vec![],
+		                layers: vec![Layer { name: "l".into(),
+		                                     visible: true,
+		                                     offset: (0, 0),
+		                                     properties: vec![],
+		                                     kind: LayerKind::Tiles { width: 2,
+		                                                              height: 1,
+		                                                              data: vec![1, 0x8000_0002] } }] };
+		let bytes = map.encode().unwrap();
+		#[rustfmt::skip]
+		let expected = [
+			b'P', b'D', b'T', b'M', 1, 2, 0, 0, 0, 1, 0, 0, 0, 8, 0, 8, 0,
+			1, 0, 1, 0, b'n', 1, 0xFF, 0xFF, 0xFF, 0xFF,
+			0, 0,
+			1, 0, 0, 1, 0, b'l', 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+			2, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0x80,
+		];
+		assert_eq!(bytes, expected);
+
+		let mut invalid = map;
+		invalid.tile_width = 70000;
+		assert!(invalid.encode().is_err());
+	}
+}
@@ -0,0 +1,946 @@
+//! Conversion of [Tiled](https://www.mapeditor.org) maps into the compact binary format
+//! read by `playdate-graphics` at runtime (`tilemap::tiled`).
+//!
+//! Conversion is declared in `[package.metadata.playdate.assets]` by mapping
+//! the `.tmj` or `.tmx` map to a target with [`EXTENSION`]:
+//!
+//! ```toml
+//! [package.metadata.playdate.assets]
+//! "levels/one.pdtm" = "maps/one.tmj"
+//! ```
+//!
+//! Images of the tilesets are placed next to the converted map, named as image tables,
+//! e.g. `tiles-table-16-16.png` for `tiles.png` with 16x16 tiles,
+//! so `pdc` compiles them into tables loadable by `BitmapTable::load`.
+//!
+//! Only finite orthogonal maps are supported, layer data should be CSV or Base64, uncompressed or zlib/gzip compressed.
+//! Tilesets with different images should have different image file names, because tables are loaded by name.
+//! Group layers are flattened, image layers are skipped.
+
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+mod xml;
+mod binary;
+
+use self::xml::Element;
+
+
+/// Extension of the converted map.
+pub const EXTENSION: &str = "pdtm";
+
+/// Extensions of the Tiled maps.
+pub const SOURCE_EXTENSIONS: [&str; 2] = ["tmj", "tmx"];
+
+
+/// Returns `true` if the `source` is a Tiled map that should be converted to the `target`.
+pub fn is_conversion(source: &Path, target: &Path) -> bool {
+	let ext = |path: &Path| {
+		path.extension()
+		    .and_then(|ext| ext.to_str())
+		    .map(str::to_ascii_lowercase)
+	};
+	ext(target).is_some_and(|ext| ext == EXTENSION) &&
+	ext(source).is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+
+/// Converts the Tiled map at `source` into the binary map at `target`
+/// and copies images of its tilesets next to it.
+///
+/// Existing images are replaced only if `overwrite` is set.
+///
+/// Returns total number of written bytes.
+pub fn convert(source: &Path, target: &Path, overwrite: bool) -> Result<u64> {
+	let map = Map::load(source)?;
+	let bytes = map.encode()?;
+
+	let dir = crate::fs::parent_of(target)?;
+	let mut written = 0;
+	for tileset in map.tables()?.into_values() {
+		let image = dir.join(tileset.table_file()?);
+		if image.try_exists()? && !overwrite {
+			debug!("tileset {}: {} exists, skipped", tileset.name, image.display());
+			continue;
+		}
+		debug!(
+		       "tileset {}: {} <- {}",
+		       tileset.name,
+		       image.display(),
+		       tileset.image.display()
+		);
+		written += std::fs::copy(&tileset.image, image)?;
+	}
+
+	std::fs::write(target, &bytes)?;
+	Ok(written + bytes.len() as u64)
+}
+
+
+fn invalid<S: Into<String>>(msg: S) -> Error { Error::new(ErrorKind::InvalidData, msg.into()) }
+
+
+/// Tiled map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Map {
+	/// Width in tiles.
+	pub width: u32,
+	/// Height in tiles.
+	pub height: u32,
+	pub tile_width: u32,
+	pub tile_height: u32,
+	pub properties: Vec<Property>,
+	pub tilesets: Vec<Tileset>,
+	pub layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tileset {
+	pub first_gid: u32,
+	pub name: String,
+	/// Absolute path of the image.
+	pub image: PathBuf,
+	pub tile_width: u32,
+	pub tile_height: u32,
+	pub tile_count: u32,
+	pub columns: u32,
+	pub spacing: u32,
+	pub margin: u32,
+	/// Collision rects of the tiles by local tile id, relative to the tile.
+	pub collision: BTreeMap<u32, Vec<Rect>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+	pub x: i32,
+	pub y: i32,
+	pub width: i32,
+	pub height: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+	pub name: String,
+	pub visible: bool,
+	/// Offset in pixels, including offsets of parent groups.
+	pub offset: (i32, i32),
+	pub properties: Vec<Property>,
+	pub kind: LayerKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayerKind {
+	/// Global tile ids with flip flags, row by row.
+	Tiles {
+		width: u32,
+		height: u32,
+		data: Vec<u32>,
+	},
+	Objects(Vec<Object>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Object {
+	pub id: u32,
+	pub name: String,
+	/// Class of the object, `type` in older versions of Tiled.
+	pub class: String,
+	pub x: i32,
+	pub y: i32,
+	pub width: i32,
+	pub height: i32,
+	/// Global tile id of the tile object, zero for others.
+	pub gid: u32,
+	pub shape: Shape,
+	/// Points of polygon and polyline, relative to the object position.
+	pub points: Vec<(i32, i32)>,
+	pub properties: Vec<Property>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+	Rect = 0,
+	Ellipse = 1,
+	Point = 2,
+	Polygon = 3,
+	Polyline = 4,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property {
+	pub name: String,
+	pub value: PropertyValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+	String(String),
+	Int(i32),
+	Float(f32),
+	Bool(bool),
+	/// ARGB color.
+	Color(u32),
+	File(String),
+	/// Id of the referenced object.
+	Object(u32),
+}
+
+
+impl Map {
+	/// Loads the `.tmj` or `.tmx` map with its external tilesets.
+	pub fn load(path: &Path) -> Result<Self> {
+		let source = std::fs::read_to_string(path)?;
+		let dir = crate::fs::parent_of(path)?;
+		let ext = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+		let map = match ext.to_ascii_lowercase().as_str() {
+			"tmj" | "json" => Self::from_json(&serde_json::from_str(&source)?, dir)?,
+			"tmx" => Self::from_xml(&xml::parse(&source)?, dir)?,
+			_ => return Err(invalid(format!("Unknown map format: {}", path.display()))),
+		};
+		Ok(map)
+	}
+
+	/// Returns tilesets by names of their image tables, tilesets sharing the same table once.
+	///
+	/// Tables are loaded by name, so different images or tile sizes with the same name are an error.
+	pub fn tables(&self) -> Result<BTreeMap<&str, &Tileset>> {
+		let mut tables = BTreeMap::<&str, &Tileset>::new();
+		for tileset in &self.tilesets {
+			let name = tileset.table_name()?;
+			match tables.get(name) {
+				Some(other) if !tileset.same_table(other) => {
+					return Err(invalid(format!(
+						"Tilesets {} and {} have different images or tile sizes, but the same image table name {name}",
+						other.name, tileset.name
+					)));
+				},
+				Some(_) => {},
+				None => {
+					tables.insert(name, tileset);
+				},
+			}
+		}
+		Ok(tables)
+	}
+
+	fn check(orientation: Option<&str>, infinite: bool) -> Result<()> {
+		if orientation.is_some_and(|o| o != "orthogonal") {
+			return Err(invalid("Only orthogonal maps are supported"));
+		}
+		if infinite {
+			return Err(invalid("Infinite maps are not supported"));
+		}
+		Ok(())
+	}
+}
+
+impl Tileset {
+	/// Returns name of the image table, the file stem of the image.
+	pub fn table_name(&self) -> Result<&str> {
+		self.image.file_stem().and_then(|s| s.to_str()).ok_or_else(|| {
+			                                               invalid(format!(
+				"Invalid image path of tileset {}",
+				self.name
+			))
+		                                               })
+	}
+
+	fn same_table(&self, other: &Tileset) -> bool {
+		self.image == other.image && self.tile_width == other.tile_width && self.tile_height == other.tile_height
+	}
+
+	/// Returns file name of the image table, e.g. `tiles-table-16-16.png`.
+	pub fn table_file(&self) -> Result<String> {
+		if self.spacing != 0 || self.margin != 0 {
+			return Err(invalid(format!(
+				"Tileset {} has spacing or margin, image tables can't have them",
+				self.name
+			)));
+		}
+		let ext = self.image.extension().and_then(|s| s.to_str()).unwrap_or("png");
+		Ok(format!(
+		           "{}-table-{}-{}.{ext}",
+		           self.table_name()?,
+		           self.tile_width,
+		           self.tile_height
+		))
+	}
+}
+
+impl Object {
+	/// Returns bounding box of the object, `None` for points.
+	fn bounds(&self) -> Option<Rect> {
+		match self.shape {
+			Shape::Point => None,
+			Shape::Rect | Shape::Ellipse => {
+				Some(Rect { x: self.x,
+				            y: self.y,
+				            width: self.width,
+				            height: self.height })
+			},
+			Shape::Polygon | Shape::Polyline => {
+				let xs = self.points.iter().map(|p| p.0);
+				let ys = self.points.iter().map(|p| p.1);
+				let (left, right) = (xs.clone().min()?, xs.max()?);
+				let (top, bottom) = (ys.clone().min()?, ys.max()?);
+				Some(Rect { x: self.x + left,
+				            y: self.y + top,
+				            width: right - left,
+				            height: bottom - top })
+			},
+		}
+	}
+}
+
+/// Returns collision rects of the tile with `objects` of its object group and `properties`.
+///
+/// Tiles without collision shapes, but with `solid` or `collides` property set, are entirely solid.
+fn tile_collision(objects: &[Object], properties: &[Property], width: u32, height: u32) -> Vec<Rect> {
+	let mut rects: Vec<_> = objects.iter().filter_map(Object::bounds).collect();
+	let solid =
+		properties.iter()
+		          .any(|p| matches!(p.name.as_str(), "solid" | "collides") && p.value == PropertyValue::Bool(true));
+	if rects.is_empty() && solid {
+		rects.push(Rect { x: 0,
+		                  y: 0,
+		                  width: width as _,
+		                  height: height as _ });
+	}
+	rects
+}
+
+
+fn property_value(ty: &str, value: &str) -> Result<PropertyValue> {
+	let err = || invalid(format!("Invalid {ty} property value: {value}"));
+	let value = match ty {
+		"" | "string" => PropertyValue::String(value.to_owned()),
+		"file" => PropertyValue::File(value.to_owned()),
+		"int" => PropertyValue::Int(value.parse().ok().ok_or_else(err)?),
+		"float" => PropertyValue::Float(value.parse().ok().ok_or_else(err)?),
+		"bool" => PropertyValue::Bool(value.parse().ok().ok_or_else(err)?),
+		"object" => PropertyValue::Object(value.parse().ok().ok_or_else(err)?),
+		"color" => PropertyValue::Color(parse_color(value)?),
+		_ => return Err(invalid(format!("Unsupported property type: {ty}"))),
+	};
+	Ok(value)
+}
+
+/// Parses `#AARRGGBB` or `#RRGGBB` color into ARGB, empty is transparent.
+fn parse_color(value: &str) -> Result<u32> {
+	let hex = value.trim_start_matches('#');
+	let argb = u32::from_str_radix(hex, 16).map_err(|_| invalid(format!("Invalid color: {value}")));
+	match hex.len() {
+		0 => Ok(0),
+		6 => Ok(0xFF00_0000 | argb?),
+		8 => argb,
+		_ => Err(invalid(format!("Invalid color: {value}"))),
+	}
+}
+
+fn round(v: f64) -> i32 { v.round() as i32 }
+
+
+fn decode_gids(data: &str, encoding: &str, compression: &str) -> Result<Vec<u32>> {
+	let bytes = match encoding {
+		"csv" => {
+			if !compression.is_empty() {
+				return Err(invalid(format!("Compressed ({compression}) CSV layer data is not supported")));
+			}
+			return data.split(',')
+			           .map(str::trim)
+			           .filter(|s| !s.is_empty())
+			           .map(|s| s.parse().map_err(|_| invalid(format!("Invalid tile: {s}"))))
+			           .collect();
+		},
+		"base64" => {
+			use base64::Engine;
+			let data: String = data.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+			let bytes =
+				base64::engine::general_purpose::STANDARD.decode(data)
+				                                         .map_err(|err| invalid(format!("Invalid Base64 data: {err}")))?;
+			decompress(bytes, compression)?
+		},
+		_ => return Err(invalid(format!("Unsupported layer encoding: {encoding}"))),
+	};
+
+	if bytes.len() % 4 != 0 {
+		return Err(invalid("Invalid length of layer data"));
+	}
+	Ok(bytes.chunks(4)
+	        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+	        .collect())
+}
+
+fn decompress(bytes: Vec<u8>, compression: &str) -> Result<Vec<u8>> {
+	use std::io::Read;
+	let mut out = Vec::new();
+	match compression {
+		"" => return Ok(bytes),
+		"zlib" => flate2::read::ZlibDecoder::new(bytes.as_slice()).read_to_end(&mut out)?,
+		"gzip" => flate2::read::GzDecoder::new(bytes.as_slice()).read_to_end(&mut out)?,
+		_ => {
+			return Err(invalid(format!("Compressed ({compression}) layer data is not supported, use zlib, gzip or no compression")));
+		},
+	};
+	Ok(out)
+}
+
+
+// JSON, `.tmj` & `.tsj` //
+
+fn json_u32(v: &Value, key: &str) -> Result<u32> {
+	v.get(key)
+	 .and_then(Value::as_u64)
+	 .and_then(|v| u32::try_from(v).ok())
+	 .ok_or_else(|| invalid(format!("Missing or invalid `{key}`")))
+}
+
+fn json_num(v: &Value, key: &str) -> f64 { v.get(key).and_then(Value::as_f64).unwrap_or_default() }
+fn json_str<'v>(v: &'v Value, key: &str) -> &'v str { v.get(key).and_then(Value::as_str).unwrap_or_default() }
+fn json_bool(v: &Value, key: &str, default: bool) -> bool {
+	v.get(key).and_then(Value::as_bool).unwrap_or(default)
+}
+fn json_array<'v>(v: &'v Value, key: &str) -> &'v [Value] {
+	v.get(key)
+	 .and_then(Value::as_array)
+	 .map(Vec::as_slice)
+	 .unwrap_or_default()
+}
+
+impl Map {
+	fn from_json(v: &Value, dir: &Path) -> Result<Self> {
+		Self::check(
+		            v.get("orientation").and_then(Value::as_str),
+		            json_bool(v, "infinite", false),
+		)?;
+
+		let mut tilesets = Vec::new();
+		for ts in json_array(v, "tilesets") {
+			let first_gid = json_u32(ts, "firstgid")?;
+			let tileset = match ts.get("source").and_then(Value::as_str) {
+				Some(source) => {
+					let path = dir.join(source);
+					let dir = crate::fs::parent_of(&path)?;
+					match path.extension().and_then(|ext| ext.to_str()) {
+						Some("tsx") => {
+							Tileset::from_xml(&xml::parse(&std::fs::read_to_string(&path)?)?, dir, first_gid)?
+						},
+						_ => {
+							let value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+							Tileset::from_json(&value, dir, first_gid)?
+						},
+					}
+				},
+				None => Tileset::from_json(ts, dir, first_gid)?,
+			};
+			tilesets.push(tileset);
+		}
+
+		let mut layers = Vec::new();
+		json_layers(json_array(v, "layers"), (0, 0), true, &mut layers)?;
+
+		Ok(Self { width: json_u32(v, "width")?,
+		          height: json_u32(v, "height")?,
+		          tile_width: json_u32(v, "tilewidth")?,
+		          tile_height: json_u32(v, "tileheight")?,
+		          properties: json_properties(v)?,
+		          tilesets,
+		          layers })
+	}
+}
+
+impl Tileset {
+	fn from_json(v: &Value, dir: &Path, first_gid: u32) -> Result<Self> {
+		let name = json_str(v, "name").to_owned();
+		let image =
+			v.get("image")
+			 .and_then(Value::as_str)
+			 .ok_or_else(|| invalid(format!("Tileset {name} is not single image, collections are not supported")))?;
+		let (tile_width, tile_height) = (json_u32(v, "tilewidth")?, json_u32(v, "tileheight")?);
+
+		let mut collision = BTreeMap::new();
+		for tile in json_array(v, "tiles") {
+			let objects = tile.get("objectgroup")
+			                  .map(|group| {
+				                  json_array(group, "objects").iter()
+				                                              .map(json_object)
+				                                              .collect::<Result<Vec<_>>>()
+			                  })
+			                  .transpose()?
+			                  .unwrap_or_default();
+			let rects = tile_collision(&objects, &json_properties(tile)?, tile_width, tile_height);
+			if !rects.is_empty() {
+				collision.insert(json_u32(tile, "id")?, rects);
+			}
+		}
+
+		Ok(Self { first_gid,
+		          image: dir.join(image),
+		          tile_width,
+		          tile_height,
+		          tile_count: json_u32(v, "tilecount")?,
+		          columns: json_u32(v, "columns")?,
+		          spacing: json_u32(v, "spacing").unwrap_or_default(),
+		          margin: json_u32(v, "margin").unwrap_or_default(),
+		          collision,
+		          name })
+	}
+}
+
+fn json_layers(values: &[Value], offset: (i32, i32), visible: bool, out: &mut Vec<Layer>) -> Result<()> {
+	for v in values {
+		let offset = (offset.0 + round(json_num(v, "offsetx")), offset.1 + round(json_num(v, "offsety")));
+		let visible = visible && json_bool(v, "visible", true);
+		let kind = match json_str(v, "type") {
+			"group" => {
+				json_layers(json_array(v, "layers"), offset, visible, out)?;
+				continue;
+			},
+			"tilelayer" => {
+				let data = match v.get("data") {
+					Some(Value::String(data)) => {
+						decode_gids(data, json_str(v, "encoding"), json_str(v, "compression"))?
+					},
+					Some(Value::Array(data)) => {
+						data.iter()
+						    .map(|gid| gid.as_u64().and_then(|gid| u32::try_from(gid).ok()))
+						    .collect::<Option<_>>()
+						    .ok_or_else(|| invalid("Invalid layer data"))?
+					},
+					_ => return Err(invalid("Missing layer data")),
+				};
+				LayerKind::Tiles { width: json_u32(v, "width")?,
+				                   height: json_u32(v, "height")?,
+				                   data }
+			},
+			"objectgroup" => {
+				LayerKind::Objects(json_array(v, "objects").iter()
+				                                           .map(json_object)
+				                                           .collect::<Result<_>>()?)
+			},
+			ty => {
+				warn!("Tiled layer {} of type {ty} is skipped", json_str(v, "name"));
+				continue;
+			},
+		};
+		out.push(Layer { name: json_str(v, "name").to_owned(),
+		                 visible,
+		                 offset,
+		                 properties: json_properties(v)?,
+		                 kind });
+	}
+	Ok(())
+}
+
+fn json_object(v: &Value) -> Result<Object> {
+	let points = |key| {
+		json_array(v, key).iter()
+		                  .map(|p| (round(json_num(p, "x")), round(json_num(p, "y"))))
+		                  .collect::<Vec<_>>()
+	};
+	let (shape, points) = if json_bool(v, "ellipse", false) {
+		(Shape::Ellipse, Vec::new())
+	} else if json_bool(v, "point", false) {
+		(Shape::Point, Vec::new())
+	} else if v.get("polygon").is_some() {
+		(Shape::Polygon, points("polygon"))
+	} else if v.get("polyline").is_some() {
+		(Shape::Polyline, points("polyline"))
+	} else {
+		(Shape::Rect, Vec::new())
+	};
+	let class = v.get("class")
+	             .or(v.get("type"))
+	             .and_then(Value::as_str)
+	             .unwrap_or_default();
+
+	Ok(Object { id: json_u32(v, "id").unwrap_or_default(),
+	            name: json_str(v, "name").to_owned(),
+	            class: class.to_owned(),
+	            x: round(json_num(v, "x")),
+	            y: round(json_num(v, "y")),
+	            width: round(json_num(v, "width")),
+	            height: round(json_num(v, "height")),
+	            gid: json_u32(v, "gid").unwrap_or_default(),
+	            shape,
+	            points,
+	            properties: json_properties(v)? })
+}
+
+fn json_properties(v: &Value) -> Result<Vec<Property>> {
+	json_array(v, "properties").iter()
+	                           .map(|p| {
+		                           let ty = json_str(p, "type");
+		                           let value = match p.get("value") {
+			                           Some(Value::String(s)) => s.clone(),
+		                              Some(value) => value.to_string(),
+		                              None => String::new(),
+		                           };
+		                           Ok(Property { name: json_str(p, "name").to_owned(),
+		                                         value: property_value(ty, &value)? })
+	                           })
+	                           .collect()
+}
+
+
+// XML, `.tmx` & `.tsx` //
+
+fn xml_attr<T: std::str::FromStr>(e: &Element, name: &str) -> Result<T> {
+	e.attr(name)
+	 .and_then(|v| v.parse().ok())
+	 .ok_or_else(|| invalid(format!("Missing or invalid `{name}` of <{}>", e.name)))
+}
+
+fn xml_num(e: &Element, name: &str) -> f64 { e.attr(name).and_then(|v| v.parse().ok()).unwrap_or_default() }
+
+impl Map {
+	fn from_xml(e: &Element, dir: &Path) -> Result<Self> {
+		Self::check(e.attr("orientation"), e.attr("infinite") == Some("1"))?;
+
+		let mut tilesets = Vec::new();
+		for ts in e.children("tileset") {
+			let first_gid = xml_attr(ts, "firstgid")?;
+			let tileset = match ts.attr("source") {
+				Some(source) => {
+					let path = dir.join(source);
+					let dir = crate::fs::parent_of(&path)?;
+					let source = std::fs::read_to_string(&path)?;
+					match path.extension().and_then(|ext| ext.to_str()) {
+						Some("tsj" | "json") => Tileset::from_json(&serde_json::from_str(&source)?, dir, first_gid)?,
+						_ => Tileset::from_xml(&xml::parse(&source)?, dir, first_gid)?,
+					}
+				},
+				None => Tileset::from_xml(ts, dir, first_gid)?,
+			};
+			tilesets.push(tileset);
+		}
+
+		let mut layers = Vec::new();
+		xml_layers(e, (0, 0), true, &mut layers)?;
+
+		Ok(Self { width: xml_attr(e, "width")?,
+		          height: xml_attr(e, "height")?,
+		          tile_width: xml_attr(e, "tilewidth")?,
+		          tile_height: xml_attr(e, "tileheight")?,
+		          properties: xml_properties(e)?,
+		          tilesets,
+		          layers })
+	}
+}
+
+impl Tileset {
+	fn from_xml(e: &Element, dir: &Path, first_gid: u32) -> Result<Self> {
+		let name = e.attr("name").unwrap_or_default().to_owned();
+		let image =
+			e.child("image")
+			 .and_then(|image| image.attr("source"))
+			 .ok_or_else(|| invalid(format!("Tileset {name} is not single image, collections are not supported")))?;
+		let (tile_width, tile_height) = (xml_attr(e, "tilewidth")?, xml_attr(e, "tileheight")?);
+
+		let mut collision = BTreeMap::new();
+		for tile in e.children("tile") {
+			let objects = tile.child("objectgroup")
+			                  .map(|group| {
+				                  group.children("object")
+				                       .map(xml_object)
+				                       .collect::<Result<Vec<_>>>()
+			                  })
+			                  .transpose()?
+			                  .unwrap_or_default();
+			let rects = tile_collision(&objects, &xml_properties(tile)?, tile_width, tile_height);
+			if !rects.is_empty() {
+				collision.insert(xml_attr(tile, "id")?, rects);
+			}
+		}
+
+		Ok(Self { first_gid,
+		          image: dir.join(image),
+		          tile_width,
+		          tile_height,
+		          tile_count: xml_attr(e, "tilecount")?,
+		          columns: xml_attr(e, "columns")?,
+		          spacing: xml_attr(e, "spacing").unwrap_or_default(),
+		          margin: xml_attr(e, "margin").unwrap_or_default(),
+		          collision,
+		          name })
+	}
+}
+
+fn xml_layers(parent: &Element, offset: (i32, i32), visible: bool, out: &mut Vec<Layer>) -> Result<()> {
+	for e in &parent.children {
+		let offset = (offset.0 + round(xml_num(e, "offsetx")), offset.1 + round(xml_num(e, "offsety")));
+		let visible = visible && e.attr("visible") != Some("0");
+		let kind = match e.name.as_str() {
+			"group" => {
+				xml_layers(e, offset, visible, out)?;
+				continue;
+			},
+			"layer" => {
+				let data = e.child("data").ok_or_else(|| invalid("Missing layer data"))?;
+				if data.child("chunk").is_some() {
+					return Err(invalid("Infinite maps are not supported"));
+				}
+				let data = if let Some(encoding) = data.attr("encoding") {
+					decode_gids(&data.text, encoding, data.attr("compression").unwrap_or_default())?
+				} else {
+					data.children("tile")
+					    .map(|tile| xml_attr(tile, "gid").unwrap_or_default())
+					    .collect()
+				};
+				LayerKind::Tiles { width: xml_attr(e, "width")?,
+				                   height: xml_attr(e, "height")?,
+				                   data }
+			},
+			"objectgroup" => LayerKind::Objects(e.children("object").map(xml_object).collect::<Result<_>>()?),
+			"imagelayer" => {
+				warn!(
+				      "Tiled image layer {} is skipped",
+				      e.attr("name").unwrap_or_default()
+				);
+				continue;
+			},
+			_ => continue,
+		};
+		out.push(Layer { name: e.attr("name").unwrap_or_default().to_owned(),
+		                 visible,
+		                 offset,
+		                 properties: xml_properties(e)?,
+		                 kind });
+	}
+	Ok(())
+}
+
+fn xml_object(e: &Element) -> Result<Object> {
+	let points = |child: &Element| -> Result<Vec<(i32, i32)>> {
+		child.attr("points")
+		     .unwrap_or_default()
+		     .split_whitespace()
+		     .map(|p| {
+			     let (x, y) = p.split_once(',')
+			                   .ok_or_else(|| invalid(format!("Invalid point: {p}")))?;
+			     let parse = |v: &str| {
+				     v.parse()
+				      .map(round)
+				      .map_err(|_| invalid(format!("Invalid point: {p}")))
+			     };
+			     Ok((parse(x)?, parse(y)?))
+		     })
+		     .collect()
+	};
+	let (shape, points) = if e.child("ellipse").is_some() {
+		(Shape::Ellipse, Vec::new())
+	} else if e.child("point").is_some() {
+		(Shape::Point, Vec::new())
+	} else if let Some(polygon) = e.child("polygon") {
+		(Shape::Polygon, points(polygon)?)
+	} else if let Some(polyline) = e.child("polyline") {
+		(Shape::Polyline, points(polyline)?)
+	} else {
+		(Shape::Rect, Vec::new())
+	};
+	let class = e.attr("class").or(e.attr("type")).unwrap_or_default();
+
+	Ok(Object { id: xml_attr(e, "id").unwrap_or_default(),
+	            name: e.attr("name").unwrap_or_default().to_owned(),
+	            class: class.to_owned(),
+	            x: round(xml_num(e, "x")),
+	            y: round(xml_num(e, "y")),
+	            width: round(xml_num(e, "width")),
+	            height: round(xml_num(e, "height")),
+	            gid: xml_attr(e, "gid").unwrap_or_default(),
+	            shape,
+	            points,
+	            properties: xml_properties(e)? })
+}
+
+fn xml_properties(e: &Element) -> Result<Vec<Property>> {
+	let Some(properties) = e.child("properties") else {
+		return Ok(Vec::new());
+	};
+	properties.children("property")
+	          .map(|p| {
+		          // Multiline strings are stored as text:
+		          let value = p.attr("value").unwrap_or(&p.text);
+		          Ok(Property { name: p.attr("name").unwrap_or_default().to_owned(),
+		                        value: property_value(p.attr("type").unwrap_or_default(), value)? })
+	          })
+	          .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	const TMJ: &str = r##"{
+		"orientation": "orthogonal", "infinite": false,
+		"width": 3, "height": 2, "tilewidth": 16, "tileheight": 16,
+		"properties": [{ "name": "music", "type": "file", "value": "sfx/theme" }],
+		"tilesets": [{
+			"firstgid": 1, "name": "tiles", "image": "img/tiles.png",
+			"tilewidth": 16, "tileheight": 16, "tilecount": 4, "columns": 2,
+			"tiles": [
+				{ "id": 1, "properties": [{ "name": "solid", "type": "bool", "value": true }] },
+				{ "id": 2, "objectgroup": { "objects": [{ "id": 1, "x": 0, "y": 8, "width": 16, "height": 8 }] } }
+			]
+		}],
+		"layers": [
+			{ "type": "tilelayer", "name": "ground", "width": 3, "height": 2, "visible": true,
+			  "data": [1, 2, 3, 0, 2147483650, 4] },
+			{ "type": "group", "name": "things", "offsetx": 2, "visible": false, "layers": [
+				{ "type": "objectgroup", "name": "spawns", "offsety": 1.6, "objects": [
+					{ "id": 7, "name": "hero", "type": "player", "x": 10.4, "y": 20, "width": 0, "height": 0, "point": true,
+					  "properties": [{ "name": "hp", "type": "int", "value": 3 },
+					                 { "name": "tint", "type": "color", "value": "#80ff0000" }] },
+					{ "id": 8, "x": 0, "y": 0, "polygon": [{ "x": 0, "y": 0 }, { "x": 4, "y": -2 }] }
+				] }
+			] }
+		]
+	}"##;
+
+	const TMX: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+		<map version="1.10" orientation="orthogonal" width="3" height="2" tilewidth="16" tileheight="16" infinite="0">
+			<properties><property name="music" type="file" value="sfx/theme"/></properties>
+			<tileset firstgid="1" name="tiles" tilewidth="16" tileheight="16" tilecount="4" columns="2">
+				<image source="img/tiles.png" width="32" height="32"/>
+				<tile id="1"><properties><property name="solid" type="bool" value="true"/></properties></tile>
+				<tile id="2"><objectgroup><object id="1" x="0" y="8" width="16" height="8"/></objectgroup></tile>
+			</tileset>
+			<layer id="1" name="ground" width="3" height="2">
+				<data encoding="csv">1,2,3,
+				0,2147483650,4</data>
+			</layer>
+			<group name="things" offsetx="2" visible="0">
+				<objectgroup name="spawns" offsety="1.6">
+					<object id="7" name="hero" type="player" x="10.4" y="20">
+						<properties>
+							<property name="hp" type="int" value="3"/>
+							<property name="tint" type="color" value="#80ff0000"/>
+						</properties>
+						<point/>
+					</object>
+					<object id="8" x="0" y="0"><polygon points="0,0 4,-2"/></object>
+				</objectgroup>
+			</group>
+		</map>"##;
+
+
+	#[test]
+	fn json_and_xml_are_same() {
+		let dir = Path::new("/maps");
+		let json = Map::from_json(&serde_json::from_str(TMJ).unwrap(), dir).unwrap();
+		let xml = Map::from_xml(&xml::parse(TMX).unwrap(), dir).unwrap();
+		assert_eq!(json, xml);
+
+		let tileset = &json.tilesets[0];
+		assert_eq!(tileset.image, Path::new("/maps/img/tiles.png"));
+		assert_eq!(json.tables().unwrap().len(), json.tilesets.len());
+		assert_eq!(tileset.table_file().unwrap(), "tiles-table-16-16.png");
+		assert_eq!(
+		           tileset.collision[&1],
+		           [Rect { x: 0,
+		                   y: 0,
+		                   width: 16,
+		                   height: 16 }]
+		);
+		assert_eq!(
+		           tileset.collision[&2],
+		           [Rect { x: 0,
+		                   y: 8,
+		                   width: 16,
+		                   height: 8 }]
+		);
+
+		assert_eq!(json.layers.len(), 2);
+		assert!(matches!(&json.layers[0].kind, LayerKind::Tiles { data, .. } if data[4] == 0x8000_0002));
+		let spawns = &json.layers[1];
+		assert_eq!(
+		           (spawns.name.as_str(), spawns.visible, spawns.offset),
+		           ("spawns", false, (2, 2))
+		);
+		let LayerKind::Objects(objects) = &spawns.kind else {
+			panic!()
+		};
+		assert_eq!(
+		           (objects[0].class.as_str(), objects[0].x, objects[0].shape),
+		           ("player", 10, Shape::Point)
+		);
+		assert_eq!(objects[0].properties[1].value, PropertyValue::Color(0x80FF_0000));
+		assert_eq!(
+		           objects[1].bounds(),
+		           Some(Rect { x: 0,
+		                       y: -2,
+		                       width: 4,
+		                       height: 2 })
+		);
+	}
+
+	#[test]
+	fn table_collision() {
+		let mut map = Map::from_json(&serde_json::from_str(TMJ).unwrap(), Path::new("/maps")).unwrap();
+		let mut shared = map.tilesets[0].clone();
+		shared.first_gid += shared.tile_count;
+		map.tilesets.push(shared.clone());
+		assert_eq!(map.tables().unwrap().len(), map.tilesets.len() - 1);
+
+		shared.image = PathBuf::from("/maps/other/tiles.png");
+		map.tilesets.push(shared);
+		assert!(map.tables().is_err());
+	}
+
+	#[test]
+	fn unsupported() {
+		let dir = Path::new("/maps");
+		let infinite = TMX.replace(r#"infinite="0""#, r#"infinite="1""#);
+		assert!(Map::from_xml(&xml::parse(&infinite).unwrap(), dir).is_err());
+		let iso = TMJ.replace("orthogonal", "isometric");
+		assert!(Map::from_json(&serde_json::from_str(&iso).unwrap(), dir).is_err());
+		assert!(decode_gids("AAAA", "base64", "zlib").is_err());
+		assert!(decode_gids("KLUv/QBYAQAA", "base64", "zstd").is_err());
+		assert!(decode_gids("1,2", "csv", "gzip").is_err());
+	}
+
+	#[test]
+	fn base64() {
+		assert_eq!(
+		           decode_gids("AQAAAAIAAIA=", "base64", "").unwrap(),
+		           [1, 0x8000_0002]
+		);
+		assert!(decode_gids("AQAAAAIAAIA", "base64", "").is_err());
+	}
+
+	#[test]
+	fn compressed() {
+		use std::io::Write;
+		use base64::Engine;
+		use flate2::Compression;
+
+		let gids = [1_u32, 0x8000_0002, 3];
+		let raw: Vec<u8> = gids.iter().flat_map(|gid| gid.to_le_bytes()).collect();
+		let encode = |bytes: Vec<u8>| base64::engine::general_purpose::STANDARD.encode(bytes);
+
+		let mut zlib = flate2::write::ZlibEncoder::new(Vec::new(), Compression::default());
+		zlib.write_all(&raw).unwrap();
+		let zlib = encode(zlib.finish().unwrap());
+		assert_eq!(decode_gids(&zlib, "base64", "zlib").unwrap(), gids);
+
+		let mut gzip = flate2::write::GzEncoder::new(Vec::new(), Compression::default());
+		gzip.write_all(&raw).unwrap();
+		let gzip = encode(gzip.finish().unwrap());
+		assert_eq!(
+		           decode_gids(&format!("\n  {gzip}\n"), "base64", "gzip").unwrap(),
+		           gids
+		);
+	}
+
+	#[test]
+	fn conversion() {
+		assert!(is_conversion(Path::new("maps/one.tmj"), Path::new("levels/one.pdtm")));
+		assert!(is_conversion(Path::new("maps/one.TMX"), Path::new("one.pdtm")));
+		assert!(!is_conversion(Path::new("maps/one.tmj"), Path::new("levels/one.tmj")));
+	}
+}
@@ -0,0 +1,148 @@
+//! Tiled `.tmx` and `.tsx` documents read with `quick-xml` into trees of elements.
+//!
+//! Keeps elements, attributes, text and CDATA, skips declarations, comments and doctypes.
+
+use std::io::{Error, ErrorKind};
+
+use quick_xml::escape::resolve_xml_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Element {
+	pub name: String,
+	pub attrs: Vec<(String, String)>,
+	pub children: Vec<Element>,
+	pub text: String,
+}
+
+impl Element {
+	pub fn attr(&self, name: &str) -> Option<&str> {
+		self.attrs
+		    .iter()
+		    .find(|(k, _)| k == name)
+		    .map(|(_, v)| v.as_str())
+	}
+
+	pub fn child(&self, name: &str) -> Option<&Element> { self.children.iter().find(|e| e.name == name) }
+
+	pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+		self.children.iter().filter(move |e| e.name == name)
+	}
+}
+
+
+/// Parses the document, returns its root element.
+pub fn parse(source: &str) -> Result<Element, Error> {
+	let mut reader = Reader::from_str(source);
+	let error = |pos: usize, msg: &dyn std::fmt::Display| {
+		let line = source.bytes().take(pos).filter(|b| *b == b'\n').count() + 1;
+		Error::new(
+		           ErrorKind::InvalidData,
+		           format!("Invalid XML at line {line}: {msg}"),
+		)
+	};
+
+	// Open elements, the innermost is last:
+	let mut open: Vec<Element> = Vec::new();
+	let mut root = None;
+	loop {
+		let event = reader.read_event()
+		                  .map_err(|err| error(reader.error_position(), &err))?;
+		let pos = reader.buffer_position();
+		let closed = match event {
+			Event::Start(start) => {
+				open.push(element(&start).map_err(|err| error(pos, &err))?);
+				None
+			},
+			Event::Empty(start) => Some(element(&start).map_err(|err| error(pos, &err))?),
+			Event::End(_) => open.pop(),
+			Event::Text(text) => {
+				let text = text.unescape().map_err(|err| error(pos, &err))?;
+				match open.last_mut() {
+					Some(element) => element.text.push_str(&text),
+					None if text.trim().is_empty() => {},
+					None => return Err(error(pos, &"text outside of the root element")),
+				}
+				None
+			},
+			Event::CData(cdata) => {
+				let text = std::str::from_utf8(&cdata).map_err(|err| error(pos, &err))?;
+				open.last_mut()
+				    .ok_or_else(|| error(pos, &"CDATA outside of the root element"))?
+				    .text
+				    .push_str(text);
+				None
+			},
+			Event::Eof => break,
+			_ => None,
+		};
+
+		if let Some(element) = closed {
+			match open.last_mut() {
+				Some(parent) => parent.children.push(element),
+				None if root.is_none() => root = Some(element),
+				None => return Err(error(pos, &"content after the root element")),
+			}
+		}
+	}
+
+	if !open.is_empty() {
+		return Err(error(source.len(), &"unclosed element"));
+	}
+	root.ok_or_else(|| error(source.len(), &"no root element"))
+}
+
+
+fn element(start: &BytesStart) -> Result<Element, Error> {
+	let utf8 = |bytes: &[u8]| {
+		std::str::from_utf8(bytes).map(str::to_owned)
+		                          .map_err(|err| Error::new(ErrorKind::InvalidData, err))
+	};
+	let mut element = Element { name: utf8(start.name().as_ref())?,
+	                            ..Default::default() };
+	for attr in start.attributes() {
+		let attr = attr.map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+		let value = attr.unescape_value_with(resolve_xml_entity)
+		                .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+		element.attrs.push((utf8(attr.key.as_ref())?, value.into_owned()));
+	}
+	Ok(element)
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	fn parse_document() {
+		let doc = r#"<?xml version="1.0" encoding="UTF-8"?>
+			<!-- comment -->
+			<map a="1" b='x &amp; y'>
+				<layer name="ground"><data encoding="csv">
+					1,2,
+					3,4
+				</data></layer>
+				<property name="note"><![CDATA[<raw>]]> &#x41;</property>
+				<empty/>
+			</map>"#;
+		let root = parse(doc).unwrap();
+		assert_eq!(root.name, "map");
+		assert_eq!(root.attr("b"), Some("x & y"));
+		let data = root.child("layer").and_then(|l| l.child("data")).unwrap();
+		assert_eq!(
+		           data.text.split(',').map(|s| s.trim()).collect::<Vec<_>>(),
+		           ["1", "2", "3", "4"]
+		);
+		assert_eq!(root.child("property").unwrap().text, "<raw> A");
+		assert_eq!(root.children("empty").count(), 1);
+
+		assert!(parse("<a><b></a>").is_err());
+		assert!(parse("<a/><b/>").is_err());
+		assert!(parse("<a><b/>").is_err());
+		assert!(parse("<a b='1' b='2'/>").is_err());
+	}
+}